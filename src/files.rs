@@ -0,0 +1,347 @@
+use std::{
+    convert::TryFrom,
+    io::{self, ErrorKind, Read, Seek, SeekFrom},
+};
+
+use crate::{
+    hostcalls::{hostcall_file_create, hostcall_file_read},
+    value_presenter::value::{file_object::FileObject, uuid::Uuid},
+};
+
+/// The most bytes read from the host at once.
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+type ReadAt = fn(&str, u64, &mut [u8]) -> io::Result<usize>;
+type Upload = fn(&str, &str, &[u8]) -> io::Result<[u8; 16]>;
+
+/// A reader over the contents of a file object, reading from the host in
+/// chunks of at most `CHUNK_SIZE` bytes and never past its `filesize`.
+#[derive(Debug)]
+pub struct FileReader {
+    object_uuid: String,
+    filesize: u64,
+    position: u64,
+    read_at: ReadAt,
+}
+
+impl FileReader {
+    pub fn new(file_object: &FileObject) -> Self {
+        Self {
+            object_uuid: file_object.object_uuid.to_string(),
+            filesize: file_object.filesize,
+            position: 0,
+            read_at: host_read_at,
+        }
+    }
+
+    pub fn filesize(&self) -> u64 {
+        self.filesize
+    }
+
+    /// Reads all the remaining contents, failing with `UnexpectedEof` if the
+    /// host has fewer bytes than `filesize`.
+    pub fn read_remaining(&mut self) -> io::Result<Vec<u8>> {
+        let remaining = self.filesize.saturating_sub(self.position);
+        // `filesize` comes from the host, so the buffer grows as bytes are read
+        // rather than being allocated up front
+        let mut bytes = Vec::with_capacity(remaining.min(CHUNK_SIZE as u64) as usize);
+
+        self.read_to_end(&mut bytes)?;
+
+        if (bytes.len() as u64) < remaining {
+            return Err(io::Error::new(
+                ErrorKind::UnexpectedEof,
+                "file object contents are shorter than its filesize",
+            ));
+        }
+
+        Ok(bytes)
+    }
+}
+
+impl Read for FileReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.filesize.saturating_sub(self.position);
+        let len = buf
+            .len()
+            .min(CHUNK_SIZE)
+            .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let read = (self.read_at)(&self.object_uuid, self.position, &mut buf[..len])?;
+
+        if read > len {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "host read more bytes than requested",
+            ));
+        }
+
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl Seek for FileReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => add_offset(self.filesize, offset),
+            SeekFrom::Current(offset) => add_offset(self.position, offset),
+        };
+
+        match position {
+            Some(position) => {
+                self.position = position;
+                Ok(position)
+            }
+            None => Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+/// Uploads `bytes` as a new file object, returning it with the `object_uuid`
+/// the host gave it.
+pub fn create(filename: &str, mimetype: &str, bytes: &[u8]) -> io::Result<FileObject> {
+    create_with(host_upload, filename, mimetype, bytes)
+}
+
+fn create_with(
+    upload: Upload,
+    filename: &str,
+    mimetype: &str,
+    bytes: &[u8],
+) -> io::Result<FileObject> {
+    if filename.is_empty() {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "file object filename is empty",
+        ));
+    }
+
+    let object_uuid = Uuid::from_bytes(upload(filename, mimetype, bytes)?);
+
+    if object_uuid.is_nil() {
+        return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            "host gave the file object a nil object_uuid",
+        ));
+    }
+
+    Ok(FileObject::new(
+        object_uuid,
+        filename.to_string(),
+        bytes.len() as u64,
+        mimetype.to_string(),
+    ))
+}
+
+fn add_offset(base: u64, offset: i64) -> Option<u64> {
+    if offset >= 0 {
+        base.checked_add(offset as u64)
+    } else {
+        base.checked_sub(offset.unsigned_abs())
+    }
+}
+
+fn host_read_at(object_uuid: &str, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+    let read = unsafe {
+        hostcall_file_read(
+            object_uuid.as_ptr(),
+            object_uuid.len(),
+            offset,
+            buf.as_mut_ptr(),
+            buf.len(),
+        )
+    };
+
+    if read >= 0 {
+        return Ok(read as usize);
+    }
+
+    let kind = match read {
+        -1 => ErrorKind::NotFound,
+        -2 => ErrorKind::PermissionDenied,
+        _ => ErrorKind::Other,
+    };
+
+    Err(io::Error::new(
+        kind,
+        format!("reading the file object failed with code {}", read),
+    ))
+}
+
+fn host_upload(filename: &str, mimetype: &str, bytes: &[u8]) -> io::Result<[u8; 16]> {
+    let mut uuid = [0; 16];
+    let code = unsafe {
+        hostcall_file_create(
+            filename.as_ptr(),
+            filename.len(),
+            mimetype.as_ptr(),
+            mimetype.len(),
+            bytes.as_ptr(),
+            bytes.len(),
+            uuid.as_mut_ptr(),
+        )
+    };
+
+    if code == 0 {
+        return Ok(uuid);
+    }
+
+    let kind = match code {
+        -2 => ErrorKind::PermissionDenied,
+        -3 => ErrorKind::InvalidInput,
+        _ => ErrorKind::Other,
+    };
+
+    Err(io::Error::new(
+        kind,
+        format!("creating the file object failed with code {}", code),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENTS: &[u8] = b"id,name\n1,Jet\n";
+
+    fn read_contents(_object_uuid: &str, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+        let contents = CONTENTS.get(offset as usize..).unwrap_or_default();
+        let len = buf.len().min(contents.len());
+        buf[..len].copy_from_slice(&contents[..len]);
+        Ok(len)
+    }
+
+    fn read_pattern(_object_uuid: &str, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+        for (index, byte) in buf.iter_mut().enumerate() {
+            *byte = ((offset + index as u64) % 251) as u8;
+        }
+
+        Ok(buf.len())
+    }
+
+    fn read_too_much(_object_uuid: &str, _offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(buf.len() + 1)
+    }
+
+    // built without `FileReader::new` so that tests don't link the hostcall
+    fn reader(filesize: u64, read_at: ReadAt) -> FileReader {
+        FileReader {
+            object_uuid: Uuid::nil().to_string(),
+            filesize,
+            position: 0,
+            read_at,
+        }
+    }
+
+    #[test]
+    fn test_read() {
+        let mut file = reader(CONTENTS.len() as u64, read_contents);
+        assert_eq!(file.read_remaining().unwrap(), CONTENTS);
+        assert_eq!(file.read(&mut [0; 4]).unwrap(), 0);
+
+        // reads stop at the filesize
+        let mut file = reader(4, read_contents);
+        assert_eq!(file.read_remaining().unwrap(), b"id,n");
+
+        // contents shorter than the filesize
+        let mut file = reader(100, read_contents);
+        assert_eq!(
+            file.read_remaining().unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+
+        let mut file = reader(4, read_too_much);
+        assert_eq!(
+            file.read(&mut [0; 8]).unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_read_chunks() {
+        let filesize = CHUNK_SIZE as u64 * 2 + 10;
+        let mut file = reader(filesize, read_pattern);
+        let mut buf = vec![0; CHUNK_SIZE * 3];
+
+        assert_eq!(file.read(&mut buf).unwrap(), CHUNK_SIZE);
+        assert_eq!(buf[CHUNK_SIZE - 1], ((CHUNK_SIZE - 1) % 251) as u8);
+
+        let bytes = file.read_remaining().unwrap();
+        assert_eq!(bytes.len(), CHUNK_SIZE + 10);
+        assert_eq!(bytes[0], (CHUNK_SIZE % 251) as u8);
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut file = reader(CONTENTS.len() as u64, read_contents);
+
+        assert_eq!(file.seek(SeekFrom::Start(3)).unwrap(), 3);
+        assert_eq!(file.seek(SeekFrom::Current(5)).unwrap(), 8);
+        assert_eq!(file.read_remaining().unwrap(), b"1,Jet\n");
+
+        assert_eq!(file.seek(SeekFrom::End(-4)).unwrap(), 10);
+        assert_eq!(file.read_remaining().unwrap(), b"Jet\n");
+
+        assert_eq!(
+            file.seek(SeekFrom::Current(-100)).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+
+        // seeking past the end reads nothing
+        file.seek(SeekFrom::End(10)).unwrap();
+        assert_eq!(file.read_remaining().unwrap(), b"");
+    }
+
+    fn upload(_filename: &str, _mimetype: &str, _bytes: &[u8]) -> io::Result<[u8; 16]> {
+        Ok([7; 16])
+    }
+
+    fn upload_denied(_filename: &str, _mimetype: &str, _bytes: &[u8]) -> io::Result<[u8; 16]> {
+        Err(io::Error::from(ErrorKind::PermissionDenied))
+    }
+
+    fn upload_nil(_filename: &str, _mimetype: &str, _bytes: &[u8]) -> io::Result<[u8; 16]> {
+        Ok([0; 16])
+    }
+
+    #[test]
+    fn test_create() {
+        assert_eq!(
+            create_with(upload, "report.csv", "text/csv", CONTENTS).unwrap(),
+            FileObject::new(
+                Uuid::from_bytes([7; 16]),
+                "report.csv".to_string(),
+                CONTENTS.len() as u64,
+                "text/csv".to_string()
+            )
+        );
+
+        assert_eq!(
+            create_with(upload, "", "text/csv", CONTENTS)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            create_with(upload_denied, "report.csv", "text/csv", CONTENTS)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::PermissionDenied
+        );
+        assert_eq!(
+            create_with(upload_nil, "report.csv", "text/csv", CONTENTS)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::InvalidData
+        );
+    }
+}
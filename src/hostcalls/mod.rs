@@ -60,4 +60,92 @@ extern "C" {
     /// }
     /// ```
     pub fn hostcall_logger_debug(ptr: *const u8, len: usize);
+    /// Fills a buffer with cryptographically secure random bytes
+    ///
+    /// # Arguments
+    ///
+    /// * `ptr` - Pointer to the buffer
+    /// * `len` - Length of the buffer
+    ///
+    /// ## Code Example
+    /// ```ignore
+    /// let mut bytes = [0u8; 16];
+    /// unsafe {
+    ///     hostcall_random_fill(bytes.as_mut_ptr(), bytes.len());
+    /// }
+    /// ```
+    pub fn hostcall_random_fill(ptr: *mut u8, len: usize);
+    /// Reads the contents of a file object into a buffer
+    ///
+    /// # Arguments
+    ///
+    /// * `uuid_ptr` - Pointer to the hyphenated `object_uuid` of the file object
+    /// * `uuid_len` - Length of the `object_uuid`
+    /// * `offset` - Offset in the contents to read from
+    /// * `buf_ptr` - Pointer to the buffer
+    /// * `buf_len` - Length of the buffer
+    /// * Return the number of bytes read, `0` at the end of the contents,
+    ///   `-1` if the file object is not found or `-2` if reading it is denied
+    ///
+    /// ## Code Example
+    /// ```ignore
+    /// let object_uuid = file_object.object_uuid.to_string();
+    /// let mut buf = [0u8; 4096];
+    /// let read = unsafe {
+    ///     hostcall_file_read(
+    ///         object_uuid.as_ptr(),
+    ///         object_uuid.len(),
+    ///         0,
+    ///         buf.as_mut_ptr(),
+    ///         buf.len(),
+    ///     )
+    /// };
+    /// ```
+    pub fn hostcall_file_read(
+        uuid_ptr: *const u8,
+        uuid_len: usize,
+        offset: u64,
+        buf_ptr: *mut u8,
+        buf_len: usize,
+    ) -> i64;
+    /// Uploads the contents of a new file object
+    ///
+    /// # Arguments
+    ///
+    /// * `filename_ptr` - Pointer to the filename
+    /// * `filename_len` - Length of the filename
+    /// * `mimetype_ptr` - Pointer to the mimetype
+    /// * `mimetype_len` - Length of the mimetype
+    /// * `bytes_ptr` - Pointer to the contents
+    /// * `bytes_len` - Length of the contents
+    /// * `uuid_ptr` - Pointer to a 16 byte buffer the host writes the
+    ///   `object_uuid` of the new file object to
+    /// * Return `0` on success, `-2` if creating file objects is denied or
+    ///   `-3` if the contents are too large
+    ///
+    /// ## Code Example
+    /// ```ignore
+    /// let (filename, mimetype, bytes) = ("report.csv", "text/csv", b"id,name\n");
+    /// let mut uuid = [0u8; 16];
+    /// let code = unsafe {
+    ///     hostcall_file_create(
+    ///         filename.as_ptr(),
+    ///         filename.len(),
+    ///         mimetype.as_ptr(),
+    ///         mimetype.len(),
+    ///         bytes.as_ptr(),
+    ///         bytes.len(),
+    ///         uuid.as_mut_ptr(),
+    ///     )
+    /// };
+    /// ```
+    pub fn hostcall_file_create(
+        filename_ptr: *const u8,
+        filename_len: usize,
+        mimetype_ptr: *const u8,
+        mimetype_len: usize,
+        bytes_ptr: *const u8,
+        bytes_len: usize,
+        uuid_ptr: *mut u8,
+    ) -> i32;
 }
@@ -1,8 +1,37 @@
 use serde_json::Value;
 
-use crate::value_presenter::{error::DecodeError, field_type::FieldType, ValuePresenter};
+use crate::value_presenter::{
+    coercion::Coercion, error::DecodeError, field_type::FieldType, ValuePresenter,
+};
+
+pub fn parse(args: &Value, types: Vec<FieldType>) -> Result<Vec<ValuePresenter>, DecodeError<'_>> {
+    parse_with(args, types, |value| {
+        ValuePresenter::from_json(value).map(|vp| (vp, Vec::new()))
+    })
+    .map(|(result, _coercions)| result)
+}
+
+/// Like `parse`, but converts values held in a compatible representation
+/// (see `value_presenter::coercion`) instead of rejecting them.
+///
+/// The coercions applied to each input are returned at the input's index.
+#[allow(clippy::type_complexity)]
+pub fn parse_lenient(
+    args: &Value,
+    types: Vec<FieldType>,
+) -> Result<(Vec<ValuePresenter>, Vec<Vec<Coercion>>), DecodeError<'_>> {
+    parse_with(args, types, ValuePresenter::from_json_lenient)
+}
 
-pub fn parse(args: &Value, types: Vec<FieldType>) -> Result<Vec<ValuePresenter>, DecodeError> {
+#[allow(clippy::type_complexity)]
+fn parse_with<F>(
+    args: &Value,
+    types: Vec<FieldType>,
+    decode: F,
+) -> Result<(Vec<ValuePresenter>, Vec<Vec<Coercion>>), DecodeError<'_>>
+where
+    F: Fn(&Value) -> Result<(ValuePresenter, Vec<Coercion>), DecodeError<'_>>,
+{
     match args {
         Value::Array(list) => {
             let types_len = types.len();
@@ -14,16 +43,18 @@ pub fn parse(args: &Value, types: Vec<FieldType>) -> Result<Vec<ValuePresenter>,
             }
 
             let mut result: Vec<ValuePresenter> = Vec::new();
+            let mut coercions: Vec<Vec<Coercion>> = Vec::new();
 
             for (field_type, value) in pairs {
                 if value.is_object() {
-                    match ValuePresenter::from_json(value) {
-                        Ok(vp) => {
+                    match decode(value) {
+                        Ok((vp, vp_coercions)) => {
                             if vp.get_field_type() == field_type {
                                 result.push(vp);
+                                coercions.push(vp_coercions);
                             } else {
                                 return Err(DecodeError::MismatchedFieldType {
-                                    value_presenter: vp,
+                                    value_presenter: Box::new(vp),
                                     field_type,
                                 });
                             }
@@ -35,7 +66,7 @@ pub fn parse(args: &Value, types: Vec<FieldType>) -> Result<Vec<ValuePresenter>,
                 }
             }
 
-            Ok(result)
+            Ok((result, coercions))
         }
         value => Err(DecodeError::InvalidJsonObject(value)),
     }
@@ -45,7 +76,8 @@ pub fn parse(args: &Value, types: Vec<FieldType>) -> Result<Vec<ValuePresenter>,
 mod tests {
 
     use crate::value_presenter::{
-        literal_naive_value::BooleanFieldValue, literal_value_presenter::LiteralValuePresenter,
+        coercion::CoercionKind, literal_naive_value::BooleanFieldValue,
+        literal_value_presenter::LiteralValuePresenter,
     };
 
     use super::*;
@@ -160,4 +192,63 @@ mod tests {
             vec![FieldType::BooleanField, FieldType::BooleanField],
         );
     }
+
+    #[test]
+    fn test_parse_lenient() {
+        let args = json!([
+            {
+                "type": "literal",
+                "field_type": "boolean_field",
+                "value": "true"
+            },
+            {
+                "type": "literal",
+                "field_type": "boolean_field",
+                "value": false
+            }
+        ]);
+
+        let (vps, coercions) = parse_lenient(
+            &args,
+            vec![FieldType::BooleanField, FieldType::BooleanField],
+        )
+        .unwrap();
+
+        assert!(matches!(
+            vps.as_slice(),
+            [
+                ValuePresenter::Literal(LiteralValuePresenter::BooleanField(
+                    BooleanFieldValue::Value(true)
+                )),
+                ValuePresenter::Literal(LiteralValuePresenter::BooleanField(
+                    BooleanFieldValue::Value(false)
+                ))
+            ]
+        ));
+        assert_eq!(
+            coercions,
+            vec![
+                vec![Coercion {
+                    field_type: FieldType::BooleanField,
+                    kind: CoercionKind::StringToBoolean
+                }],
+                vec![]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_lenient_invalid_value_presenter_json() {
+        let args = json!([
+            {
+                "type": "literal",
+                "field_type": "boolean_field",
+                "value": "123"
+            }
+        ]);
+
+        let result = parse_lenient(&args, vec![FieldType::BooleanField]);
+
+        assert!(matches!(result, Err(DecodeError::InvalidValue { .. })));
+    }
 }
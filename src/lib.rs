@@ -6,10 +6,14 @@ pub mod outputs;
 #[macro_use]
 mod scaffolding;
 
+#[cfg(feature = "files")]
+pub mod files;
 pub mod hostcalls;
 #[cfg(feature = "memory")]
 pub mod memory;
 #[cfg(feature = "networking")]
 pub mod networking;
+#[cfg(feature = "random")]
+pub mod random;
 
-pub use crate::scaffolding::wrap_run;
+pub use crate::scaffolding::{wrap_run, wrap_run_with};
@@ -1,6 +1,20 @@
 use serde_json::Value;
 
-use crate::value_presenter::ValuePresenter;
+use crate::value_presenter::{
+    error::EncodeError, literal_naive_value::NumericFieldValue,
+    literal_value_presenter::LiteralValuePresenter, value::number::Number, ValuePresenter,
+};
+
+/// How outputs holding a NaN or infinite number are encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonFiniteNumberPolicy {
+    /// Fail with `EncodeError::NonFiniteNumber`.
+    Error,
+    /// Replace the number with `Nil`.
+    Nil,
+    /// Replace an infinity with `f64::MAX` or `f64::MIN`, and NaN with `Nil`.
+    Clamp,
+}
 
 pub struct Outputs(pub Vec<ValuePresenter>);
 
@@ -9,8 +23,52 @@ impl Outputs {
         Self(value_presenters)
     }
 
-    pub fn to_json(&self) -> Value {
-        Value::Array(self.0.iter().map(|vp| vp.to_json()).collect())
+    pub fn to_json(&self) -> Result<Value, EncodeError> {
+        self.to_json_with(NonFiniteNumberPolicy::Error)
+    }
+
+    pub fn to_json_with(&self, policy: NonFiniteNumberPolicy) -> Result<Value, EncodeError> {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(index, vp)| match vp {
+                ValuePresenter::Literal(vp) => {
+                    literal_to_json(vp, policy).ok_or(EncodeError::NonFiniteNumber { index })
+                }
+            })
+            .collect::<Result<Vec<Value>, EncodeError>>()
+            .map(Value::Array)
+    }
+}
+
+/// Encodes `vp` after applying `policy` to its non-finite numbers, `None` when
+/// the policy rejects one.
+fn literal_to_json(vp: &LiteralValuePresenter, policy: NonFiniteNumberPolicy) -> Option<Value> {
+    match vp {
+        LiteralValuePresenter::NumericField(value) => finite_number(value, policy)
+            .map(|value| LiteralValuePresenter::NumericField(value).to_json()),
+        LiteralValuePresenter::NumericListField(values) => values
+            .try_map(|value| finite_number(value, policy).ok_or(()))
+            .ok()
+            .map(|values| LiteralValuePresenter::NumericListField(values).to_json()),
+        vp => Some(vp.to_json()),
+    }
+}
+
+fn finite_number(
+    value: &NumericFieldValue,
+    policy: NonFiniteNumberPolicy,
+) -> Option<NumericFieldValue> {
+    match value {
+        NumericFieldValue::Value(Number::Float(f)) if !f.is_finite() => match policy {
+            NonFiniteNumberPolicy::Error => None,
+            NonFiniteNumberPolicy::Nil => Some(NumericFieldValue::Nil),
+            NonFiniteNumberPolicy::Clamp if f.is_nan() => Some(NumericFieldValue::Nil),
+            NonFiniteNumberPolicy::Clamp => Some(NumericFieldValue::Value(Number::Float(
+                f.clamp(f64::MIN, f64::MAX),
+            ))),
+        },
+        value => Some(value.clone()),
     }
 }
 
@@ -46,6 +104,66 @@ mod tests {
               }
         ]);
 
-        assert!(outputs.to_json().to_string() == expected.to_string());
+        assert!(outputs.to_json().unwrap() == expected);
+    }
+
+    #[test]
+    fn test_to_json_with_non_finite_number() {
+        let outputs = Outputs(vec![
+            ValuePresenter::Literal(LiteralValuePresenter::NumericField(
+                NumericFieldValue::Value(Number::Integer(1)),
+            )),
+            ValuePresenter::Literal(LiteralValuePresenter::NumericListField(
+                vec![Number::Float(f64::INFINITY), Number::Float(f64::NAN)].into(),
+            )),
+        ]);
+
+        assert_eq!(
+            outputs.to_json(),
+            Err(EncodeError::NonFiniteNumber { index: 1 })
+        );
+
+        assert_eq!(
+            outputs.to_json_with(NonFiniteNumberPolicy::Nil).unwrap(),
+            json!([
+                {
+                    "type": "LITERAL",
+                    "field_type": "NUMERIC_FIELD",
+                    "value": 1
+                },
+                {
+                    "type": "LITERAL",
+                    "field_type": "NUMERIC_LIST_FIELD",
+                    "value": [null, null]
+                }
+            ])
+        );
+
+        assert_eq!(
+            outputs.to_json_with(NonFiniteNumberPolicy::Clamp).unwrap()[1]["value"],
+            json!([f64::MAX, null])
+        );
+    }
+
+    #[test]
+    fn test_to_json_with_non_finite_number_field() {
+        let outputs = Outputs(vec![ValuePresenter::Literal(
+            LiteralValuePresenter::NumericField(NumericFieldValue::Value(Number::Float(
+                f64::NEG_INFINITY,
+            ))),
+        )]);
+
+        assert_eq!(
+            outputs.to_json(),
+            Err(EncodeError::NonFiniteNumber { index: 0 })
+        );
+        assert_eq!(
+            outputs.to_json_with(NonFiniteNumberPolicy::Clamp).unwrap()[0]["value"],
+            json!(f64::MIN)
+        );
+        assert_eq!(
+            outputs.to_json_with(NonFiniteNumberPolicy::Nil).unwrap()[0]["value"],
+            json!(null)
+        );
     }
 }
@@ -0,0 +1,15 @@
+use crate::hostcalls::hostcall_random_fill;
+
+/// Fills `bytes` with random bytes from the host.
+pub fn fill_bytes(bytes: &mut [u8]) {
+    unsafe {
+        hostcall_random_fill(bytes.as_mut_ptr(), bytes.len());
+    }
+}
+
+/// Returns `N` random bytes from the host.
+pub fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+    fill_bytes(&mut bytes);
+    bytes
+}
@@ -3,6 +3,7 @@
 use crate::{
     hostcalls::hostcall_set_outputs,
     inputs::parse,
+    outputs::NonFiniteNumberPolicy,
     outputs::Outputs,
     value_presenter::{field_type::FieldType, ValuePresenter},
 };
@@ -28,6 +29,16 @@ macro_rules! program {
             $crate::wrap_run(inputs, $entrypoint, $types)
         }
 
+        $crate::if_memory! {
+            pub use $crate::memory::*;
+        }
+    };
+    ($entrypoint:ident, $types:expr, $non_finite_number_policy:expr) => {
+        #[no_mangle]
+        pub fn run(inputs: &str) {
+            $crate::wrap_run_with(inputs, $entrypoint, $types, $non_finite_number_policy)
+        }
+
         $crate::if_memory! {
             pub use $crate::memory::*;
         }
@@ -38,6 +49,18 @@ macro_rules! program {
 pub fn wrap_run<F>(inputs: &str, entrypoint: F, types: Vec<FieldType>)
 where
     F: Fn(Vec<ValuePresenter>) -> Outputs,
+{
+    wrap_run_with(inputs, entrypoint, types, NonFiniteNumberPolicy::Error)
+}
+
+#[doc(hidden)]
+pub fn wrap_run_with<F>(
+    inputs: &str,
+    entrypoint: F,
+    types: Vec<FieldType>,
+    non_finite_number_policy: NonFiniteNumberPolicy,
+) where
+    F: Fn(Vec<ValuePresenter>) -> Outputs,
 {
     let json: Value = match serde_json::from_str(inputs) {
         Ok(json) => json,
@@ -49,7 +72,10 @@ where
         Err(err) => panic!("Failed to decode inputs: {:?}", err),
     };
 
-    let str = outputs.to_json().to_string();
+    let str = match outputs.to_json_with(non_finite_number_policy) {
+        Ok(json) => json.to_string(),
+        Err(err) => panic!("Failed to encode outputs: {:?}", err),
+    };
 
     unsafe {
         hostcall_set_outputs(str.as_ptr(), str.len());
@@ -0,0 +1,313 @@
+//! Statistics over a `NumericListFieldValue`.
+//!
+//! Every method takes a `NilPolicy` deciding how `Nil` items are treated and
+//! returns a `NumericFieldValue`. A `Nil` list is treated as an empty list: its
+//! `sum` and `count` are `0`, every other statistic is `Nil`.
+
+use std::cmp::Ordering;
+
+use super::{
+    literal_list_value::NumericListFieldValue, literal_naive_value::NumericFieldValue,
+    value::number::Number,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NilPolicy {
+    /// Ignore `Nil` items.
+    Skip,
+    /// Treat `Nil` items as `0`.
+    Zero,
+    /// Return `Nil` if any item is `Nil`.
+    Propagate,
+}
+
+impl NumericListFieldValue {
+    pub fn sum(&self, policy: NilPolicy) -> NumericFieldValue {
+        match self.numbers(policy) {
+            Some(numbers) => NumericFieldValue::Value(sum(&numbers)),
+            None => NumericFieldValue::Nil,
+        }
+    }
+
+    /// Counts the items, excluding `Nil` items unless `policy` is `Zero`.
+    pub fn count(&self, policy: NilPolicy) -> NumericFieldValue {
+        match self.numbers(policy) {
+            Some(numbers) => NumericFieldValue::Value(Number::Integer(numbers.len() as i64)),
+            None => NumericFieldValue::Nil,
+        }
+    }
+
+    pub fn mean(&self, policy: NilPolicy) -> NumericFieldValue {
+        self.aggregate(policy, |numbers| {
+            Some(sum(numbers) / Number::Integer(numbers.len() as i64))
+        })
+    }
+
+    /// The middle item, or the mean of the two middle items for an even count.
+    pub fn median(&self, policy: NilPolicy) -> NumericFieldValue {
+        self.aggregate(policy, |numbers| {
+            let numbers = sorted(numbers);
+            let middle = numbers.len() / 2;
+
+            if middle * 2 == numbers.len() {
+                Some((&numbers[middle - 1] + &numbers[middle]) / Number::Integer(2))
+            } else {
+                Some(numbers[middle].clone())
+            }
+        })
+    }
+
+    pub fn min(&self, policy: NilPolicy) -> NumericFieldValue {
+        self.aggregate(policy, |numbers| {
+            numbers.iter().min_by(|a, b| compare(a, b)).cloned()
+        })
+    }
+
+    pub fn max(&self, policy: NilPolicy) -> NumericFieldValue {
+        self.aggregate(policy, |numbers| {
+            numbers.iter().max_by(|a, b| compare(a, b)).cloned()
+        })
+    }
+
+    /// The population standard deviation.
+    pub fn stddev(&self, policy: NilPolicy) -> NumericFieldValue {
+        self.aggregate(policy, |numbers| variance(numbers, 0))
+    }
+
+    /// The sample standard deviation, `Nil` for fewer than two items.
+    pub fn sample_stddev(&self, policy: NilPolicy) -> NumericFieldValue {
+        self.aggregate(policy, |numbers| variance(numbers, 1))
+    }
+
+    /// The `percentile`-th percentile, interpolating linearly between the two
+    /// closest items. `Nil` if `percentile` is outside `0.0..=100.0`.
+    pub fn percentile(&self, percentile: f64, policy: NilPolicy) -> NumericFieldValue {
+        if !(0.0..=100.0).contains(&percentile) {
+            return NumericFieldValue::Nil;
+        }
+
+        self.aggregate(policy, |numbers| {
+            let numbers = sorted(numbers);
+            let rank = percentile / 100.0 * (numbers.len() - 1) as f64;
+            let lower = &numbers[rank.floor() as usize];
+            let upper = &numbers[rank.ceil() as usize];
+            let fraction = rank - rank.floor();
+
+            if fraction == 0.0 {
+                Some(lower.clone())
+            } else {
+                Some(lower + &((upper - lower) * Number::Float(fraction)))
+            }
+        })
+    }
+
+    /// Applies `f` to the numbers, giving `Nil` for an empty list.
+    fn aggregate<F>(&self, policy: NilPolicy, f: F) -> NumericFieldValue
+    where
+        F: FnOnce(&[Number]) -> Option<Number>,
+    {
+        match self.numbers(policy) {
+            Some(numbers) if !numbers.is_empty() => match f(&numbers) {
+                Some(number) => NumericFieldValue::Value(number),
+                None => NumericFieldValue::Nil,
+            },
+            _ => NumericFieldValue::Nil,
+        }
+    }
+
+    /// Returns the numbers of the list after applying `policy`, `None` when a
+    /// `Nil` item is propagated.
+    fn numbers(&self, policy: NilPolicy) -> Option<Vec<Number>> {
+        self.iter()
+            .filter_map(|item| match (item, policy) {
+                (NumericFieldValue::Value(number), _) => Some(Some(number.clone())),
+                (NumericFieldValue::Nil, NilPolicy::Skip) => None,
+                (NumericFieldValue::Nil, NilPolicy::Zero) => Some(Some(Number::Integer(0))),
+                (NumericFieldValue::Nil, NilPolicy::Propagate) => Some(None),
+            })
+            .collect()
+    }
+}
+
+fn sum(numbers: &[Number]) -> Number {
+    numbers
+        .iter()
+        .fold(Number::Integer(0), |acc, number| &acc + number)
+}
+
+fn compare(a: &Number, b: &Number) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+fn sorted(numbers: &[Number]) -> Vec<Number> {
+    let mut numbers = numbers.to_vec();
+    numbers.sort_by(compare);
+    numbers
+}
+
+fn variance(numbers: &[Number], degrees_of_freedom: usize) -> Option<Number> {
+    if numbers.len() <= degrees_of_freedom {
+        return None;
+    }
+
+    let values = numbers
+        .iter()
+        .map(Number::to_f64)
+        .collect::<Option<Vec<f64>>>()?;
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let squares = values
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>();
+
+    Some(Number::Float(
+        (squares / (values.len() - degrees_of_freedom) as f64).sqrt(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(values: Vec<Option<i64>>) -> NumericListFieldValue {
+        values.into()
+    }
+
+    #[test]
+    fn test_sum_and_count() {
+        let values = list(vec![Some(1), None, Some(2), Some(3)]);
+
+        assert_eq!(
+            values.sum(NilPolicy::Skip),
+            NumericFieldValue::Value(Number::Integer(6))
+        );
+        assert_eq!(values.sum(NilPolicy::Propagate), NumericFieldValue::Nil);
+        assert_eq!(
+            values.count(NilPolicy::Skip),
+            NumericFieldValue::Value(Number::Integer(3))
+        );
+        assert_eq!(
+            values.count(NilPolicy::Zero),
+            NumericFieldValue::Value(Number::Integer(4))
+        );
+
+        // nil list
+        assert_eq!(
+            NumericListFieldValue::Nil.sum(NilPolicy::Skip),
+            NumericFieldValue::Value(Number::Integer(0))
+        );
+        assert_eq!(
+            NumericListFieldValue::Nil.count(NilPolicy::Propagate),
+            NumericFieldValue::Value(Number::Integer(0))
+        );
+    }
+
+    #[test]
+    fn test_mean() {
+        let values = list(vec![Some(1), None, Some(2)]);
+
+        assert_eq!(
+            values.mean(NilPolicy::Skip),
+            NumericFieldValue::Value(Number::Float(1.5))
+        );
+        assert_eq!(
+            values.mean(NilPolicy::Zero),
+            NumericFieldValue::Value(Number::Integer(1))
+        );
+        assert_eq!(values.mean(NilPolicy::Propagate), NumericFieldValue::Nil);
+        assert_eq!(list(vec![]).mean(NilPolicy::Skip), NumericFieldValue::Nil);
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(
+            list(vec![Some(3), Some(1), Some(2)]).median(NilPolicy::Skip),
+            NumericFieldValue::Value(Number::Integer(2))
+        );
+        assert_eq!(
+            list(vec![Some(4), Some(1), None, Some(2), Some(3)]).median(NilPolicy::Skip),
+            NumericFieldValue::Value(Number::Float(2.5))
+        );
+        assert_eq!(
+            list(vec![Some(4), Some(1), None]).median(NilPolicy::Zero),
+            NumericFieldValue::Value(Number::Integer(1))
+        );
+    }
+
+    #[test]
+    fn test_min_and_max() {
+        let values: NumericListFieldValue =
+            vec![Some(Number::Float(1.5)), None, Some(Number::Integer(-2))].into();
+
+        assert_eq!(
+            values.min(NilPolicy::Skip),
+            NumericFieldValue::Value(Number::Integer(-2))
+        );
+        assert_eq!(
+            values.max(NilPolicy::Skip),
+            NumericFieldValue::Value(Number::Float(1.5))
+        );
+        assert_eq!(
+            values.max(NilPolicy::Zero),
+            NumericFieldValue::Value(Number::Float(1.5))
+        );
+        assert_eq!(values.min(NilPolicy::Propagate), NumericFieldValue::Nil);
+        assert_eq!(
+            NumericListFieldValue::Nil.max(NilPolicy::Skip),
+            NumericFieldValue::Nil
+        );
+    }
+
+    #[test]
+    fn test_stddev() {
+        let values = list(vec![
+            Some(2),
+            Some(4),
+            Some(4),
+            Some(4),
+            Some(5),
+            Some(5),
+            Some(7),
+            Some(9),
+        ]);
+
+        assert_eq!(
+            values.stddev(NilPolicy::Skip),
+            NumericFieldValue::Value(Number::Float(2.0))
+        );
+        assert!(matches!(
+            values.sample_stddev(NilPolicy::Skip),
+            NumericFieldValue::Value(Number::Float(f)) if (f - 2.138_089_935_299_395).abs() < 1e-12
+        ));
+        assert_eq!(
+            list(vec![Some(1)]).sample_stddev(NilPolicy::Skip),
+            NumericFieldValue::Nil
+        );
+    }
+
+    #[test]
+    fn test_percentile() {
+        let values = list(vec![Some(15), Some(20), Some(35), Some(40), Some(50)]);
+
+        assert_eq!(
+            values.percentile(0.0, NilPolicy::Skip),
+            NumericFieldValue::Value(Number::Integer(15))
+        );
+        assert_eq!(
+            values.percentile(50.0, NilPolicy::Skip),
+            NumericFieldValue::Value(Number::Integer(35))
+        );
+        assert_eq!(
+            values.percentile(100.0, NilPolicy::Skip),
+            NumericFieldValue::Value(Number::Integer(50))
+        );
+        assert!(matches!(
+            values.percentile(40.0, NilPolicy::Skip),
+            NumericFieldValue::Value(Number::Float(f)) if (f - 29.0).abs() < 1e-9
+        ));
+        assert_eq!(
+            values.percentile(101.0, NilPolicy::Skip),
+            NumericFieldValue::Nil
+        );
+    }
+}
@@ -0,0 +1,917 @@
+//! Conversions between field types.
+//!
+//! `LiteralValuePresenter::cast_to` supports the following conversions,
+//! a `Nil` value always converts to the `Nil` value of the target type:
+//!
+//! | from                  | to                                                     |
+//! |-----------------------|--------------------------------------------------------|
+//! | any field             | the same field (identity)                              |
+//! | `BooleanField`        | `NumericField` (`1`/`0`), `SingleLineField`            |
+//! | `NumericField`        | `BooleanField` (non-zero is `true`), `SingleLineField` |
+//! | `SingleLineField`     | `BooleanField` (`true`/`false`, `1`/`0`), `NumericField`, `DateTimeField`, `DateField`, `TimeField`, `TableRowField`, `CheckboxField`, `RadioButtonField`, `MultipleLineField` (a paragraph per line) |
+//! | `DateTimeField`       | `SingleLineField`, `DateField`, `TimeField` (local date and time of day) |
+//! | `DateField`           | `SingleLineField`, `DateTimeField` (midnight)          |
+//! | `TimeField`           | `SingleLineField`                                      |
+//! | `MultipleLineField`   | `SingleLineField` (the plain text)                     |
+//! | `TableRowField`       | `SingleLineField`, `RelationField` (`DATABASE_ROW`)    |
+//! | `RelationField`       | `TableRowField` (`DATABASE_ROW` only)                  |
+//! | `CheckboxField`       | `RadioButtonField`, `SingleLineField` (at most one option), `SingleLineListField` |
+//! | `RadioButtonField`    | `CheckboxField`, `SingleLineField`                     |
+//! | `SingleLineListField` | `CheckboxField` (non-nil items become options)         |
+//! | `XField`              | `YListField` when `X` converts to `Y`, as a one-element list |
+//! | `XListField`          | `YField` when `X` converts to `Y` and the list holds at most one item |
+//! | `XListField`          | `YListField` when `X` converts to `Y`, item by item    |
+//!
+//! Any other pair fails with `CastError::UnsupportedCast`, a value that cannot
+//! be represented in the target type fails with `CastError::InvalidValue`.
+
+use bigdecimal::Zero;
+use serde_json::Value;
+
+use super::{
+    coercion::boolean_from_str,
+    field_type::FieldType,
+    literal_list_value::{ListFieldValue, SingleLineListFieldValue},
+    literal_naive_value::{
+        BooleanFieldValue, CheckboxFieldValue, DateFieldValue, DateTimeFieldValue,
+        MultipleLineFieldValue, NumericFieldValue, RadioButtonFieldValue, RelationFieldValue,
+        SingleLineFieldValue, TableRowFieldValue, TimeFieldValue,
+    },
+    literal_value::LiteralValue,
+    literal_value_presenter::LiteralValuePresenter,
+    value::{
+        naive_date_time::NaiveDateTime,
+        naive_time::NaiveTime,
+        number::Number,
+        options_value::OptionsValue,
+        relation_value::{RelationValue, ResourceType},
+        uuid::Uuid,
+    },
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CastError {
+    UnsupportedCast { from: FieldType, to: FieldType },
+    InvalidValue { from: FieldType, to: FieldType },
+}
+
+macro_rules! map_value {
+    ($value:expr, $from:ident => $to:ident, |$v:ident| $body:expr) => {
+        match $value {
+            $from::Value($v) => $body.map($to::Value),
+            $from::Nil => Ok($to::Nil),
+        }
+    };
+}
+
+impl LiteralValuePresenter {
+    pub fn cast_to(&self, field_type: FieldType) -> Result<LiteralValuePresenter, CastError> {
+        let from = self.get_field_type();
+
+        if from == field_type {
+            return Ok(self.clone());
+        }
+
+        match (self, field_type) {
+            (LiteralValuePresenter::SingleLineListField(value), FieldType::CheckboxField) => {
+                return Ok(LiteralValuePresenter::CheckboxField(match value {
+                    SingleLineListFieldValue::Value(_values) => {
+                        CheckboxFieldValue::Value(OptionsValue::from(value))
+                    }
+                    SingleLineListFieldValue::Nil => CheckboxFieldValue::Nil,
+                }));
+            }
+            (LiteralValuePresenter::CheckboxField(value), FieldType::SingleLineListField) => {
+                return Ok(LiteralValuePresenter::SingleLineListField(match value {
+                    CheckboxFieldValue::Value(options_value) => {
+                        SingleLineListFieldValue::from(options_value)
+                    }
+                    CheckboxFieldValue::Nil => SingleLineListFieldValue::Nil,
+                }));
+            }
+            _ => (),
+        }
+
+        let error = CastError::InvalidValue {
+            from,
+            to: field_type,
+        };
+
+        match (from.item_field_type(), field_type.item_field_type()) {
+            (None, None) => cast_naive_value(self, field_type),
+            (None, Some(item_field_type)) => {
+                let item = self.cast_to(item_field_type)?;
+
+                if *self == LiteralValuePresenter::nil(from) {
+                    Ok(LiteralValuePresenter::nil(field_type))
+                } else {
+                    Ok(list_from_items(field_type, vec![item]))
+                }
+            }
+            (Some(from_item_field_type), None) => match list_items(self) {
+                Some(items) if items.len() > 1 => Err(error),
+                Some(items) if items.len() == 1 => items[0].cast_to(field_type),
+                _ => LiteralValuePresenter::nil(from_item_field_type).cast_to(field_type),
+            },
+            (Some(from_item_field_type), Some(item_field_type)) => match list_items(self) {
+                Some(items) => items
+                    .iter()
+                    .map(|item| item.cast_to(item_field_type))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|items| list_from_items(field_type, items)),
+                None => LiteralValuePresenter::nil(from_item_field_type)
+                    .cast_to(item_field_type)
+                    .map(|_nil| LiteralValuePresenter::nil(field_type)),
+            },
+        }
+        .map_err(|err| match err {
+            CastError::UnsupportedCast { .. } => CastError::UnsupportedCast {
+                from,
+                to: field_type,
+            },
+            CastError::InvalidValue { .. } => CastError::InvalidValue {
+                from,
+                to: field_type,
+            },
+        })
+    }
+}
+
+fn cast_naive_value(
+    vp: &LiteralValuePresenter,
+    field_type: FieldType,
+) -> Result<LiteralValuePresenter, CastError> {
+    let from = vp.get_field_type();
+    let error = CastError::InvalidValue {
+        from,
+        to: field_type,
+    };
+
+    match (vp, field_type) {
+        (LiteralValuePresenter::BooleanField(value), FieldType::NumericField) => {
+            map_value!(value, BooleanFieldValue => NumericFieldValue, |v| {
+                Ok(Number::Integer(*v as i64))
+            })
+            .map(LiteralValuePresenter::NumericField)
+        }
+        (LiteralValuePresenter::BooleanField(value), FieldType::SingleLineField) => {
+            map_value!(value, BooleanFieldValue => SingleLineFieldValue, |v| Ok(v.to_string()))
+                .map(LiteralValuePresenter::SingleLineField)
+        }
+
+        (LiteralValuePresenter::NumericField(value), FieldType::BooleanField) => {
+            map_value!(value, NumericFieldValue => BooleanFieldValue, |v| Ok(match v {
+                Number::Integer(i) => *i != 0,
+                Number::Float(f) => *f != 0.0,
+                Number::Decimal(d) => !d.is_zero(),
+            }))
+            .map(LiteralValuePresenter::BooleanField)
+        }
+        (LiteralValuePresenter::NumericField(value), FieldType::SingleLineField) => {
+            map_value!(value, NumericFieldValue => SingleLineFieldValue, |v| Ok(v.to_string()))
+                .map(LiteralValuePresenter::SingleLineField)
+        }
+
+        (LiteralValuePresenter::SingleLineField(value), FieldType::BooleanField) => {
+            map_value!(value, SingleLineFieldValue => BooleanFieldValue, |v| {
+                boolean_from_str(v).ok_or(error)
+            })
+            .map(LiteralValuePresenter::BooleanField)
+        }
+        (LiteralValuePresenter::SingleLineField(value), FieldType::NumericField) => {
+            map_value!(value, SingleLineFieldValue => NumericFieldValue, |v| {
+                Number::parse_str(v).map_err(|_err| error)
+            })
+            .map(LiteralValuePresenter::NumericField)
+        }
+        (LiteralValuePresenter::SingleLineField(value), FieldType::DateTimeField) => match value {
+            SingleLineFieldValue::Value(v) => {
+                DateTimeFieldValue::from_json(&Value::String(v.clone())).map_err(|_err| error)
+            }
+            SingleLineFieldValue::Nil => Ok(DateTimeFieldValue::Nil),
+        }
+        .map(LiteralValuePresenter::DateTimeField),
+        (LiteralValuePresenter::SingleLineField(value), FieldType::DateField) => match value {
+            SingleLineFieldValue::Value(v) => {
+                DateFieldValue::from_json(&Value::String(v.clone())).map_err(|_err| error)
+            }
+            SingleLineFieldValue::Nil => Ok(DateFieldValue::Nil),
+        }
+        .map(LiteralValuePresenter::DateField),
+        (LiteralValuePresenter::SingleLineField(value), FieldType::TimeField) => match value {
+            SingleLineFieldValue::Value(v) => {
+                TimeFieldValue::from_json(&Value::String(v.clone())).map_err(|_err| error)
+            }
+            SingleLineFieldValue::Nil => Ok(TimeFieldValue::Nil),
+        }
+        .map(LiteralValuePresenter::TimeField),
+        (LiteralValuePresenter::SingleLineField(value), FieldType::TableRowField) => {
+            map_value!(value, SingleLineFieldValue => TableRowFieldValue, |v| {
+                Uuid::new(v).map_err(|_err| error)
+            })
+            .map(LiteralValuePresenter::TableRowField)
+        }
+        (LiteralValuePresenter::SingleLineField(value), FieldType::CheckboxField) => {
+            map_value!(value, SingleLineFieldValue => CheckboxFieldValue, |v| {
+                Ok(OptionsValue {
+                    options: vec![v.clone()],
+                    other: None,
+                })
+            })
+            .map(LiteralValuePresenter::CheckboxField)
+        }
+        (LiteralValuePresenter::SingleLineField(value), FieldType::RadioButtonField) => {
+            map_value!(value, SingleLineFieldValue => RadioButtonFieldValue, |v| {
+                Ok(OptionsValue {
+                    options: vec![v.clone()],
+                    other: None,
+                })
+            })
+            .map(LiteralValuePresenter::RadioButtonField)
+        }
+
+        (LiteralValuePresenter::DateTimeField(value), FieldType::SingleLineField) => {
+            match value.to_json() {
+                Value::String(str) => Ok(SingleLineFieldValue::Value(str)),
+                Value::Null if value.is_nil() => Ok(SingleLineFieldValue::Nil),
+                _ => Err(error),
+            }
+            .map(LiteralValuePresenter::SingleLineField)
+        }
+        (LiteralValuePresenter::DateTimeField(value), FieldType::DateField) => match value {
+            DateTimeFieldValue::Value(v) => Ok(DateFieldValue::Value(v.date())),
+            DateTimeFieldValue::OffsetValue(v) => Ok(DateFieldValue::Value(v.date_time.date())),
+            DateTimeFieldValue::Nil => Ok(DateFieldValue::Nil),
+        }
+        .map(LiteralValuePresenter::DateField),
+        (LiteralValuePresenter::DateTimeField(value), FieldType::TimeField) => match value {
+            DateTimeFieldValue::Value(v) => Ok(TimeFieldValue::Value(v.time())),
+            DateTimeFieldValue::OffsetValue(v) => Ok(TimeFieldValue::Value(v.date_time.time())),
+            DateTimeFieldValue::Nil => Ok(TimeFieldValue::Nil),
+        }
+        .map(LiteralValuePresenter::TimeField),
+
+        (LiteralValuePresenter::DateField(value), FieldType::SingleLineField) => {
+            match value.to_json() {
+                Value::String(str) => Ok(SingleLineFieldValue::Value(str)),
+                Value::Null if value.is_nil() => Ok(SingleLineFieldValue::Nil),
+                _ => Err(error),
+            }
+            .map(LiteralValuePresenter::SingleLineField)
+        }
+        (LiteralValuePresenter::DateField(value), FieldType::DateTimeField) => {
+            map_value!(value, DateFieldValue => DateTimeFieldValue, |v| {
+                Ok(NaiveDateTime::from_date_and_time(v, &NaiveTime::new(0, 0, 0, 0)))
+            })
+            .map(LiteralValuePresenter::DateTimeField)
+        }
+
+        (LiteralValuePresenter::TimeField(value), FieldType::SingleLineField) => {
+            match value.to_json() {
+                Value::String(str) => Ok(SingleLineFieldValue::Value(str)),
+                Value::Null if value.is_nil() => Ok(SingleLineFieldValue::Nil),
+                _ => Err(error),
+            }
+            .map(LiteralValuePresenter::SingleLineField)
+        }
+
+        (LiteralValuePresenter::SingleLineField(value), FieldType::MultipleLineField) => Ok(
+            LiteralValuePresenter::MultipleLineField(MultipleLineFieldValue::from(value)),
+        ),
+        (LiteralValuePresenter::MultipleLineField(value), FieldType::SingleLineField) => Ok(
+            LiteralValuePresenter::SingleLineField(SingleLineFieldValue::from(value)),
+        ),
+
+        (LiteralValuePresenter::TableRowField(value), FieldType::SingleLineField) => {
+            map_value!(value, TableRowFieldValue => SingleLineFieldValue, |v| Ok(v.to_string()))
+                .map(LiteralValuePresenter::SingleLineField)
+        }
+        (LiteralValuePresenter::TableRowField(value), FieldType::RelationField) => {
+            map_value!(value, TableRowFieldValue => RelationFieldValue, |v| {
+                Ok(RelationValue::new(ResourceType::DatabaseRow, *v))
+            })
+            .map(LiteralValuePresenter::RelationField)
+        }
+
+        (LiteralValuePresenter::RelationField(value), FieldType::TableRowField) => {
+            map_value!(value, RelationFieldValue => TableRowFieldValue, |v| {
+                match v.resource_type {
+                    ResourceType::DatabaseRow => Ok(v.resource_uuid),
+                    _ => Err(error),
+                }
+            })
+            .map(LiteralValuePresenter::TableRowField)
+        }
+
+        (LiteralValuePresenter::CheckboxField(value), FieldType::RadioButtonField) => {
+            map_value!(value, CheckboxFieldValue => RadioButtonFieldValue, |v| {
+                if v.count_options() <= 1 {
+                    Ok(v.clone())
+                } else {
+                    Err(error)
+                }
+            })
+            .map(LiteralValuePresenter::RadioButtonField)
+        }
+        (LiteralValuePresenter::CheckboxField(value), FieldType::SingleLineField) => {
+            let value = match value {
+                CheckboxFieldValue::Value(options_value) => Some(options_value),
+                CheckboxFieldValue::Nil => None,
+            };
+
+            single_option(value)
+                .ok_or(error)
+                .map(LiteralValuePresenter::SingleLineField)
+        }
+
+        (LiteralValuePresenter::RadioButtonField(value), FieldType::CheckboxField) => {
+            map_value!(value, RadioButtonFieldValue => CheckboxFieldValue, |v| Ok(v.clone()))
+                .map(LiteralValuePresenter::CheckboxField)
+        }
+        (LiteralValuePresenter::RadioButtonField(value), FieldType::SingleLineField) => {
+            let value = match value {
+                RadioButtonFieldValue::Value(options_value) => Some(options_value),
+                RadioButtonFieldValue::Nil => None,
+            };
+
+            single_option(value)
+                .ok_or(error)
+                .map(LiteralValuePresenter::SingleLineField)
+        }
+
+        _ => Err(CastError::UnsupportedCast {
+            from,
+            to: field_type,
+        }),
+    }
+}
+
+fn single_option(options_value: Option<&OptionsValue>) -> Option<SingleLineFieldValue> {
+    match options_value {
+        Some(options_value) if options_value.count_options() > 1 => None,
+        Some(options_value) => Some(
+            match options_value
+                .options
+                .first()
+                .or(options_value.other.as_ref())
+            {
+                Some(option) => SingleLineFieldValue::Value(option.clone()),
+                None => SingleLineFieldValue::Nil,
+            },
+        ),
+        None => Some(SingleLineFieldValue::Nil),
+    }
+}
+
+macro_rules! list_conversions {
+    ($($list:ident => $item:ident),* $(,)?) => {
+        pub(super) fn list_items(vp: &LiteralValuePresenter) -> Option<Vec<LiteralValuePresenter>> {
+            match vp {
+                $(LiteralValuePresenter::$list(value) => match value {
+                    ListFieldValue::Value(values) => Some(
+                        values
+                            .iter()
+                            .cloned()
+                            .map(LiteralValuePresenter::$item)
+                            .collect(),
+                    ),
+                    ListFieldValue::Nil => None,
+                },)*
+                _ => None,
+            }
+        }
+
+        pub(super) fn list_from_items(
+            field_type: FieldType,
+            items: Vec<LiteralValuePresenter>,
+        ) -> LiteralValuePresenter {
+            match field_type {
+                $(FieldType::$list => LiteralValuePresenter::$list(
+                    items
+                        .into_iter()
+                        .filter_map(|item| match item {
+                            LiteralValuePresenter::$item(value) => Some(value),
+                            _ => None,
+                        })
+                        .collect(),
+                ),)*
+                _ => unreachable!("{:?} is not a list field type", field_type),
+            }
+        }
+    };
+}
+
+list_conversions! {
+    BooleanListField => BooleanField,
+    CascaderListField => CascaderField,
+    CheckboxListField => CheckboxField,
+    DateListField => DateField,
+    DateTimeListField => DateTimeField,
+    FileListField => FileField,
+    MultipleLineListField => MultipleLineField,
+    NumericListField => NumericField,
+    RadioButtonListField => RadioButtonField,
+    RelationListField => RelationField,
+    SingleLineListField => SingleLineField,
+    TableRowListField => TableRowField,
+    TimeListField => TimeField,
+    UserBoundaryListField => UserBoundaryField,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_presenter::literal_list_value::{
+        CascaderListFieldValue, NumericListFieldValue, RelationListFieldValue,
+        TableRowListFieldValue,
+    };
+    use crate::value_presenter::literal_naive_value::FileFieldValue;
+    use crate::value_presenter::value::{naive_date::NaiveDate, offset_date_time::OffsetDateTime};
+
+    #[test]
+    fn test_cast_to_same_field_type() {
+        let vp = LiteralValuePresenter::BooleanField(BooleanFieldValue::Value(true));
+
+        assert_eq!(vp.cast_to(FieldType::BooleanField), Ok(vp));
+    }
+
+    #[test]
+    fn test_cast_to_single_line_field() {
+        {
+            let vp =
+                LiteralValuePresenter::NumericField(NumericFieldValue::Value(Number::Integer(42)));
+
+            assert_eq!(
+                vp.cast_to(FieldType::SingleLineField),
+                Ok(LiteralValuePresenter::SingleLineField(
+                    SingleLineFieldValue::Value("42".to_string())
+                ))
+            );
+        }
+
+        {
+            let vp =
+                LiteralValuePresenter::NumericField(NumericFieldValue::Value(Number::Float(1.5)));
+
+            assert_eq!(
+                vp.cast_to(FieldType::SingleLineField),
+                Ok(LiteralValuePresenter::SingleLineField(
+                    SingleLineFieldValue::Value("1.5".to_string())
+                ))
+            );
+        }
+
+        {
+            let vp = LiteralValuePresenter::DateTimeField(DateTimeFieldValue::Value(
+                NaiveDateTime::new(2022, 4, 29, 7, 34, 10, 0),
+            ));
+
+            assert_eq!(
+                vp.cast_to(FieldType::SingleLineField),
+                Ok(LiteralValuePresenter::SingleLineField(
+                    SingleLineFieldValue::Value("2022-04-29T07:34:10".to_string())
+                ))
+            );
+        }
+
+        {
+            let vp = LiteralValuePresenter::DateTimeField(DateTimeFieldValue::OffsetValue(
+                OffsetDateTime::new(NaiveDateTime::new(2022, 4, 29, 7, 34, 10, 0), 8 * 3600),
+            ));
+
+            assert_eq!(
+                vp.cast_to(FieldType::SingleLineField),
+                Ok(LiteralValuePresenter::SingleLineField(
+                    SingleLineFieldValue::Value("2022-04-29T07:34:10+08:00".to_string())
+                ))
+            );
+        }
+
+        // nil
+        {
+            let vp = LiteralValuePresenter::BooleanField(BooleanFieldValue::Nil);
+
+            assert_eq!(
+                vp.cast_to(FieldType::SingleLineField),
+                Ok(LiteralValuePresenter::SingleLineField(
+                    SingleLineFieldValue::Nil
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn test_cast_from_single_line_field() {
+        {
+            let vp = LiteralValuePresenter::SingleLineField(SingleLineFieldValue::Value(
+                "1.5".to_string(),
+            ));
+
+            assert_eq!(
+                vp.cast_to(FieldType::NumericField),
+                Ok(LiteralValuePresenter::NumericField(
+                    NumericFieldValue::Value(Number::Float(1.5))
+                ))
+            );
+        }
+
+        {
+            let vp = LiteralValuePresenter::SingleLineField(SingleLineFieldValue::Value(
+                "option".to_string(),
+            ));
+
+            assert_eq!(
+                vp.cast_to(FieldType::RadioButtonField),
+                Ok(LiteralValuePresenter::RadioButtonField(
+                    RadioButtonFieldValue::Value(OptionsValue {
+                        options: vec!["option".to_string()],
+                        other: None
+                    })
+                ))
+            );
+        }
+
+        // booleans are parsed as lenient decoding coerces them
+        for (str, bool) in [
+            ("true", true),
+            ("FALSE", false),
+            (" 1 ", true),
+            ("0", false),
+        ] {
+            let vp = LiteralValuePresenter::SingleLineField(SingleLineFieldValue::Value(
+                str.to_string(),
+            ));
+
+            assert_eq!(
+                vp.cast_to(FieldType::BooleanField),
+                Ok(LiteralValuePresenter::BooleanField(
+                    BooleanFieldValue::Value(bool)
+                ))
+            );
+        }
+
+        // invalid value
+        {
+            let vp = LiteralValuePresenter::SingleLineField(SingleLineFieldValue::Value(
+                "foo".to_string(),
+            ));
+
+            assert_eq!(
+                vp.cast_to(FieldType::NumericField),
+                Err(CastError::InvalidValue {
+                    from: FieldType::SingleLineField,
+                    to: FieldType::NumericField
+                })
+            );
+            assert_eq!(
+                vp.cast_to(FieldType::TableRowField),
+                Err(CastError::InvalidValue {
+                    from: FieldType::SingleLineField,
+                    to: FieldType::TableRowField
+                })
+            );
+            assert_eq!(
+                vp.cast_to(FieldType::BooleanField),
+                Err(CastError::InvalidValue {
+                    from: FieldType::SingleLineField,
+                    to: FieldType::BooleanField
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_cast_between_table_row_and_relation_field() {
+        let uuid = Uuid::new("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+
+        {
+            let vp = LiteralValuePresenter::TableRowField(TableRowFieldValue::Value(uuid));
+
+            assert_eq!(
+                vp.cast_to(FieldType::RelationField),
+                Ok(LiteralValuePresenter::RelationField(
+                    RelationFieldValue::Value(RelationValue::new(ResourceType::DatabaseRow, uuid))
+                ))
+            );
+        }
+
+        {
+            let vp = LiteralValuePresenter::RelationField(RelationFieldValue::Value(
+                RelationValue::new(ResourceType::DatabaseRow, uuid),
+            ));
+
+            assert_eq!(
+                vp.cast_to(FieldType::TableRowField),
+                Ok(LiteralValuePresenter::TableRowField(
+                    TableRowFieldValue::Value(uuid)
+                ))
+            );
+        }
+
+        // other resource types
+        {
+            let vp = LiteralValuePresenter::RelationField(RelationFieldValue::Value(
+                RelationValue::new(ResourceType::WorkflowCase, uuid),
+            ));
+
+            assert!(matches!(
+                vp.cast_to(FieldType::TableRowField),
+                Err(CastError::InvalidValue { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_cast_between_options_fields() {
+        {
+            let vp =
+                LiteralValuePresenter::SingleLineListField(SingleLineListFieldValue::Value(vec![
+                    SingleLineFieldValue::Value("foo".to_string()),
+                    SingleLineFieldValue::Nil,
+                    SingleLineFieldValue::Value("bar".to_string()),
+                ]));
+
+            assert_eq!(
+                vp.cast_to(FieldType::CheckboxField),
+                Ok(LiteralValuePresenter::CheckboxField(
+                    CheckboxFieldValue::Value(OptionsValue {
+                        options: vec!["foo".to_string(), "bar".to_string()],
+                        other: None
+                    })
+                ))
+            );
+        }
+
+        {
+            let vp =
+                LiteralValuePresenter::CheckboxField(CheckboxFieldValue::Value(OptionsValue {
+                    options: vec!["foo".to_string()],
+                    other: Some("other".to_string()),
+                }));
+
+            assert_eq!(
+                vp.cast_to(FieldType::SingleLineListField),
+                Ok(LiteralValuePresenter::SingleLineListField(
+                    SingleLineListFieldValue::Value(vec![
+                        SingleLineFieldValue::Value("foo".to_string()),
+                        SingleLineFieldValue::Value("other".to_string()),
+                    ])
+                ))
+            );
+            assert!(matches!(
+                vp.cast_to(FieldType::RadioButtonField),
+                Err(CastError::InvalidValue { .. })
+            ));
+            assert!(matches!(
+                vp.cast_to(FieldType::SingleLineField),
+                Err(CastError::InvalidValue { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_cast_between_naive_and_list_fields() {
+        {
+            let vp = LiteralValuePresenter::BooleanField(BooleanFieldValue::Value(true));
+
+            assert_eq!(
+                vp.cast_to(FieldType::NumericListField),
+                Ok(LiteralValuePresenter::NumericListField(
+                    NumericListFieldValue::Value(vec![NumericFieldValue::Value(Number::Integer(
+                        1
+                    ))])
+                ))
+            );
+        }
+
+        {
+            let vp = LiteralValuePresenter::NumericListField(NumericListFieldValue::Value(vec![
+                NumericFieldValue::Value(Number::Integer(1)),
+            ]));
+
+            assert_eq!(
+                vp.cast_to(FieldType::SingleLineField),
+                Ok(LiteralValuePresenter::SingleLineField(
+                    SingleLineFieldValue::Value("1".to_string())
+                ))
+            );
+        }
+
+        // empty list
+        {
+            let vp = LiteralValuePresenter::NumericListField(NumericListFieldValue::Value(vec![]));
+
+            assert_eq!(
+                vp.cast_to(FieldType::NumericField),
+                Ok(LiteralValuePresenter::NumericField(NumericFieldValue::Nil))
+            );
+        }
+
+        // too many items
+        {
+            let vp = LiteralValuePresenter::NumericListField(NumericListFieldValue::Value(vec![
+                NumericFieldValue::Value(Number::Integer(1)),
+                NumericFieldValue::Value(Number::Integer(2)),
+            ]));
+
+            assert_eq!(
+                vp.cast_to(FieldType::NumericField),
+                Err(CastError::InvalidValue {
+                    from: FieldType::NumericListField,
+                    to: FieldType::NumericField
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_cast_between_list_fields() {
+        {
+            let vp = LiteralValuePresenter::TableRowListField(TableRowListFieldValue::Value(vec![
+                TableRowFieldValue::Value(
+                    Uuid::new("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap(),
+                ),
+                TableRowFieldValue::Nil,
+            ]));
+
+            assert_eq!(
+                vp.cast_to(FieldType::RelationListField),
+                Ok(LiteralValuePresenter::RelationListField(
+                    RelationListFieldValue::Value(vec![
+                        RelationFieldValue::Value(RelationValue::new(
+                            ResourceType::DatabaseRow,
+                            Uuid::new("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap()
+                        )),
+                        RelationFieldValue::Nil,
+                    ])
+                ))
+            );
+        }
+
+        // nil
+        {
+            let vp = LiteralValuePresenter::NumericListField(NumericListFieldValue::Nil);
+
+            assert_eq!(
+                vp.cast_to(FieldType::SingleLineListField),
+                Ok(LiteralValuePresenter::SingleLineListField(
+                    SingleLineListFieldValue::Nil
+                ))
+            );
+        }
+
+        // invalid item
+        {
+            let vp =
+                LiteralValuePresenter::SingleLineListField(SingleLineListFieldValue::Value(vec![
+                    SingleLineFieldValue::Value("1".to_string()),
+                    SingleLineFieldValue::Value("foo".to_string()),
+                ]));
+
+            assert_eq!(
+                vp.cast_to(FieldType::NumericListField),
+                Err(CastError::InvalidValue {
+                    from: FieldType::SingleLineListField,
+                    to: FieldType::NumericListField
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_cast_between_date_time_fields() {
+        let date_time = NaiveDateTime::new(2022, 4, 29, 7, 34, 10, 0);
+
+        {
+            let vp =
+                LiteralValuePresenter::DateTimeField(DateTimeFieldValue::Value(date_time.clone()));
+
+            assert_eq!(
+                vp.cast_to(FieldType::DateField),
+                Ok(LiteralValuePresenter::DateField(DateFieldValue::Value(
+                    NaiveDate::new(2022, 4, 29)
+                )))
+            );
+            assert_eq!(
+                vp.cast_to(FieldType::TimeField),
+                Ok(LiteralValuePresenter::TimeField(TimeFieldValue::Value(
+                    NaiveTime::new(7, 34, 10, 0)
+                )))
+            );
+        }
+
+        // keeps the local date
+        {
+            let vp = LiteralValuePresenter::DateTimeField(DateTimeFieldValue::OffsetValue(
+                OffsetDateTime::new(NaiveDateTime::new(2022, 4, 29, 2, 0, 0, 0), 8 * 3600),
+            ));
+
+            assert_eq!(
+                vp.cast_to(FieldType::DateField),
+                Ok(LiteralValuePresenter::DateField(DateFieldValue::Value(
+                    NaiveDate::new(2022, 4, 29)
+                )))
+            );
+        }
+
+        {
+            let vp = LiteralValuePresenter::DateField(DateFieldValue::Value(NaiveDate::new(
+                2022, 4, 29,
+            )));
+
+            assert_eq!(
+                vp.cast_to(FieldType::DateTimeField),
+                Ok(LiteralValuePresenter::DateTimeField(
+                    DateTimeFieldValue::Value(NaiveDateTime::new(2022, 4, 29, 0, 0, 0, 0))
+                ))
+            );
+            assert_eq!(
+                vp.cast_to(FieldType::SingleLineField),
+                Ok(LiteralValuePresenter::SingleLineField(
+                    SingleLineFieldValue::Value("2022-04-29".to_string())
+                ))
+            );
+        }
+
+        {
+            let vp = LiteralValuePresenter::SingleLineField(SingleLineFieldValue::Value(
+                "07:34:10".to_string(),
+            ));
+
+            assert_eq!(
+                vp.cast_to(FieldType::TimeField),
+                Ok(LiteralValuePresenter::TimeField(TimeFieldValue::Value(
+                    NaiveTime::new(7, 34, 10, 0)
+                )))
+            );
+            assert_eq!(
+                vp.cast_to(FieldType::DateField),
+                Err(CastError::InvalidValue {
+                    from: FieldType::SingleLineField,
+                    to: FieldType::DateField
+                })
+            );
+        }
+
+        {
+            let vp = LiteralValuePresenter::TimeField(TimeFieldValue::Nil);
+
+            assert_eq!(
+                vp.cast_to(FieldType::SingleLineField),
+                Ok(LiteralValuePresenter::SingleLineField(
+                    SingleLineFieldValue::Nil
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn test_cast_between_single_line_and_multiple_line_field() {
+        let vp = LiteralValuePresenter::SingleLineField(SingleLineFieldValue::Value(
+            "Hello\nworld".to_string(),
+        ));
+        let multiple_line = vp.cast_to(FieldType::MultipleLineField).unwrap();
+
+        assert!(matches!(
+            multiple_line,
+            LiteralValuePresenter::MultipleLineField(MultipleLineFieldValue::Value(ref state))
+            if state.doc.content.len() == 2
+        ));
+        assert_eq!(multiple_line.cast_to(FieldType::SingleLineField), Ok(vp));
+
+        assert_eq!(
+            LiteralValuePresenter::MultipleLineField(MultipleLineFieldValue::Nil)
+                .cast_to(FieldType::SingleLineField),
+            Ok(LiteralValuePresenter::SingleLineField(
+                SingleLineFieldValue::Nil
+            ))
+        );
+    }
+
+    #[test]
+    fn test_unsupported_cast() {
+        {
+            let vp = LiteralValuePresenter::FileField(FileFieldValue::Nil);
+
+            assert_eq!(
+                vp.cast_to(FieldType::NumericField),
+                Err(CastError::UnsupportedCast {
+                    from: FieldType::FileField,
+                    to: FieldType::NumericField
+                })
+            );
+        }
+
+        {
+            let vp = LiteralValuePresenter::CascaderListField(CascaderListFieldValue::Nil);
+
+            assert_eq!(
+                vp.cast_to(FieldType::BooleanListField),
+                Err(CastError::UnsupportedCast {
+                    from: FieldType::CascaderListField,
+                    to: FieldType::BooleanListField
+                })
+            );
+        }
+    }
+}
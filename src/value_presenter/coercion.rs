@@ -0,0 +1,379 @@
+use serde_json::Value;
+use time::{macros::format_description, Time};
+
+use super::{
+    field_type::FieldType,
+    value::{
+        date_time_pattern::DateTimePattern, json_codec::JsonCodec, naive_date::NaiveDate,
+        naive_date_time::NaiveDateTime, naive_time::NaiveTime, number::Number,
+    },
+};
+
+/// The layouts coerced into a date time after the ISO ones, see
+/// `date_time_from_str`. Ambiguous layouts such as `DateTimePattern::UsDate`
+/// and `DateTimePattern::CompactDate` are left out.
+const DATE_TIME_PATTERNS: [DateTimePattern; 8] = [
+    DateTimePattern::DateTime,
+    DateTimePattern::DateTimeMinute,
+    DateTimePattern::Date,
+    DateTimePattern::SlashDateTime,
+    DateTimePattern::SlashDateTimeMinute,
+    DateTimePattern::SlashDate,
+    DateTimePattern::ChineseDate,
+    DateTimePattern::ChineseDateTimeMinute,
+];
+
+/// The layouts coerced into a date.
+const DATE_PATTERNS: [DateTimePattern; 3] = [
+    DateTimePattern::Date,
+    DateTimePattern::SlashDate,
+    DateTimePattern::ChineseDate,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoercionKind {
+    StringToNumber,
+    StringToBoolean,
+    NumberToBoolean,
+    DateTimeFormat,
+    SingleValueToList,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coercion {
+    pub field_type: FieldType,
+    pub kind: CoercionKind,
+}
+
+/// Converts `value` into the representation expected by `field_type` when it
+/// is held in a compatible but different form, e.g. `"42"` for a numeric field.
+///
+/// Values that are already valid, or that cannot be coerced, are returned
+/// unchanged; the strict decoder reports the latter as invalid.
+pub fn coerce(field_type: &FieldType, value: &Value, coercions: &mut Vec<Coercion>) -> Value {
+    if let Some(item_field_type) = field_type.item_field_type() {
+        return match value {
+            Value::Null => Value::Null,
+            Value::Array(values) => Value::Array(
+                values
+                    .iter()
+                    .map(|value| coerce(&item_field_type, value, coercions))
+                    .collect(),
+            ),
+            value => {
+                coercions.push(Coercion {
+                    field_type: *field_type,
+                    kind: CoercionKind::SingleValueToList,
+                });
+
+                Value::Array(vec![coerce(&item_field_type, value, coercions)])
+            }
+        };
+    }
+
+    let coerced = match (field_type, value) {
+        (FieldType::BooleanField, Value::String(str)) => {
+            boolean_from_str(str).map(|v| (Value::Bool(v), CoercionKind::StringToBoolean))
+        }
+        (FieldType::BooleanField, Value::Number(number)) => match number.as_u64() {
+            Some(0) => Some((Value::Bool(false), CoercionKind::NumberToBoolean)),
+            Some(1) => Some((Value::Bool(true), CoercionKind::NumberToBoolean)),
+            _ => None,
+        },
+        (FieldType::NumericField, Value::String(str)) => Number::parse_str(str)
+            .ok()
+            .map(|v| (v.to_json(), CoercionKind::StringToNumber)),
+        (FieldType::DateTimeField, Value::String(str)) => {
+            if NaiveDateTime::from_json(value).is_ok() {
+                None
+            } else {
+                date_time_from_str(str).map(|v| (v.to_json(), CoercionKind::DateTimeFormat))
+            }
+        }
+        (FieldType::DateField, Value::String(str)) => {
+            if NaiveDate::from_json(value).is_ok() {
+                None
+            } else {
+                NaiveDateTime::parse_any(str.trim(), &DATE_PATTERNS)
+                    .ok()
+                    .map(|v| (v.date().to_json(), CoercionKind::DateTimeFormat))
+            }
+        }
+        (FieldType::TimeField, Value::String(str)) => {
+            if NaiveTime::from_json(value).is_ok() {
+                None
+            } else {
+                Time::parse(str.trim(), format_description!("[hour]:[minute]"))
+                    .ok()
+                    .map(|v| (NaiveTime::from(v).to_json(), CoercionKind::DateTimeFormat))
+            }
+        }
+        _ => None,
+    };
+
+    match coerced {
+        Some((value, kind)) => {
+            coercions.push(Coercion {
+                field_type: *field_type,
+                kind,
+            });
+
+            value
+        }
+        None => value.clone(),
+    }
+}
+
+/// Parses `true`/`false` or `1`/`0`, ignoring case and surrounding whitespace.
+pub(super) fn boolean_from_str(str: &str) -> Option<bool> {
+    match str.trim().to_ascii_lowercase().as_str() {
+        "true" | "1" => Some(true),
+        "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parses ISO 8601 date times to the second or minute, then the layouts of
+/// `DATE_TIME_PATTERNS`, a date alone giving midnight.
+fn date_time_from_str(str: &str) -> Option<NaiveDateTime> {
+    let str = str.trim();
+
+    NaiveDateTime::parse_with(str, DateTimePattern::Iso8601.format_description())
+        .or_else(|_| {
+            NaiveDateTime::parse_with(
+                str,
+                format_description!("[year]-[month]-[day]T[hour]:[minute]"),
+            )
+        })
+        .or_else(|_| NaiveDateTime::parse_any(str, &DATE_TIME_PATTERNS))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_coerce_boolean() {
+        {
+            let mut coercions = vec![];
+            let value = coerce(&FieldType::BooleanField, &json!("true"), &mut coercions);
+
+            assert_eq!(value, json!(true));
+            assert_eq!(
+                coercions,
+                vec![Coercion {
+                    field_type: FieldType::BooleanField,
+                    kind: CoercionKind::StringToBoolean
+                }]
+            );
+        }
+
+        {
+            let mut coercions = vec![];
+            let value = coerce(&FieldType::BooleanField, &json!(0), &mut coercions);
+
+            assert_eq!(value, json!(false));
+            assert_eq!(coercions[0].kind, CoercionKind::NumberToBoolean);
+        }
+
+        // not coercible
+        {
+            let mut coercions = vec![];
+            let value = coerce(&FieldType::BooleanField, &json!(2), &mut coercions);
+
+            assert_eq!(value, json!(2));
+            assert!(coercions.is_empty());
+        }
+
+        // already valid
+        {
+            let mut coercions = vec![];
+            let value = coerce(&FieldType::BooleanField, &json!(true), &mut coercions);
+
+            assert_eq!(value, json!(true));
+            assert!(coercions.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_coerce_number() {
+        {
+            let mut coercions = vec![];
+            let value = coerce(&FieldType::NumericField, &json!(" 42 "), &mut coercions);
+
+            assert_eq!(value, json!(42));
+            assert_eq!(coercions[0].kind, CoercionKind::StringToNumber);
+        }
+
+        {
+            let mut coercions = vec![];
+            let value = coerce(&FieldType::NumericField, &json!("-1.5"), &mut coercions);
+
+            assert_eq!(value, json!(-1.5));
+        }
+
+        // not coercible
+        {
+            let mut coercions = vec![];
+            let value = coerce(&FieldType::NumericField, &json!("NaN"), &mut coercions);
+
+            assert_eq!(value, json!("NaN"));
+            assert!(coercions.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_coerce_date_time() {
+        {
+            let mut coercions = vec![];
+            let value = coerce(
+                &FieldType::DateTimeField,
+                &json!("2022-04-29 07:34"),
+                &mut coercions,
+            );
+
+            assert_eq!(value, json!("2022-04-29T07:34:00"));
+            assert_eq!(coercions[0].kind, CoercionKind::DateTimeFormat);
+        }
+
+        {
+            let mut coercions = vec![];
+            let value = coerce(
+                &FieldType::DateTimeField,
+                &json!("2022/04/29"),
+                &mut coercions,
+            );
+
+            assert_eq!(value, json!("2022-04-29T00:00:00"));
+        }
+
+        {
+            let mut coercions = vec![];
+            let value = coerce(
+                &FieldType::DateTimeField,
+                &json!(" 2022年04月29日 07:34 "),
+                &mut coercions,
+            );
+
+            assert_eq!(value, json!("2022-04-29T07:34:00"));
+        }
+
+        // already valid
+        {
+            let mut coercions = vec![];
+            let value = coerce(
+                &FieldType::DateTimeField,
+                &json!("2022-04-29T07:34:10Z"),
+                &mut coercions,
+            );
+
+            assert_eq!(value, json!("2022-04-29T07:34:10Z"));
+            assert!(coercions.is_empty());
+        }
+
+        // minute precision
+        {
+            let mut coercions = vec![];
+            let value = coerce(
+                &FieldType::DateTimeField,
+                &json!("2022-04-29T07:34"),
+                &mut coercions,
+            );
+
+            assert_eq!(value, json!("2022-04-29T07:34:00"));
+            assert_eq!(coercions[0].kind, CoercionKind::DateTimeFormat);
+        }
+
+        // ambiguous layouts are not coerced
+        for str in ["04/29/2022", "20220429"] {
+            let mut coercions = vec![];
+            let value = coerce(&FieldType::DateTimeField, &json!(str), &mut coercions);
+
+            assert_eq!(value, json!(str));
+            assert!(coercions.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_coerce_date_and_time() {
+        {
+            let mut coercions = vec![];
+            let value = coerce(&FieldType::DateField, &json!("2022/04/29"), &mut coercions);
+
+            assert_eq!(value, json!("2022-04-29"));
+            assert_eq!(coercions[0].kind, CoercionKind::DateTimeFormat);
+        }
+
+        {
+            let mut coercions = vec![];
+            let value = coerce(&FieldType::TimeField, &json!(" 07:34 "), &mut coercions);
+
+            assert_eq!(value, json!("07:34:00"));
+            assert_eq!(coercions[0].kind, CoercionKind::DateTimeFormat);
+        }
+
+        // already valid
+        {
+            let mut coercions = vec![];
+            let value = coerce(&FieldType::DateField, &json!("2022-04-29"), &mut coercions);
+
+            assert_eq!(value, json!("2022-04-29"));
+            assert!(coercions.is_empty());
+        }
+
+        // not coercible
+        {
+            let mut coercions = vec![];
+            let value = coerce(&FieldType::TimeField, &json!("7pm"), &mut coercions);
+
+            assert_eq!(value, json!("7pm"));
+            assert!(coercions.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_coerce_list() {
+        {
+            let mut coercions = vec![];
+            let value = coerce(&FieldType::NumericListField, &json!("42"), &mut coercions);
+
+            assert_eq!(value, json!([42]));
+            assert_eq!(
+                coercions,
+                vec![
+                    Coercion {
+                        field_type: FieldType::NumericListField,
+                        kind: CoercionKind::SingleValueToList
+                    },
+                    Coercion {
+                        field_type: FieldType::NumericField,
+                        kind: CoercionKind::StringToNumber
+                    }
+                ]
+            );
+        }
+
+        {
+            let mut coercions = vec![];
+            let value = coerce(
+                &FieldType::BooleanListField,
+                &json!([1, "false", null]),
+                &mut coercions,
+            );
+
+            assert_eq!(value, json!([true, false, null]));
+            assert_eq!(coercions.len(), 2);
+        }
+
+        // null
+        {
+            let mut coercions = vec![];
+            let value = coerce(&FieldType::BooleanListField, &json!(null), &mut coercions);
+
+            assert_eq!(value, json!(null));
+            assert!(coercions.is_empty());
+        }
+    }
+}
@@ -0,0 +1,659 @@
+//! Conversions between Rust values and value presenters.
+//!
+//! Outputs are built with `From`/`Into` (or the `literal!` macro), inputs are
+//! extracted with `TryFrom<&ValuePresenter>`:
+//!
+//! ```
+//! use core::convert::TryFrom;
+//! use jet_programmable_rust_binding::{literal, value_presenter::ValuePresenter};
+//!
+//! let input: ValuePresenter = literal!(NumericField, 41);
+//! let n = i64::try_from(&input).unwrap();
+//!
+//! let output: ValuePresenter = literal!(n + 1);
+//! assert_eq!(output, ValuePresenter::from(42));
+//! ```
+
+use core::convert::TryFrom;
+
+use super::{
+    field_type::FieldType,
+    literal_list_value::{
+        BooleanListFieldValue, CascaderListFieldValue, CheckboxListFieldValue, DateListFieldValue,
+        DateTimeListFieldValue, FileListFieldValue, MultipleLineListFieldValue,
+        NumericListFieldValue, RadioButtonListFieldValue, RelationListFieldValue,
+        SingleLineListFieldValue, TableRowListFieldValue, TimeListFieldValue,
+        UserBoundaryListFieldValue,
+    },
+    literal_naive_value::{
+        BooleanFieldValue, CascaderFieldValue, CheckboxFieldValue, DateFieldValue,
+        DateTimeFieldValue, FileFieldValue, MultipleLineFieldValue, NumericFieldValue,
+        RadioButtonFieldValue, RelationFieldValue, SingleLineFieldValue, TableRowFieldValue,
+        TimeFieldValue, UserBoundaryFieldValue,
+    },
+    literal_value_presenter::LiteralValuePresenter,
+    value::{
+        cascader_value::CascaderValue,
+        file_object::FileObject,
+        naive_date::NaiveDate,
+        naive_date_time::NaiveDateTime,
+        naive_time::NaiveTime,
+        number::{BigDecimal, Number},
+        offset_date_time::OffsetDateTime,
+        options_value::OptionsValue,
+        prosemirror::ProsemirrorState,
+        relation_value::RelationValue,
+        user_boundary::UserBoundary,
+        uuid::Uuid,
+    },
+    ValuePresenter,
+};
+
+/// Builds a `ValuePresenter` from a Rust value.
+///
+/// * `literal!(value)` picks the field type from the value's type,
+///   e.g. `i64` becomes a `NumericField` and `String` a `SingleLineField`.
+/// * `literal!(Field, value)` converts the value into the given field.
+/// * `literal!(Field, nil)` is the `Nil` value of the given field.
+#[macro_export]
+macro_rules! literal {
+    ($field:ident, nil) => {
+        $crate::value_presenter::ValuePresenter::Literal(
+            $crate::value_presenter::literal_value_presenter::LiteralValuePresenter::nil(
+                $crate::value_presenter::field_type::FieldType::$field,
+            ),
+        )
+    };
+    ($field:ident, $value:expr) => {
+        $crate::value_presenter::ValuePresenter::Literal(
+            $crate::value_presenter::literal_value_presenter::LiteralValuePresenter::$field(
+                ::core::convert::From::from($value),
+            ),
+        )
+    };
+    ($value:expr) => {
+        $crate::value_presenter::ValuePresenter::from($value)
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtractError {
+    MismatchedFieldType {
+        expected: FieldType,
+        found: FieldType,
+    },
+    Nil,
+    InvalidValue,
+}
+
+impl From<i32> for Number {
+    fn from(value: i32) -> Self {
+        Number::Integer(value as i64)
+    }
+}
+
+impl From<i64> for Number {
+    fn from(value: i64) -> Self {
+        Number::Integer(value)
+    }
+}
+
+impl From<f64> for Number {
+    fn from(value: f64) -> Self {
+        Number::Float(value)
+    }
+}
+
+impl From<BigDecimal> for Number {
+    fn from(value: BigDecimal) -> Self {
+        Number::Decimal(value)
+    }
+}
+
+macro_rules! impl_from_for_field_value {
+    ($($field_value:ident($($from:ty),*) => $list_field_value:ident;)*) => {
+        $(
+            $(
+                impl From<$from> for $field_value {
+                    fn from(value: $from) -> Self {
+                        $field_value::Value(value.into())
+                    }
+                }
+            )*
+
+            impl<T: Into<$field_value>> From<Option<T>> for $field_value {
+                fn from(value: Option<T>) -> Self {
+                    match value {
+                        Some(value) => value.into(),
+                        None => $field_value::Nil,
+                    }
+                }
+            }
+
+            impl<T: Into<$field_value>> From<Vec<T>> for $list_field_value {
+                fn from(values: Vec<T>) -> Self {
+                    $list_field_value::Value(values.into_iter().map(Into::into).collect())
+                }
+            }
+
+            impl<T: Into<$list_field_value>> From<Option<T>> for $list_field_value {
+                fn from(value: Option<T>) -> Self {
+                    match value {
+                        Some(value) => value.into(),
+                        None => $list_field_value::Nil,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_field_value! {
+    BooleanFieldValue(bool) => BooleanListFieldValue;
+    CascaderFieldValue(CascaderValue) => CascaderListFieldValue;
+    CheckboxFieldValue(OptionsValue) => CheckboxListFieldValue;
+    DateFieldValue(NaiveDate) => DateListFieldValue;
+    DateTimeFieldValue(NaiveDateTime) => DateTimeListFieldValue;
+    FileFieldValue(FileObject) => FileListFieldValue;
+    MultipleLineFieldValue(ProsemirrorState) => MultipleLineListFieldValue;
+    NumericFieldValue(Number, i32, i64, f64, BigDecimal) => NumericListFieldValue;
+    RadioButtonFieldValue(OptionsValue) => RadioButtonListFieldValue;
+    RelationFieldValue(RelationValue) => RelationListFieldValue;
+    SingleLineFieldValue(String, &str) => SingleLineListFieldValue;
+    TableRowFieldValue(Uuid) => TableRowListFieldValue;
+    TimeFieldValue(NaiveTime) => TimeListFieldValue;
+    UserBoundaryFieldValue(UserBoundary) => UserBoundaryListFieldValue;
+}
+
+macro_rules! impl_from_for_presenter {
+    ($($from:ty => $field:ident;)*) => {
+        $(
+            impl From<$from> for LiteralValuePresenter {
+                fn from(value: $from) -> Self {
+                    LiteralValuePresenter::$field(value.into())
+                }
+            }
+
+            impl From<$from> for ValuePresenter {
+                fn from(value: $from) -> Self {
+                    ValuePresenter::Literal(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_presenter! {
+    BooleanFieldValue => BooleanField;
+    CascaderFieldValue => CascaderField;
+    CheckboxFieldValue => CheckboxField;
+    DateFieldValue => DateField;
+    DateTimeFieldValue => DateTimeField;
+    FileFieldValue => FileField;
+    MultipleLineFieldValue => MultipleLineField;
+    NumericFieldValue => NumericField;
+    RadioButtonFieldValue => RadioButtonField;
+    RelationFieldValue => RelationField;
+    SingleLineFieldValue => SingleLineField;
+    TableRowFieldValue => TableRowField;
+    TimeFieldValue => TimeField;
+    UserBoundaryFieldValue => UserBoundaryField;
+
+    BooleanListFieldValue => BooleanListField;
+    CascaderListFieldValue => CascaderListField;
+    CheckboxListFieldValue => CheckboxListField;
+    DateListFieldValue => DateListField;
+    DateTimeListFieldValue => DateTimeListField;
+    FileListFieldValue => FileListField;
+    MultipleLineListFieldValue => MultipleLineListField;
+    NumericListFieldValue => NumericListField;
+    RadioButtonListFieldValue => RadioButtonListField;
+    RelationListFieldValue => RelationListField;
+    SingleLineListFieldValue => SingleLineListField;
+    TableRowListFieldValue => TableRowListField;
+    TimeListFieldValue => TimeListField;
+    UserBoundaryListFieldValue => UserBoundaryListField;
+
+    bool => BooleanField;
+    Option<bool> => BooleanField;
+    Vec<bool> => BooleanListField;
+    Vec<Option<bool>> => BooleanListField;
+    NaiveDate => DateField;
+    Option<NaiveDate> => DateField;
+    Vec<NaiveDate> => DateListField;
+    Vec<Option<NaiveDate>> => DateListField;
+    NaiveDateTime => DateTimeField;
+    Option<NaiveDateTime> => DateTimeField;
+    Vec<NaiveDateTime> => DateTimeListField;
+    Vec<Option<NaiveDateTime>> => DateTimeListField;
+    OffsetDateTime => DateTimeField;
+    Option<OffsetDateTime> => DateTimeField;
+    Vec<OffsetDateTime> => DateTimeListField;
+    Vec<Option<OffsetDateTime>> => DateTimeListField;
+    NaiveTime => TimeField;
+    Option<NaiveTime> => TimeField;
+    Vec<NaiveTime> => TimeListField;
+    Vec<Option<NaiveTime>> => TimeListField;
+    Number => NumericField;
+    Option<Number> => NumericField;
+    Vec<Number> => NumericListField;
+    Vec<Option<Number>> => NumericListField;
+    i32 => NumericField;
+    Option<i32> => NumericField;
+    Vec<i32> => NumericListField;
+    Vec<Option<i32>> => NumericListField;
+    i64 => NumericField;
+    Option<i64> => NumericField;
+    Vec<i64> => NumericListField;
+    Vec<Option<i64>> => NumericListField;
+    f64 => NumericField;
+    Option<f64> => NumericField;
+    Vec<f64> => NumericListField;
+    Vec<Option<f64>> => NumericListField;
+    BigDecimal => NumericField;
+    Option<BigDecimal> => NumericField;
+    Vec<BigDecimal> => NumericListField;
+    Vec<Option<BigDecimal>> => NumericListField;
+    String => SingleLineField;
+    Option<String> => SingleLineField;
+    Vec<String> => SingleLineListField;
+    Vec<Option<String>> => SingleLineListField;
+    &str => SingleLineField;
+    Option<&str> => SingleLineField;
+    Vec<&str> => SingleLineListField;
+    Vec<Option<&str>> => SingleLineListField;
+}
+
+impl From<OffsetDateTime> for DateTimeFieldValue {
+    fn from(value: OffsetDateTime) -> Self {
+        DateTimeFieldValue::OffsetValue(value)
+    }
+}
+
+/// Builds a document with one paragraph per line.
+impl From<&SingleLineFieldValue> for MultipleLineFieldValue {
+    fn from(value: &SingleLineFieldValue) -> Self {
+        match value {
+            SingleLineFieldValue::Value(text) => {
+                MultipleLineFieldValue::Value(ProsemirrorState::from_plain_text(text))
+            }
+            SingleLineFieldValue::Nil => MultipleLineFieldValue::Nil,
+        }
+    }
+}
+
+/// Keeps the text of the document, one line per textblock.
+impl From<&MultipleLineFieldValue> for SingleLineFieldValue {
+    fn from(value: &MultipleLineFieldValue) -> Self {
+        match value {
+            MultipleLineFieldValue::Value(state) => {
+                SingleLineFieldValue::Value(state.to_plain_text())
+            }
+            MultipleLineFieldValue::Nil => SingleLineFieldValue::Nil,
+        }
+    }
+}
+
+/// Keeps the options followed by `other`.
+impl From<&OptionsValue> for SingleLineListFieldValue {
+    fn from(value: &OptionsValue) -> Self {
+        value
+            .selected()
+            .map(|option| SingleLineFieldValue::Value(option.clone()))
+            .collect()
+    }
+}
+
+/// Keeps the non-nil items as options, a `Nil` list having no option. A list
+/// does not tell which item was `other`, so converting an `OptionsValue` to a
+/// list and back gives its `other` as the last option.
+impl From<&SingleLineListFieldValue> for OptionsValue {
+    fn from(value: &SingleLineListFieldValue) -> Self {
+        OptionsValue {
+            options: value
+                .iter()
+                .filter_map(|item| match item {
+                    SingleLineFieldValue::Value(option) => Some(option.clone()),
+                    SingleLineFieldValue::Nil => None,
+                })
+                .collect(),
+            other: None,
+        }
+    }
+}
+
+impl From<LiteralValuePresenter> for ValuePresenter {
+    fn from(value: LiteralValuePresenter) -> Self {
+        ValuePresenter::Literal(value)
+    }
+}
+
+macro_rules! impl_try_from_presenter {
+    ($($to:ty => $field:ident($field_value:ident), $list_field:ident($list_field_value:ident), {
+        $($pattern:pat => $extract:expr),+ $(,)?
+    };)*) => {
+        $(
+            impl TryFrom<&LiteralValuePresenter> for Option<$to> {
+                type Error = ExtractError;
+
+                fn try_from(vp: &LiteralValuePresenter) -> Result<Self, Self::Error> {
+                    match vp {
+                        LiteralValuePresenter::$field(value) => match value {
+                            $($pattern => $extract.map(Some),)+
+                            $field_value::Nil => Ok(None),
+                        },
+                        vp => Err(ExtractError::MismatchedFieldType {
+                            expected: FieldType::$field,
+                            found: vp.get_field_type(),
+                        }),
+                    }
+                }
+            }
+
+            impl TryFrom<&LiteralValuePresenter> for $to {
+                type Error = ExtractError;
+
+                fn try_from(vp: &LiteralValuePresenter) -> Result<Self, Self::Error> {
+                    Option::<$to>::try_from(vp)?.ok_or(ExtractError::Nil)
+                }
+            }
+
+            impl TryFrom<&LiteralValuePresenter> for Vec<Option<$to>> {
+                type Error = ExtractError;
+
+                fn try_from(vp: &LiteralValuePresenter) -> Result<Self, Self::Error> {
+                    match vp {
+                        LiteralValuePresenter::$list_field($list_field_value::Value(values)) => values
+                            .iter()
+                            .map(|value| match value {
+                                $($pattern => $extract.map(Some),)+
+                                $field_value::Nil => Ok(None),
+                            })
+                            .collect(),
+                        LiteralValuePresenter::$list_field($list_field_value::Nil) => {
+                            Err(ExtractError::Nil)
+                        }
+                        vp => Err(ExtractError::MismatchedFieldType {
+                            expected: FieldType::$list_field,
+                            found: vp.get_field_type(),
+                        }),
+                    }
+                }
+            }
+
+            impl TryFrom<&LiteralValuePresenter> for Vec<$to> {
+                type Error = ExtractError;
+
+                fn try_from(vp: &LiteralValuePresenter) -> Result<Self, Self::Error> {
+                    Vec::<Option<$to>>::try_from(vp)?
+                        .into_iter()
+                        .map(|value| value.ok_or(ExtractError::Nil))
+                        .collect()
+                }
+            }
+
+            impl_try_from_presenter!(@value_presenter $to);
+            impl_try_from_presenter!(@value_presenter Option<$to>);
+            impl_try_from_presenter!(@value_presenter Vec<$to>);
+            impl_try_from_presenter!(@value_presenter Vec<Option<$to>>);
+        )*
+    };
+    (@value_presenter $to:ty) => {
+        impl TryFrom<&ValuePresenter> for $to {
+            type Error = ExtractError;
+
+            fn try_from(vp: &ValuePresenter) -> Result<Self, Self::Error> {
+                match vp {
+                    ValuePresenter::Literal(vp) => <$to>::try_from(vp),
+                }
+            }
+        }
+    };
+}
+
+impl_try_from_presenter! {
+    bool => BooleanField(BooleanFieldValue), BooleanListField(BooleanListFieldValue), {
+        BooleanFieldValue::Value(v) => Ok(*v),
+    };
+    NaiveDate => DateField(DateFieldValue), DateListField(DateListFieldValue), {
+        DateFieldValue::Value(v) => Ok(v.clone()),
+    };
+    NaiveDateTime => DateTimeField(DateTimeFieldValue), DateTimeListField(DateTimeListFieldValue), {
+        DateTimeFieldValue::Value(v) => Ok(v.clone()),
+        DateTimeFieldValue::OffsetValue(v) => v.to_utc().ok_or(ExtractError::InvalidValue),
+    };
+    OffsetDateTime => DateTimeField(DateTimeFieldValue), DateTimeListField(DateTimeListFieldValue), {
+        DateTimeFieldValue::Value(v) => Ok(OffsetDateTime::new(v.clone(), 0)),
+        DateTimeFieldValue::OffsetValue(v) => Ok(v.clone()),
+    };
+    NaiveTime => TimeField(TimeFieldValue), TimeListField(TimeListFieldValue), {
+        TimeFieldValue::Value(v) => Ok(v.clone()),
+    };
+    Number => NumericField(NumericFieldValue), NumericListField(NumericListFieldValue), {
+        NumericFieldValue::Value(v) => Ok(v.clone()),
+    };
+    i64 => NumericField(NumericFieldValue), NumericListField(NumericListFieldValue), {
+        NumericFieldValue::Value(Number::Integer(i)) => Ok(*i),
+        NumericFieldValue::Value(_v) => Err(ExtractError::InvalidValue),
+    };
+    f64 => NumericField(NumericFieldValue), NumericListField(NumericListFieldValue), {
+        NumericFieldValue::Value(v) => v.to_f64().ok_or(ExtractError::InvalidValue),
+    };
+    BigDecimal => NumericField(NumericFieldValue), NumericListField(NumericListFieldValue), {
+        NumericFieldValue::Value(v) => v.to_decimal().ok_or(ExtractError::InvalidValue),
+    };
+    String => SingleLineField(SingleLineFieldValue), SingleLineListField(SingleLineListFieldValue), {
+        SingleLineFieldValue::Value(v) => Ok(v.clone()),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_primitive() {
+        assert_eq!(
+            ValuePresenter::from(42),
+            ValuePresenter::Literal(LiteralValuePresenter::NumericField(
+                NumericFieldValue::Value(Number::Integer(42))
+            ))
+        );
+        assert_eq!(
+            ValuePresenter::from(1.5),
+            ValuePresenter::Literal(LiteralValuePresenter::NumericField(
+                NumericFieldValue::Value(Number::Float(1.5))
+            ))
+        );
+        assert_eq!(
+            ValuePresenter::from(true),
+            ValuePresenter::Literal(LiteralValuePresenter::BooleanField(
+                BooleanFieldValue::Value(true)
+            ))
+        );
+        assert_eq!(
+            ValuePresenter::from("foo"),
+            ValuePresenter::Literal(LiteralValuePresenter::SingleLineField(
+                SingleLineFieldValue::Value("foo".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_from_option() {
+        assert_eq!(NumericFieldValue::from(None::<i64>), NumericFieldValue::Nil);
+        assert_eq!(
+            NumericFieldValue::from(Some(1)),
+            NumericFieldValue::Value(Number::Integer(1))
+        );
+        assert_eq!(
+            ValuePresenter::from(None::<String>),
+            ValuePresenter::Literal(LiteralValuePresenter::SingleLineField(
+                SingleLineFieldValue::Nil
+            ))
+        );
+        assert_eq!(
+            NumericListFieldValue::from(None::<Vec<i64>>),
+            NumericListFieldValue::Nil
+        );
+    }
+
+    #[test]
+    fn test_from_vec() {
+        assert_eq!(
+            NumericListFieldValue::from(vec![Some(1), None]),
+            NumericListFieldValue::Value(vec![
+                NumericFieldValue::Value(Number::Integer(1)),
+                NumericFieldValue::Nil
+            ])
+        );
+        assert_eq!(
+            ValuePresenter::from(vec!["foo", "bar"]),
+            ValuePresenter::Literal(LiteralValuePresenter::SingleLineListField(
+                SingleLineListFieldValue::Value(vec![
+                    SingleLineFieldValue::Value("foo".to_string()),
+                    SingleLineFieldValue::Value("bar".to_string()),
+                ])
+            ))
+        );
+    }
+
+    #[test]
+    fn test_literal_macro() {
+        assert_eq!(literal!(42), ValuePresenter::from(42));
+        assert_eq!(
+            literal!(NumericField, 42),
+            ValuePresenter::Literal(LiteralValuePresenter::NumericField(
+                NumericFieldValue::Value(Number::Integer(42))
+            ))
+        );
+        assert_eq!(
+            literal!(
+                CheckboxField,
+                OptionsValue {
+                    options: vec!["foo".to_string()],
+                    other: None
+                }
+            ),
+            ValuePresenter::Literal(LiteralValuePresenter::CheckboxField(
+                CheckboxFieldValue::Value(OptionsValue {
+                    options: vec!["foo".to_string()],
+                    other: None
+                })
+            ))
+        );
+        assert_eq!(
+            literal!(DateTimeField, nil),
+            ValuePresenter::Literal(LiteralValuePresenter::DateTimeField(
+                DateTimeFieldValue::Nil
+            ))
+        );
+    }
+
+    #[test]
+    fn test_try_from_value_presenter() {
+        {
+            let vp = literal!(42);
+
+            assert_eq!(i64::try_from(&vp), Ok(42));
+            assert_eq!(f64::try_from(&vp), Ok(42.0));
+            assert_eq!(Option::<i64>::try_from(&vp), Ok(Some(42)));
+            assert_eq!(
+                bool::try_from(&vp),
+                Err(ExtractError::MismatchedFieldType {
+                    expected: FieldType::BooleanField,
+                    found: FieldType::NumericField
+                })
+            );
+        }
+
+        {
+            let vp = literal!(1.5);
+
+            assert_eq!(i64::try_from(&vp), Err(ExtractError::InvalidValue));
+        }
+
+        // date time with offset
+        {
+            let odt = OffsetDateTime::new(NaiveDateTime::new(2022, 5, 1, 2, 0, 0, 0), 8 * 3600);
+            let vp = literal!(odt.clone());
+
+            assert_eq!(OffsetDateTime::try_from(&vp), Ok(odt));
+            assert_eq!(
+                NaiveDateTime::try_from(&vp),
+                Ok(NaiveDateTime::new(2022, 4, 30, 18, 0, 0, 0))
+            );
+        }
+
+        // date and time
+        {
+            let vp = literal!(vec![Some(NaiveDate::new(2022, 4, 29)), None]);
+
+            assert_eq!(
+                Vec::<Option<NaiveDate>>::try_from(&vp),
+                Ok(vec![Some(NaiveDate::new(2022, 4, 29)), None])
+            );
+            assert_eq!(
+                NaiveTime::try_from(&literal!(NaiveTime::new(7, 34, 10, 0))),
+                Ok(NaiveTime::new(7, 34, 10, 0))
+            );
+        }
+
+        // nil
+        {
+            let vp = literal!(SingleLineField, nil);
+
+            assert_eq!(String::try_from(&vp), Err(ExtractError::Nil));
+            assert_eq!(Option::<String>::try_from(&vp), Ok(None));
+        }
+
+        // list
+        {
+            let vp = literal!(vec![Some(true), None]);
+
+            assert_eq!(
+                Vec::<Option<bool>>::try_from(&vp),
+                Ok(vec![Some(true), None])
+            );
+            assert_eq!(Vec::<bool>::try_from(&vp), Err(ExtractError::Nil));
+        }
+    }
+
+    #[test]
+    fn test_options_value_and_single_line_list() {
+        let options_value = OptionsValue {
+            options: vec!["a".to_string(), "b".to_string()],
+            other: Some("x".to_string()),
+        };
+
+        assert_eq!(
+            SingleLineListFieldValue::from(&options_value),
+            SingleLineListFieldValue::from(vec!["a", "b", "x"])
+        );
+        assert_eq!(
+            OptionsValue::from(&SingleLineListFieldValue::from(vec![Some("a"), None])),
+            OptionsValue {
+                options: vec!["a".to_string()],
+                other: None,
+            }
+        );
+        assert_eq!(
+            OptionsValue::from(&SingleLineListFieldValue::Nil),
+            OptionsValue {
+                options: vec![],
+                other: None,
+            }
+        );
+
+        // the round trip turns `other` into an option
+        let round_trip = OptionsValue::from(&SingleLineListFieldValue::from(&options_value));
+        assert_eq!(
+            round_trip,
+            OptionsValue {
+                options: vec!["a".to_string(), "b".to_string(), "x".to_string()],
+                other: None,
+            }
+        );
+        assert!(round_trip.set_eq(&options_value));
+    }
+}
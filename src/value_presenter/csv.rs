@@ -0,0 +1,573 @@
+//! Reading and writing columns of list values as CSV or TSV.
+//!
+//! `parse_columns` turns each column of a CSV into a list value of the given
+//! list field type, e.g. `NumericListField`, converting cells as
+//! `LiteralValuePresenter::cast_to` converts a `SingleLineField`. Empty cells
+//! and missing cells of short rows are `Nil`, and a cell that cannot be
+//! converted is `Nil` with a `CellError` reporting it. `write_columns` is the
+//! reverse, converting the items of each list value to a `SingleLineField`.
+//!
+//! XLSX workbooks are read the same way by `xlsx::parse_columns`. With the
+//! `files` feature an uploaded file is parsed with e.g.
+//!
+//! ```ignore
+//! let text = String::from_utf8(file_object.read()?)?;
+//! let table = parse_columns(
+//!     &text,
+//!     &[FieldType::SingleLineListField, FieldType::NumericListField],
+//!     CsvOptions::default(),
+//! )?;
+//! ```
+
+use super::{
+    cast::{list_from_items, list_items, CastError},
+    field_type::FieldType,
+    literal_naive_value::SingleLineFieldValue,
+    literal_value_presenter::LiteralValuePresenter,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// The character separating cells, `,` by default.
+    pub delimiter: char,
+    /// Whether the first row is a header row, `true` by default.
+    pub header: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            header: true,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Tab separated values with a header row.
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: '\t',
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvError {
+    /// A quoted cell starting on `line` is not closed.
+    UnterminatedQuote { line: usize },
+    /// A quoted cell on `line` is followed by something other than a delimiter
+    /// or the end of the row.
+    UnexpectedCharacter { line: usize },
+    /// The field type is not a list field type whose items can be converted
+    /// from and to a `SingleLineField`.
+    UnsupportedFieldType(FieldType),
+    /// The header row to write has a different number of cells than there are
+    /// columns.
+    HeaderLengthMismatch { header: usize, columns: usize },
+    /// The item at `row` of the list value of `column` cannot be written.
+    InvalidValue {
+        row: usize,
+        column: usize,
+        error: CastError,
+    },
+}
+
+/// A cell that cannot be converted to the item field type of its column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellError {
+    /// The index of the row, not counting the header row.
+    pub row: usize,
+    pub column: usize,
+    pub value: String,
+    pub error: CastError,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvTable {
+    /// The cells of the header row, empty without one.
+    pub header: Vec<String>,
+    /// A list value for each column, with an item for each row.
+    pub columns: Vec<LiteralValuePresenter>,
+    pub errors: Vec<CellError>,
+}
+
+/// Parses the columns of `text` into list values of `field_types`, a column
+/// for each field type. Cells of columns past the last field type are
+/// ignored.
+pub fn parse_columns(
+    text: &str,
+    field_types: &[FieldType],
+    options: CsvOptions,
+) -> Result<CsvTable, CsvError> {
+    let item_field_types = item_field_types(field_types).map_err(CsvError::UnsupportedFieldType)?;
+    let records = parse_records(text, options.delimiter)?;
+
+    Ok(table_from_records(
+        records,
+        field_types,
+        &item_field_types,
+        options.header,
+    ))
+}
+
+/// Writes `columns`, which are list values, as rows of cells, a row for each
+/// item. The header row is written if `options.header` is `true` and `header`
+/// is not empty, in which case it needs a cell for each column. `Nil` lists
+/// and items are written as empty cells.
+pub fn write_columns<S>(
+    header: &[S],
+    columns: &[LiteralValuePresenter],
+    options: CsvOptions,
+) -> Result<String, CsvError>
+where
+    S: AsRef<str>,
+{
+    let write_header = options.header && !header.is_empty();
+
+    if write_header && header.len() != columns.len() {
+        return Err(CsvError::HeaderLengthMismatch {
+            header: header.len(),
+            columns: columns.len(),
+        });
+    }
+
+    let mut cells = vec![];
+
+    for (column, value) in columns.iter().enumerate() {
+        let field_type = value.get_field_type();
+
+        if field_type.item_field_type().is_none() {
+            return Err(CsvError::UnsupportedFieldType(field_type));
+        }
+
+        let mut items = vec![];
+
+        for (row, item) in list_items(value).unwrap_or_default().iter().enumerate() {
+            match item.cast_to(FieldType::SingleLineField) {
+                Ok(LiteralValuePresenter::SingleLineField(SingleLineFieldValue::Value(text))) => {
+                    items.push(text)
+                }
+                Ok(_nil) => items.push(String::new()),
+                Err(error) => return Err(CsvError::InvalidValue { row, column, error }),
+            }
+        }
+
+        cells.push(items);
+    }
+
+    let mut text = String::new();
+
+    if write_header {
+        let header: Vec<&str> = header.iter().map(AsRef::as_ref).collect();
+        write_record(&mut text, &header, options.delimiter);
+    }
+
+    let rows = cells.iter().map(Vec::len).max().unwrap_or(0);
+
+    for row in 0..rows {
+        let record: Vec<&str> = cells
+            .iter()
+            .map(|items| items.get(row).map(String::as_str).unwrap_or_default())
+            .collect();
+
+        write_record(&mut text, &record, options.delimiter);
+    }
+
+    Ok(text)
+}
+
+/// The item field type of each of `field_types`, or the first field type
+/// whose items cannot be converted from text.
+pub(super) fn item_field_types(field_types: &[FieldType]) -> Result<Vec<FieldType>, FieldType> {
+    field_types
+        .iter()
+        .map(|field_type| match field_type.item_field_type() {
+            Some(item_field_type) if converts_from_text(item_field_type) => Ok(item_field_type),
+            _ => Err(*field_type),
+        })
+        .collect()
+}
+
+/// Converts the cells of `records` into a list value of each of `field_types`,
+/// the first record being the header row if `header` is `true`.
+pub(super) fn table_from_records(
+    mut records: Vec<Vec<String>>,
+    field_types: &[FieldType],
+    item_field_types: &[FieldType],
+    header: bool,
+) -> CsvTable {
+    let header = if header && !records.is_empty() {
+        records.remove(0)
+    } else {
+        vec![]
+    };
+
+    let mut items = vec![vec![]; field_types.len()];
+    let mut errors = vec![];
+
+    for (row, record) in records.iter().enumerate() {
+        for (column, item_field_type) in item_field_types.iter().enumerate() {
+            let value = record.get(column).map(String::as_str).unwrap_or_default();
+
+            let item = parse_cell(value, *item_field_type).unwrap_or_else(|error| {
+                errors.push(CellError {
+                    row,
+                    column,
+                    value: value.to_string(),
+                    error,
+                });
+
+                LiteralValuePresenter::nil(*item_field_type)
+            });
+
+            items[column].push(item);
+        }
+    }
+
+    CsvTable {
+        header,
+        columns: field_types
+            .iter()
+            .zip(items)
+            .map(|(field_type, items)| list_from_items(*field_type, items))
+            .collect(),
+        errors,
+    }
+}
+
+fn converts_from_text(field_type: FieldType) -> bool {
+    LiteralValuePresenter::nil(FieldType::SingleLineField)
+        .cast_to(field_type)
+        .is_ok()
+}
+
+fn parse_cell(value: &str, field_type: FieldType) -> Result<LiteralValuePresenter, CastError> {
+    let value = match field_type {
+        FieldType::SingleLineField | FieldType::MultipleLineField => value,
+        _ => value.trim(),
+    };
+
+    if value.is_empty() {
+        return Ok(LiteralValuePresenter::nil(field_type));
+    }
+
+    LiteralValuePresenter::SingleLineField(SingleLineFieldValue::Value(value.to_string()))
+        .cast_to(field_type)
+}
+
+/// Parses RFC 4180 records, allowing `\n` line endings and skipping blank
+/// lines.
+fn parse_records(text: &str, delimiter: char) -> Result<Vec<Vec<String>>, CsvError> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut chars = text.chars().peekable();
+
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+
+    while let Some(char) = chars.next() {
+        match char {
+            '"' if cell.is_empty() && !quoted => {
+                let start_line = line;
+
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            cell.push('"');
+                        }
+                        Some('"') => break,
+                        Some(char) => {
+                            if char == '\n' {
+                                line += 1;
+                            }
+
+                            cell.push(char);
+                        }
+                        None => return Err(CsvError::UnterminatedQuote { line: start_line }),
+                    }
+                }
+
+                quoted = true;
+            }
+            char if char == delimiter => {
+                record.push(std::mem::take(&mut cell));
+                quoted = false;
+            }
+            '\r' if chars.peek() == Some(&'\n') => (),
+            '\r' | '\n' => {
+                end_record(&mut records, &mut record, &mut cell, quoted);
+                quoted = false;
+                line += 1;
+            }
+            _ if quoted => return Err(CsvError::UnexpectedCharacter { line }),
+            char => cell.push(char),
+        }
+    }
+
+    end_record(&mut records, &mut record, &mut cell, quoted);
+
+    Ok(records)
+}
+
+fn end_record(
+    records: &mut Vec<Vec<String>>,
+    record: &mut Vec<String>,
+    cell: &mut String,
+    quoted: bool,
+) {
+    if record.is_empty() && cell.is_empty() && !quoted {
+        return;
+    }
+
+    record.push(std::mem::take(cell));
+    records.push(std::mem::take(record));
+}
+
+fn write_record(text: &mut String, record: &[&str], delimiter: char) {
+    for (index, cell) in record.iter().enumerate() {
+        if index > 0 {
+            text.push(delimiter);
+        }
+
+        // a lone empty cell is quoted so that its row is not a blank line
+        if cell.contains([delimiter, '"', '\r', '\n']) || (record.len() == 1 && cell.is_empty()) {
+            text.push('"');
+            text.push_str(&cell.replace('"', "\"\""));
+            text.push('"');
+        } else {
+            text.push_str(cell);
+        }
+    }
+
+    text.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_presenter::{
+        literal_list_value::ListFieldValue,
+        literal_naive_value::{BooleanFieldValue, DateFieldValue, NumericFieldValue},
+        value::{naive_date::NaiveDate, number::Number},
+    };
+
+    fn single_line_list(items: &[Option<&str>]) -> LiteralValuePresenter {
+        LiteralValuePresenter::SingleLineListField(ListFieldValue::Value(
+            items
+                .iter()
+                .map(|item| match item {
+                    Some(item) => SingleLineFieldValue::Value(item.to_string()),
+                    None => SingleLineFieldValue::Nil,
+                })
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn test_parse_records() {
+        assert_eq!(
+            parse_records("\u{feff}a,\"b \"\"c\"\"\",\r\n\n\"multi\nline\",x\r\n", ',').unwrap(),
+            vec![
+                vec!["a".to_string(), "b \"c\"".to_string(), String::new()],
+                vec!["multi\nline".to_string(), "x".to_string()],
+            ]
+        );
+        assert_eq!(
+            parse_records("a\tb", '\t').unwrap(),
+            vec![vec!["a".to_string(), "b".to_string()]]
+        );
+        assert_eq!(parse_records("", ',').unwrap(), Vec::<Vec<String>>::new());
+        assert_eq!(
+            parse_records("\"\"", ',').unwrap(),
+            vec![vec![String::new()]]
+        );
+
+        assert_eq!(
+            parse_records("a\n\"b\nc", ','),
+            Err(CsvError::UnterminatedQuote { line: 2 })
+        );
+        assert_eq!(
+            parse_records("a\n\"b\"c", ','),
+            Err(CsvError::UnexpectedCharacter { line: 2 })
+        );
+    }
+
+    #[test]
+    fn test_parse_columns() {
+        let text = "name,score,passed,date,extra\n\
+                    Jet, 42 ,true,2024-01-31,x\n\
+                    Ann,n/a,,not a date\n\
+                    Bob\n";
+
+        let table = parse_columns(
+            text,
+            &[
+                FieldType::SingleLineListField,
+                FieldType::NumericListField,
+                FieldType::BooleanListField,
+                FieldType::DateListField,
+            ],
+            CsvOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            table.header,
+            vec!["name", "score", "passed", "date", "extra"]
+        );
+        assert_eq!(
+            table.columns,
+            vec![
+                single_line_list(&[Some("Jet"), Some("Ann"), Some("Bob")]),
+                LiteralValuePresenter::NumericListField(ListFieldValue::Value(vec![
+                    NumericFieldValue::Value(Number::Integer(42)),
+                    NumericFieldValue::Nil,
+                    NumericFieldValue::Nil,
+                ])),
+                LiteralValuePresenter::BooleanListField(ListFieldValue::Value(vec![
+                    BooleanFieldValue::Value(true),
+                    BooleanFieldValue::Nil,
+                    BooleanFieldValue::Nil,
+                ])),
+                LiteralValuePresenter::DateListField(ListFieldValue::Value(vec![
+                    DateFieldValue::Value(NaiveDate::new(2024, 1, 31)),
+                    DateFieldValue::Nil,
+                    DateFieldValue::Nil,
+                ])),
+            ]
+        );
+        assert_eq!(
+            table.errors,
+            vec![
+                CellError {
+                    row: 1,
+                    column: 1,
+                    value: "n/a".to_string(),
+                    error: CastError::InvalidValue {
+                        from: FieldType::SingleLineField,
+                        to: FieldType::NumericField,
+                    },
+                },
+                CellError {
+                    row: 1,
+                    column: 3,
+                    value: "not a date".to_string(),
+                    error: CastError::InvalidValue {
+                        from: FieldType::SingleLineField,
+                        to: FieldType::DateField,
+                    },
+                },
+            ]
+        );
+
+        // without a header row
+        let table = parse_columns(
+            "1\t2\n",
+            &[FieldType::NumericListField],
+            CsvOptions {
+                header: false,
+                ..CsvOptions::tsv()
+            },
+        )
+        .unwrap();
+        assert!(table.header.is_empty());
+        assert_eq!(
+            table.columns,
+            vec![LiteralValuePresenter::NumericListField(
+                ListFieldValue::Value(vec![NumericFieldValue::Value(Number::Integer(1))])
+            )]
+        );
+
+        assert_eq!(
+            parse_columns("", &[FieldType::NumericField], CsvOptions::default()),
+            Err(CsvError::UnsupportedFieldType(FieldType::NumericField))
+        );
+        assert_eq!(
+            parse_columns("", &[FieldType::FileListField], CsvOptions::default()),
+            Err(CsvError::UnsupportedFieldType(FieldType::FileListField))
+        );
+    }
+
+    #[test]
+    fn test_write_columns() {
+        let columns = vec![
+            single_line_list(&[Some("Jet"), Some("a, \"b\""), None]),
+            LiteralValuePresenter::NumericListField(ListFieldValue::Value(vec![
+                NumericFieldValue::Value(Number::Integer(42)),
+                NumericFieldValue::Nil,
+            ])),
+            LiteralValuePresenter::DateListField(ListFieldValue::Nil),
+        ];
+
+        let text =
+            write_columns(&["name", "score", "date"], &columns, CsvOptions::default()).unwrap();
+        assert_eq!(
+            text,
+            "name,score,date\r\nJet,42,\r\n\"a, \"\"b\"\"\",,\r\n,,\r\n"
+        );
+
+        // written columns parse back
+        let table = parse_columns(
+            &text,
+            &[
+                FieldType::SingleLineListField,
+                FieldType::NumericListField,
+                FieldType::DateListField,
+            ],
+            CsvOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(table.columns[0], columns[0]);
+        assert!(table.errors.is_empty());
+
+        assert_eq!(
+            write_columns(
+                &[] as &[&str],
+                &[single_line_list(&[Some("a"), None])],
+                CsvOptions {
+                    header: false,
+                    ..CsvOptions::tsv()
+                }
+            )
+            .unwrap(),
+            "a\r\n\"\"\r\n"
+        );
+
+        // an empty header is not written, so the text parses back without one
+        let columns = vec![single_line_list(&[Some("a"), Some("b")])];
+        let text = write_columns(&[] as &[&str], &columns, CsvOptions::default()).unwrap();
+        assert_eq!(text, "a\r\nb\r\n");
+
+        let table = parse_columns(
+            &text,
+            &[FieldType::SingleLineListField],
+            CsvOptions {
+                header: false,
+                ..CsvOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(table.columns, columns);
+
+        assert_eq!(
+            write_columns(&["a", "b"], &columns, CsvOptions::default()),
+            Err(CsvError::HeaderLengthMismatch {
+                header: 2,
+                columns: 1
+            })
+        );
+        assert_eq!(
+            write_columns(
+                &["name"],
+                &[LiteralValuePresenter::NumericField(NumericFieldValue::Nil)],
+                CsvOptions::default()
+            ),
+            Err(CsvError::UnsupportedFieldType(FieldType::NumericField))
+        );
+    }
+}
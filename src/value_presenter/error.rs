@@ -19,6 +19,15 @@ pub enum DecodeError<'a> {
 
     MismatchedFieldType {
         field_type: FieldType,
-        value_presenter: ValuePresenter,
+        /// Boxed since a `ValuePresenter` holding a document tree would make
+        /// every `Result` carrying a `DecodeError` large.
+        value_presenter: Box<ValuePresenter>,
     },
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The output at `index` holds a NaN or infinite number, which JSON cannot
+    /// represent.
+    NonFiniteNumber { index: usize },
+}
@@ -1,8 +1,9 @@
-#[derive(PartialEq, Debug, Eq)]
+#[derive(PartialEq, Debug, Eq, Clone, Copy)]
 pub enum FieldType {
     BooleanField,
     CascaderField,
     CheckboxField,
+    DateField,
     DateTimeField,
     FileField,
     MultipleLineField,
@@ -11,18 +12,24 @@ pub enum FieldType {
     RelationField,
     SingleLineField,
     TableRowField,
+    TimeField,
     UserBoundaryField,
 
     // list field
     BooleanListField,
     CascaderListField,
+    CheckboxListField,
+    DateListField,
     DateTimeListField,
     FileListField,
     MultipleLineListField,
     NumericListField,
+    RadioButtonListField,
     RelationListField,
     SingleLineListField,
     TableRowListField,
+    TimeListField,
+    UserBoundaryListField,
 }
 
 pub struct ParseFieldTypeError;
@@ -33,6 +40,7 @@ impl FieldType {
             "BooleanField" | "BOOLEAN_FIELD" | "boolean_field" => Ok(FieldType::BooleanField),
             "CascaderField" | "CASCADER_FIELD" | "cascader_field" => Ok(FieldType::CascaderField),
             "CheckboxField" | "CHECKBOX_FIELD" | "checkbox_field" => Ok(FieldType::CheckboxField),
+            "DateField" | "DATE_FIELD" | "date_field" => Ok(FieldType::DateField),
             "DateTimeField" | "DATE_TIME_FIELD" | "date_time_field" => Ok(FieldType::DateTimeField),
             "FileField" | "FILE_FIELD" | "file_field" => Ok(FieldType::FileField),
             "MultipleLineField" | "MULTIPLE_LINE_FIELD" | "multiple_line_field" => {
@@ -47,6 +55,7 @@ impl FieldType {
                 Ok(FieldType::SingleLineField)
             }
             "TableRowField" | "TABLE_ROW_FIELD" | "table_row_field" => Ok(FieldType::TableRowField),
+            "TimeField" | "TIME_FIELD" | "time_field" => Ok(FieldType::TimeField),
             "UserBoundaryField" | "USER_BOUNDARY_FIELD" | "user_boundary_field" => {
                 Ok(FieldType::UserBoundaryField)
             }
@@ -58,6 +67,10 @@ impl FieldType {
             "CascaderListField" | "CASCADER_LIST_FIELD" | "cascader_list_field" => {
                 Ok(FieldType::CascaderListField)
             }
+            "CheckboxListField" | "CHECKBOX_LIST_FIELD" | "checkbox_list_field" => {
+                Ok(FieldType::CheckboxListField)
+            }
+            "DateListField" | "DATE_LIST_FIELD" | "date_list_field" => Ok(FieldType::DateListField),
             "DateTimeListField" | "DATE_TIME_LIST_FIELD" | "date_time_list_field" => {
                 Ok(FieldType::DateTimeListField)
             }
@@ -68,6 +81,9 @@ impl FieldType {
             "NumericListField" | "NUMERIC_LIST_FIELD" | "numeric_list_field" => {
                 Ok(FieldType::NumericListField)
             }
+            "RadioButtonListField" | "RADIO_BUTTON_LIST_FIELD" | "radio_button_list_field" => {
+                Ok(FieldType::RadioButtonListField)
+            }
             "RelationListField" | "RELATION_LIST_FIELD" | "relation_list_field" => {
                 Ok(FieldType::RelationListField)
             }
@@ -77,6 +93,10 @@ impl FieldType {
             "TableRowListField" | "TABLE_ROW_LIST_FIELD" | "table_row_list_field" => {
                 Ok(FieldType::TableRowListField)
             }
+            "TimeListField" | "TIME_LIST_FIELD" | "time_list_field" => Ok(FieldType::TimeListField),
+            "UserBoundaryListField" | "USER_BOUNDARY_LIST_FIELD" | "user_boundary_list_field" => {
+                Ok(FieldType::UserBoundaryListField)
+            }
             _ => Err(ParseFieldTypeError),
         }
     }
@@ -86,6 +106,7 @@ impl FieldType {
             FieldType::BooleanField => From::from("BOOLEAN_FIELD"),
             FieldType::CascaderField => From::from("CASCADER_FIELD"),
             FieldType::CheckboxField => From::from("CHECKBOX_FIELD"),
+            FieldType::DateField => From::from("DATE_FIELD"),
             FieldType::DateTimeField => From::from("DATE_TIME_FIELD"),
             FieldType::FileField => From::from("FILE_FIELD"),
             FieldType::MultipleLineField => From::from("MULTIPLE_LINE_FIELD"),
@@ -94,18 +115,44 @@ impl FieldType {
             FieldType::RelationField => From::from("RELATION_FIELD"),
             FieldType::SingleLineField => From::from("SINGLE_LINE_FIELD"),
             FieldType::TableRowField => From::from("TABLE_ROW_FIELD"),
+            FieldType::TimeField => From::from("TIME_FIELD"),
             FieldType::UserBoundaryField => From::from("USER_BOUNDARY_FIELD"),
 
             // list field
             FieldType::BooleanListField => From::from("BOOLEAN_LIST_FIELD"),
             FieldType::CascaderListField => From::from("CASCADER_LIST_FIELD"),
+            FieldType::CheckboxListField => From::from("CHECKBOX_LIST_FIELD"),
+            FieldType::DateListField => From::from("DATE_LIST_FIELD"),
             FieldType::DateTimeListField => From::from("DATE_TIME_LIST_FIELD"),
             FieldType::FileListField => From::from("FILE_LIST_FIELD"),
             FieldType::MultipleLineListField => From::from("MULTIPLE_LINE_LIST_FIELD"),
             FieldType::NumericListField => From::from("NUMERIC_LIST_FIELD"),
+            FieldType::RadioButtonListField => From::from("RADIO_BUTTON_LIST_FIELD"),
             FieldType::RelationListField => From::from("RELATION_LIST_FIELD"),
             FieldType::SingleLineListField => From::from("SINGLE_LINE_LIST_FIELD"),
             FieldType::TableRowListField => From::from("TABLE_ROW_LIST_FIELD"),
+            FieldType::TimeListField => From::from("TIME_LIST_FIELD"),
+            FieldType::UserBoundaryListField => From::from("USER_BOUNDARY_LIST_FIELD"),
+        }
+    }
+
+    pub fn item_field_type(&self) -> Option<FieldType> {
+        match self {
+            FieldType::BooleanListField => Some(FieldType::BooleanField),
+            FieldType::CascaderListField => Some(FieldType::CascaderField),
+            FieldType::CheckboxListField => Some(FieldType::CheckboxField),
+            FieldType::DateListField => Some(FieldType::DateField),
+            FieldType::DateTimeListField => Some(FieldType::DateTimeField),
+            FieldType::FileListField => Some(FieldType::FileField),
+            FieldType::MultipleLineListField => Some(FieldType::MultipleLineField),
+            FieldType::NumericListField => Some(FieldType::NumericField),
+            FieldType::RadioButtonListField => Some(FieldType::RadioButtonField),
+            FieldType::RelationListField => Some(FieldType::RelationField),
+            FieldType::SingleLineListField => Some(FieldType::SingleLineField),
+            FieldType::TableRowListField => Some(FieldType::TableRowField),
+            FieldType::TimeListField => Some(FieldType::TimeField),
+            FieldType::UserBoundaryListField => Some(FieldType::UserBoundaryField),
+            _ => None,
         }
     }
 }
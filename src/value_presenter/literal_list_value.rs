@@ -1,318 +1,182 @@
+use std::{iter::FromIterator, slice::Iter};
+
 use serde_json::Value;
 
 use super::{
     field_type::FieldType,
     literal_naive_value::{
-        BooleanFieldValue, CascaderFieldValue, DateTimeFieldValue, FileFieldValue,
-        MultipleLineFieldValue, NumericFieldValue, RelationFieldValue, SingleLineFieldValue,
-        TableRowFieldValue,
+        BooleanFieldValue, CascaderFieldValue, CheckboxFieldValue, DateFieldValue,
+        DateTimeFieldValue, FileFieldValue, MultipleLineFieldValue, NumericFieldValue,
+        RadioButtonFieldValue, RelationFieldValue, SingleLineFieldValue, TableRowFieldValue,
+        TimeFieldValue, UserBoundaryFieldValue,
     },
     literal_value::{LiteralValue, ParseLiteralValueError},
 };
 
+/// A nullable list of naive field values, e.g. `ListFieldValue<BooleanFieldValue>`
+/// for a boolean list field.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum BooleanListFieldValue {
-    Value(Vec<BooleanFieldValue>),
-    Nil,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum CascaderListFieldValue {
-    Value(Vec<CascaderFieldValue>),
+pub enum ListFieldValue<T> {
+    Value(Vec<T>),
     Nil,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum DateTimeListFieldValue {
-    Value(Vec<DateTimeFieldValue>),
-    Nil,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum FileListFieldValue {
-    Value(Vec<FileFieldValue>),
-    Nil,
+/// A naive field value that can be held by a `ListFieldValue`.
+pub trait ListItemValue: LiteralValue {
+    /// The field type of a list holding this value.
+    const LIST_FIELD_TYPE: FieldType;
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum MultipleLineListFieldValue {
-    Value(Vec<MultipleLineFieldValue>),
-    Nil,
-}
-
-#[derive(Debug, Clone, PartialEq)]
-pub enum NumericListFieldValue {
-    Value(Vec<NumericFieldValue>),
-    Nil,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum RelationListFieldValue {
-    Value(Vec<RelationFieldValue>),
-    Nil,
-}
+macro_rules! list_field_values {
+    ($($item:ident => $list:ident, $list_field_type:ident;)*) => {
+        $(
+            pub type $list = ListFieldValue<$item>;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum SingleLineListFieldValue {
-    Value(Vec<SingleLineFieldValue>),
-    Nil,
+            impl ListItemValue for $item {
+                const LIST_FIELD_TYPE: FieldType = FieldType::$list_field_type;
+            }
+        )*
+    };
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum TableRowListFieldValue {
-    Value(Vec<TableRowFieldValue>),
-    Nil,
+list_field_values! {
+    BooleanFieldValue => BooleanListFieldValue, BooleanListField;
+    CascaderFieldValue => CascaderListFieldValue, CascaderListField;
+    CheckboxFieldValue => CheckboxListFieldValue, CheckboxListField;
+    DateFieldValue => DateListFieldValue, DateListField;
+    DateTimeFieldValue => DateTimeListFieldValue, DateTimeListField;
+    FileFieldValue => FileListFieldValue, FileListField;
+    MultipleLineFieldValue => MultipleLineListFieldValue, MultipleLineListField;
+    NumericFieldValue => NumericListFieldValue, NumericListField;
+    RadioButtonFieldValue => RadioButtonListFieldValue, RadioButtonListField;
+    RelationFieldValue => RelationListFieldValue, RelationListField;
+    SingleLineFieldValue => SingleLineListFieldValue, SingleLineListField;
+    TableRowFieldValue => TableRowListFieldValue, TableRowListField;
+    TimeFieldValue => TimeListFieldValue, TimeListField;
+    UserBoundaryFieldValue => UserBoundaryListFieldValue, UserBoundaryListField;
 }
 
-impl LiteralValue for BooleanListFieldValue {
-    fn is_nil(&self) -> bool {
-        matches!(self, BooleanListFieldValue::Nil)
-    }
-
-    fn from_json(value: &Value) -> Result<Self, ParseLiteralValueError> {
-        if value.is_null() {
-            return Ok(BooleanListFieldValue::Nil);
-        }
-
-        match list_from_json::<BooleanFieldValue>(value) {
-            Ok(values) => Ok(BooleanListFieldValue::Value(values)),
-            Err(_err) => Err(ParseLiteralValueError),
-        }
-    }
-
-    fn to_json(&self) -> Value {
+impl<T> ListFieldValue<T> {
+    /// Returns the items of the list, or an empty slice when the list is nil.
+    pub fn as_slice(&self) -> &[T] {
         match self {
-            BooleanListFieldValue::Value(values) => list_to_json(values),
-            BooleanListFieldValue::Nil => Value::Null,
+            ListFieldValue::Value(values) => values.as_slice(),
+            ListFieldValue::Nil => &[],
         }
     }
 
-    fn get_field_type(&self) -> FieldType {
-        FieldType::BooleanListField
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.as_slice().iter()
     }
-}
 
-impl LiteralValue for CascaderListFieldValue {
-    fn is_nil(&self) -> bool {
-        matches!(self, CascaderListFieldValue::Nil)
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
     }
 
-    fn from_json(value: &Value) -> Result<Self, ParseLiteralValueError> {
-        if value.is_null() {
-            return Ok(CascaderListFieldValue::Nil);
-        }
-
-        match list_from_json::<CascaderFieldValue>(value) {
-            Ok(values) => Ok(CascaderListFieldValue::Value(values)),
-            Err(_err) => Err(ParseLiteralValueError),
-        }
+    /// Returns `true` if the list is nil or has no items.
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
     }
 
-    fn to_json(&self) -> Value {
-        match self {
-            CascaderListFieldValue::Value(values) => list_to_json(values),
-            CascaderListFieldValue::Nil => Value::Null,
-        }
-    }
-
-    fn get_field_type(&self) -> FieldType {
-        FieldType::CascaderListField
-    }
-}
-
-impl LiteralValue for DateTimeListFieldValue {
-    fn is_nil(&self) -> bool {
-        matches!(self, DateTimeListFieldValue::Nil)
-    }
-
-    fn from_json(value: &Value) -> Result<Self, ParseLiteralValueError> {
-        if value.is_null() {
-            return Ok(DateTimeListFieldValue::Nil);
-        }
-
-        match list_from_json::<DateTimeFieldValue>(value) {
-            Ok(values) => Ok(DateTimeListFieldValue::Value(values)),
-            Err(_err) => Err(ParseLiteralValueError),
-        }
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.as_slice().get(index)
     }
 
-    fn to_json(&self) -> Value {
+    /// Appends an item, turning a nil list into a list of one item.
+    pub fn push(&mut self, item: T) {
         match self {
-            DateTimeListFieldValue::Value(values) => list_to_json(values),
-            DateTimeListFieldValue::Nil => Value::Null,
-        }
-    }
-
-    fn get_field_type(&self) -> FieldType {
-        FieldType::DateTimeListField
-    }
-}
-
-impl LiteralValue for FileListFieldValue {
-    fn is_nil(&self) -> bool {
-        matches!(self, FileListFieldValue::Nil)
-    }
-
-    fn from_json(value: &Value) -> Result<Self, ParseLiteralValueError> {
-        if value.is_null() {
-            return Ok(FileListFieldValue::Nil);
-        }
-
-        match list_from_json::<FileFieldValue>(value) {
-            Ok(values) => Ok(FileListFieldValue::Value(values)),
-            Err(_err) => Err(ParseLiteralValueError),
+            ListFieldValue::Value(values) => values.push(item),
+            ListFieldValue::Nil => *self = ListFieldValue::Value(vec![item]),
         }
     }
 
-    fn to_json(&self) -> Value {
+    /// Maps every item, keeping a nil list nil.
+    pub fn map<U, F>(&self, f: F) -> ListFieldValue<U>
+    where
+        F: FnMut(&T) -> U,
+    {
         match self {
-            FileListFieldValue::Value(values) => list_to_json(values),
-            FileListFieldValue::Nil => Value::Null,
+            ListFieldValue::Value(values) => ListFieldValue::Value(values.iter().map(f).collect()),
+            ListFieldValue::Nil => ListFieldValue::Nil,
         }
     }
 
-    fn get_field_type(&self) -> FieldType {
-        FieldType::FileListField
-    }
-}
-
-impl LiteralValue for MultipleLineListFieldValue {
-    fn is_nil(&self) -> bool {
-        matches!(self, MultipleLineListFieldValue::Nil)
-    }
-
-    fn from_json(value: &Value) -> Result<Self, ParseLiteralValueError> {
-        if value.is_null() {
-            return Ok(MultipleLineListFieldValue::Nil);
-        }
-
-        match list_from_json::<MultipleLineFieldValue>(value) {
-            Ok(values) => Ok(MultipleLineListFieldValue::Value(values)),
-            Err(_err) => Err(ParseLiteralValueError),
-        }
-    }
-
-    fn to_json(&self) -> Value {
+    /// Like `map`, but stops at the first error.
+    pub fn try_map<U, E, F>(&self, f: F) -> Result<ListFieldValue<U>, E>
+    where
+        F: FnMut(&T) -> Result<U, E>,
+    {
         match self {
-            MultipleLineListFieldValue::Value(values) => list_to_json(values),
-            MultipleLineListFieldValue::Nil => Value::Null,
+            ListFieldValue::Value(values) => values
+                .iter()
+                .map(f)
+                .collect::<Result<Vec<U>, E>>()
+                .map(ListFieldValue::Value),
+            ListFieldValue::Nil => Ok(ListFieldValue::Nil),
         }
     }
 
-    fn get_field_type(&self) -> FieldType {
-        FieldType::MultipleLineListField
-    }
-}
-
-impl LiteralValue for NumericListFieldValue {
-    fn is_nil(&self) -> bool {
-        matches!(self, NumericListFieldValue::Nil)
-    }
-
-    fn from_json(value: &Value) -> Result<Self, ParseLiteralValueError> {
-        if value.is_null() {
-            return Ok(NumericListFieldValue::Nil);
-        }
-
-        match list_from_json::<NumericFieldValue>(value) {
-            Ok(values) => Ok(NumericListFieldValue::Value(values)),
-            Err(_err) => Err(ParseLiteralValueError),
-        }
-    }
-
-    fn to_json(&self) -> Value {
+    pub fn into_vec(self) -> Vec<T> {
         match self {
-            NumericListFieldValue::Value(values) => list_to_json(values),
-            NumericListFieldValue::Nil => Value::Null,
+            ListFieldValue::Value(values) => values,
+            ListFieldValue::Nil => Vec::new(),
         }
     }
-
-    fn get_field_type(&self) -> FieldType {
-        FieldType::NumericListField
-    }
 }
 
-impl LiteralValue for RelationListFieldValue {
-    fn is_nil(&self) -> bool {
-        matches!(self, RelationListFieldValue::Nil)
-    }
-
-    fn from_json(value: &Value) -> Result<Self, ParseLiteralValueError> {
-        if value.is_null() {
-            return Ok(RelationListFieldValue::Nil);
-        }
-
-        match list_from_json::<RelationFieldValue>(value) {
-            Ok(values) => Ok(RelationListFieldValue::Value(values)),
-            Err(_err) => Err(ParseLiteralValueError),
-        }
-    }
-
-    fn to_json(&self) -> Value {
-        match self {
-            RelationListFieldValue::Value(values) => list_to_json(values),
-            RelationListFieldValue::Nil => Value::Null,
-        }
-    }
-
-    fn get_field_type(&self) -> FieldType {
-        FieldType::RelationListField
+impl<T: LiteralValue> ListFieldValue<T> {
+    /// Iterates over the items that are not nil.
+    pub fn non_nil(&self) -> impl Iterator<Item = &T> {
+        self.iter().filter(|item| !item.is_nil())
     }
 }
 
-impl LiteralValue for SingleLineListFieldValue {
-    fn is_nil(&self) -> bool {
-        matches!(self, SingleLineListFieldValue::Nil)
+impl<T> FromIterator<T> for ListFieldValue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        ListFieldValue::Value(iter.into_iter().collect())
     }
+}
 
-    fn from_json(value: &Value) -> Result<Self, ParseLiteralValueError> {
-        if value.is_null() {
-            return Ok(SingleLineListFieldValue::Nil);
-        }
+impl<T> IntoIterator for ListFieldValue<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
 
-        match list_from_json::<SingleLineFieldValue>(value) {
-            Ok(values) => Ok(SingleLineListFieldValue::Value(values)),
-            Err(_err) => Err(ParseLiteralValueError),
-        }
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_vec().into_iter()
     }
+}
 
-    fn to_json(&self) -> Value {
-        match self {
-            SingleLineListFieldValue::Value(values) => list_to_json(values),
-            SingleLineListFieldValue::Nil => Value::Null,
-        }
-    }
+impl<'a, T> IntoIterator for &'a ListFieldValue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
 
-    fn get_field_type(&self) -> FieldType {
-        FieldType::SingleLineListField
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
-impl LiteralValue for TableRowListFieldValue {
+impl<T: ListItemValue> LiteralValue for ListFieldValue<T> {
     fn is_nil(&self) -> bool {
-        matches!(self, TableRowListFieldValue::Nil)
+        matches!(self, ListFieldValue::Nil)
     }
 
     fn from_json(value: &Value) -> Result<Self, ParseLiteralValueError> {
         if value.is_null() {
-            return Ok(TableRowListFieldValue::Nil);
+            return Ok(ListFieldValue::Nil);
         }
 
-        match list_from_json::<TableRowFieldValue>(value) {
-            Ok(values) => Ok(TableRowListFieldValue::Value(values)),
-            Err(_err) => Err(ParseLiteralValueError),
-        }
+        list_from_json::<T>(value).map(ListFieldValue::Value)
     }
 
     fn to_json(&self) -> Value {
         match self {
-            TableRowListFieldValue::Value(values) => list_to_json(values),
-            TableRowListFieldValue::Nil => Value::Null,
+            ListFieldValue::Value(values) => list_to_json(values),
+            ListFieldValue::Nil => Value::Null,
         }
     }
 
     fn get_field_type(&self) -> FieldType {
-        FieldType::TableRowListField
+        T::LIST_FIELD_TYPE
     }
 }
 
@@ -333,7 +197,7 @@ fn list_from_json<T: LiteralValue>(value: &Value) -> Result<Vec<T>, ParseLiteral
     }
 }
 
-fn list_to_json<T: LiteralValue>(values: &Vec<T>) -> Value {
+fn list_to_json<T: LiteralValue>(values: &[T]) -> Value {
     let mut result = Vec::new();
 
     for value in values {
@@ -348,7 +212,8 @@ mod tests {
     use serde_json::json;
 
     use crate::value_presenter::value::{
-        cascader_value::CascaderValue, naive_date_time::NaiveDateTime, number::Number, uuid::Uuid,
+        cascader_value::CascaderValue, naive_date_time::NaiveDateTime, number::Number,
+        options_value::OptionsValue, uuid::Uuid,
     };
 
     use super::*;
@@ -404,8 +269,8 @@ mod tests {
                 result.unwrap(),
                 CascaderListFieldValue::Value(values) if values.as_slice() == vec![
                     CascaderFieldValue::Value(CascaderValue {
-                        options_table_uuid: Uuid("00000000-0000-0000-0000-000000000000".to_string()),
-                        row_uuid: Uuid("00000000-0000-0000-0000-000000000001".to_string()),
+                        options_table_uuid: Uuid::new("00000000-0000-0000-0000-000000000000").unwrap(),
+                        row_uuid: Uuid::new("00000000-0000-0000-0000-000000000001").unwrap(),
                     }),
                     CascaderFieldValue::Nil,
                 ]
@@ -423,6 +288,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_checkbox_list_field_value_from_json() {
+        {
+            let value = json!([{"options": ["option1"], "other": null}, null]);
+            let result = CheckboxListFieldValue::from_json(&value);
+
+            assert!(result.is_ok());
+
+            assert!(matches!(
+                result.unwrap(),
+                CheckboxListFieldValue::Value(values) if values.as_slice() == vec![
+                    CheckboxFieldValue::Value(OptionsValue {
+                        options: vec!["option1".to_string()],
+                        other: None,
+                    }),
+                    CheckboxFieldValue::Nil,
+                ]
+            ));
+        }
+
+        // null
+        {
+            let value = json!(null);
+            let result = CheckboxListFieldValue::from_json(&value);
+
+            assert!(result.is_ok());
+
+            assert!(matches!(result.unwrap(), CheckboxListFieldValue::Nil));
+        }
+    }
+
     #[test]
     fn test_date_time_list_field_value_from_json() {
         {
@@ -458,7 +354,7 @@ mod tests {
     #[test]
     fn test_numeric_list_field_value_from_json() {
         {
-            let value = json!([1 as i64, 1.01 as f64, null]);
+            let value = json!([1_i64, 1.01_f64, null]);
             let result = NumericListFieldValue::from_json(&value);
 
             assert!(result.is_ok());
@@ -534,7 +430,7 @@ mod tests {
             assert!(matches!(
                 result,
                 TableRowListFieldValue::Value(values) if values.as_slice() == vec![
-                    TableRowFieldValue::Value(Uuid("67e55044-10b1-426f-9247-bb680e5fe0c8".to_string())),
+                    TableRowFieldValue::Value(Uuid::new("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap()),
                     TableRowFieldValue::Nil,
                 ]
             ));
@@ -552,4 +448,54 @@ mod tests {
             assert!(matches!(result, TableRowListFieldValue::Nil));
         }
     }
+
+    #[test]
+    fn test_list_field_value_helpers() {
+        let mut list = BooleanListFieldValue::Nil;
+
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+        assert_eq!(list.iter().count(), 0);
+
+        list.push(BooleanFieldValue::Value(true));
+        list.push(BooleanFieldValue::Nil);
+        list.push(BooleanFieldValue::Value(false));
+
+        assert_eq!(list.len(), 3);
+        assert!(!list.is_empty());
+        assert_eq!(list.get(1), Some(&BooleanFieldValue::Nil));
+        assert_eq!(
+            list.non_nil().collect::<Vec<_>>(),
+            vec![
+                &BooleanFieldValue::Value(true),
+                &BooleanFieldValue::Value(false)
+            ]
+        );
+        assert_eq!(list.get_field_type(), FieldType::BooleanListField);
+
+        let negated = list.map(|item| match item {
+            BooleanFieldValue::Value(value) => BooleanFieldValue::Value(!value),
+            BooleanFieldValue::Nil => BooleanFieldValue::Nil,
+        });
+
+        assert_eq!(
+            negated,
+            vec![
+                BooleanFieldValue::Value(false),
+                BooleanFieldValue::Nil,
+                BooleanFieldValue::Value(true),
+            ]
+            .into_iter()
+            .collect::<BooleanListFieldValue>()
+        );
+
+        let strict: Result<BooleanListFieldValue, ()> = list.try_map(|item| match item {
+            BooleanFieldValue::Nil => Err(()),
+            item => Ok(item.clone()),
+        });
+
+        assert!(strict.is_err());
+        assert!(BooleanListFieldValue::Nil.map(|item| item.clone()).is_nil());
+        assert_eq!(list.into_vec().len(), 3);
+    }
 }
@@ -9,8 +9,9 @@ use super::{
 use serde_json::Value;
 
 use super::value::{
-    naive_date_time::NaiveDateTime, number::Number, options_value::OptionsValue,
-    user_boundary::UserBoundary, uuid::Uuid,
+    naive_date::NaiveDate, naive_date_time::NaiveDateTime, naive_time::NaiveTime, number::Number,
+    offset_date_time::OffsetDateTime, options_value::OptionsValue, user_boundary::UserBoundary,
+    uuid::Uuid,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -31,9 +32,18 @@ pub enum CheckboxFieldValue {
     Nil,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateFieldValue {
+    Value(NaiveDate),
+    Nil,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DateTimeFieldValue {
     Value(NaiveDateTime),
+    /// A date and time decoded with an explicit UTC offset, which `Value` is
+    /// not able to keep.
+    OffsetValue(OffsetDateTime),
     Nil,
 }
 
@@ -79,6 +89,12 @@ pub enum TableRowFieldValue {
     Nil,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeFieldValue {
+    Value(NaiveTime),
+    Nil,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum UserBoundaryFieldValue {
     Value(UserBoundary),
@@ -169,6 +185,34 @@ impl LiteralValue for CascaderFieldValue {
     }
 }
 
+impl LiteralValue for DateFieldValue {
+    fn is_nil(&self) -> bool {
+        matches!(self, DateFieldValue::Nil)
+    }
+
+    fn from_json(value: &Value) -> Result<Self, ParseLiteralValueError> {
+        if value.is_null() {
+            return Ok(DateFieldValue::Nil);
+        }
+
+        match NaiveDate::from_json(value) {
+            Ok(v) => Ok(DateFieldValue::Value(v)),
+            Err(_) => Err(ParseLiteralValueError),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            DateFieldValue::Value(value) => value.to_json(),
+            DateFieldValue::Nil => Value::Null,
+        }
+    }
+
+    fn get_field_type(&self) -> FieldType {
+        FieldType::DateField
+    }
+}
+
 impl LiteralValue for DateTimeFieldValue {
     fn is_nil(&self) -> bool {
         matches!(self, DateTimeFieldValue::Nil)
@@ -181,13 +225,17 @@ impl LiteralValue for DateTimeFieldValue {
 
         match NaiveDateTime::from_json(value) {
             Ok(v) => Ok(DateTimeFieldValue::Value(v)),
-            Err(_) => Err(ParseLiteralValueError),
+            Err(_) => match OffsetDateTime::from_json(value) {
+                Ok(v) => Ok(DateTimeFieldValue::OffsetValue(v)),
+                Err(_) => Err(ParseLiteralValueError),
+            },
         }
     }
 
     fn to_json(&self) -> Value {
         match self {
             DateTimeFieldValue::Value(value) => value.to_json(),
+            DateTimeFieldValue::OffsetValue(value) => value.to_json(),
             DateTimeFieldValue::Nil => Value::Null,
         }
     }
@@ -197,6 +245,20 @@ impl LiteralValue for DateTimeFieldValue {
     }
 }
 
+impl DateTimeFieldValue {
+    /// Converts an `OffsetValue` to a `Value` in UTC, for hosts that expect
+    /// naive date times. Returns `Nil` if the date and time is invalid.
+    pub fn to_naive(&self) -> DateTimeFieldValue {
+        match self {
+            DateTimeFieldValue::OffsetValue(value) => match value.to_utc() {
+                Some(value) => DateTimeFieldValue::Value(value),
+                None => DateTimeFieldValue::Nil,
+            },
+            value => value.clone(),
+        }
+    }
+}
+
 impl LiteralValue for FileFieldValue {
     fn is_nil(&self) -> bool {
         matches!(self, FileFieldValue::Nil)
@@ -292,8 +354,8 @@ impl LiteralValue for RadioButtonFieldValue {
         }
 
         match OptionsValue::from_json(value) {
-            Ok(v) => Ok(RadioButtonFieldValue::Value(v)),
-            Err(_) => Err(ParseLiteralValueError),
+            Ok(v) if v.count_options() <= 1 => Ok(RadioButtonFieldValue::Value(v)),
+            _ => Err(ParseLiteralValueError),
         }
     }
 
@@ -393,6 +455,34 @@ impl LiteralValue for TableRowFieldValue {
     }
 }
 
+impl LiteralValue for TimeFieldValue {
+    fn is_nil(&self) -> bool {
+        matches!(self, TimeFieldValue::Nil)
+    }
+
+    fn from_json(value: &Value) -> Result<Self, ParseLiteralValueError> {
+        if value.is_null() {
+            return Ok(TimeFieldValue::Nil);
+        }
+
+        match NaiveTime::from_json(value) {
+            Ok(v) => Ok(TimeFieldValue::Value(v)),
+            Err(_) => Err(ParseLiteralValueError),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            TimeFieldValue::Value(value) => value.to_json(),
+            TimeFieldValue::Nil => Value::Null,
+        }
+    }
+
+    fn get_field_type(&self) -> FieldType {
+        FieldType::TimeField
+    }
+}
+
 impl LiteralValue for UserBoundaryFieldValue {
     fn is_nil(&self) -> bool {
         matches!(self, UserBoundaryFieldValue::Nil)
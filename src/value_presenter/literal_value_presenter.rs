@@ -1,78 +1,117 @@
 use serde_json::{json, Value};
 
 use super::{
+    coercion::{coerce, Coercion},
     error::DecodeError,
     field_type::FieldType,
     literal_list_value::{
-        BooleanListFieldValue, CascaderListFieldValue, DateTimeListFieldValue, FileListFieldValue,
-        MultipleLineListFieldValue, NumericListFieldValue, RelationListFieldValue,
-        SingleLineListFieldValue, TableRowListFieldValue,
+        BooleanListFieldValue, CascaderListFieldValue, CheckboxListFieldValue, DateListFieldValue,
+        DateTimeListFieldValue, FileListFieldValue, MultipleLineListFieldValue,
+        NumericListFieldValue, RadioButtonListFieldValue, RelationListFieldValue,
+        SingleLineListFieldValue, TableRowListFieldValue, TimeListFieldValue,
+        UserBoundaryListFieldValue,
     },
     literal_naive_value::{
-        BooleanFieldValue, CascaderFieldValue, CheckboxFieldValue, DateTimeFieldValue,
-        FileFieldValue, MultipleLineFieldValue, NumericFieldValue, RadioButtonFieldValue,
-        RelationFieldValue, SingleLineFieldValue, TableRowFieldValue, UserBoundaryFieldValue,
+        BooleanFieldValue, CascaderFieldValue, CheckboxFieldValue, DateFieldValue,
+        DateTimeFieldValue, FileFieldValue, MultipleLineFieldValue, NumericFieldValue,
+        RadioButtonFieldValue, RelationFieldValue, SingleLineFieldValue, TableRowFieldValue,
+        TimeFieldValue, UserBoundaryFieldValue,
     },
     literal_value::{LiteralValue, ParseLiteralValueError},
 };
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum LiteralValuePresenter {
-    BooleanField(BooleanFieldValue),
-    CascaderField(CascaderFieldValue),
-    CheckboxField(CheckboxFieldValue),
-    DateTimeField(DateTimeFieldValue),
-    FileField(FileFieldValue),
-    MultipleLineField(MultipleLineFieldValue),
-    NumericField(NumericFieldValue),
-    RadioButtonField(RadioButtonFieldValue),
-    RelationField(RelationFieldValue),
-    SingleLineField(SingleLineFieldValue),
-    TableRowField(TableRowFieldValue),
-    UserBoundaryField(UserBoundaryFieldValue),
+/// Declares `LiteralValuePresenter` with one variant per field type, along with
+/// the per-variant plumbing: `get_field_type`, `nil`, the `as_*` accessors and
+/// decoding. Each variant is named after its `FieldType`.
+macro_rules! literal_value_presenter {
+    ($($variant:ident($value:ident) => $as_value:ident;)*) => {
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum LiteralValuePresenter {
+            $($variant($value),)*
+        }
+
+        impl LiteralValuePresenter {
+            pub fn get_field_type(&self) -> FieldType {
+                match self {
+                    $(LiteralValuePresenter::$variant(value) => value.get_field_type(),)*
+                }
+            }
+
+            pub fn nil(field_type: FieldType) -> Self {
+                match field_type {
+                    $(FieldType::$variant => LiteralValuePresenter::$variant($value::Nil),)*
+                }
+            }
+
+            fn value_to_json(&self) -> Value {
+                match self {
+                    $(LiteralValuePresenter::$variant(value) => value.to_json(),)*
+                }
+            }
+
+            $(
+                pub fn $as_value(&self) -> Option<&$value> {
+                    match self {
+                        LiteralValuePresenter::$variant(value) => Some(value),
+                        _ => None,
+                    }
+                }
+            )*
+        }
+
+        fn make_literal_field_value(
+            field_type: &FieldType,
+            value: &Value,
+        ) -> Result<LiteralValuePresenter, ParseLiteralValueError> {
+            if !value.is_object() {
+                return Err(ParseLiteralValueError);
+            }
+
+            match field_type {
+                $(FieldType::$variant => match value.get("value") {
+                    Some(value) => $value::from_json(value).map(LiteralValuePresenter::$variant),
+                    None => Ok(LiteralValuePresenter::$variant($value::Nil)),
+                },)*
+            }
+        }
+    };
+}
+
+literal_value_presenter! {
+    BooleanField(BooleanFieldValue) => as_boolean_field_value;
+    CascaderField(CascaderFieldValue) => as_cascade_field_value;
+    CheckboxField(CheckboxFieldValue) => as_checkbox_field_value;
+    DateField(DateFieldValue) => as_date_field_value;
+    DateTimeField(DateTimeFieldValue) => as_date_time_field_value;
+    FileField(FileFieldValue) => as_file_field_value;
+    MultipleLineField(MultipleLineFieldValue) => as_multiple_line_field_value;
+    NumericField(NumericFieldValue) => as_numeric_field_value;
+    RadioButtonField(RadioButtonFieldValue) => as_radio_button_field_value;
+    RelationField(RelationFieldValue) => as_relation_field_value;
+    SingleLineField(SingleLineFieldValue) => as_single_line_field_value;
+    TableRowField(TableRowFieldValue) => as_table_row_field_value;
+    TimeField(TimeFieldValue) => as_time_field_value;
+    UserBoundaryField(UserBoundaryFieldValue) => as_user_boundary_field_value;
 
     // list field
-    BooleanListField(BooleanListFieldValue),
-    CascaderListField(CascaderListFieldValue),
-    DateTimeListField(DateTimeListFieldValue),
-    FileListField(FileListFieldValue),
-    MultipleLineListField(MultipleLineListFieldValue),
-    NumericListField(NumericListFieldValue),
-    RelationListField(RelationListFieldValue),
-    SingleLineListField(SingleLineListFieldValue),
-    TableRowListField(TableRowListFieldValue),
+    BooleanListField(BooleanListFieldValue) => as_boolean_list_field_value;
+    CascaderListField(CascaderListFieldValue) => as_cascader_list_field_value;
+    CheckboxListField(CheckboxListFieldValue) => as_checkbox_list_field_value;
+    DateListField(DateListFieldValue) => as_date_list_field_value;
+    DateTimeListField(DateTimeListFieldValue) => as_date_time_list_field_value;
+    FileListField(FileListFieldValue) => as_file_list_field_value;
+    MultipleLineListField(MultipleLineListFieldValue) => as_multiple_line_list_field_value;
+    NumericListField(NumericListFieldValue) => as_numeric_list_field_value;
+    RadioButtonListField(RadioButtonListFieldValue) => as_radio_button_list_field_value;
+    RelationListField(RelationListFieldValue) => as_relation_list_field_value;
+    SingleLineListField(SingleLineListFieldValue) => as_single_line_list_field_value;
+    TableRowListField(TableRowListFieldValue) => as_table_row_list_field_value;
+    TimeListField(TimeListFieldValue) => as_time_list_field_value;
+    UserBoundaryListField(UserBoundaryListFieldValue) => as_user_boundary_list_field_value;
 }
 
 impl LiteralValuePresenter {
-    pub fn get_field_type(&self) -> FieldType {
-        match self {
-            LiteralValuePresenter::BooleanField(value) => value.get_field_type(),
-            LiteralValuePresenter::CascaderField(value) => value.get_field_type(),
-            LiteralValuePresenter::CheckboxField(value) => value.get_field_type(),
-            LiteralValuePresenter::DateTimeField(value) => value.get_field_type(),
-            LiteralValuePresenter::FileField(value) => value.get_field_type(),
-            LiteralValuePresenter::MultipleLineField(value) => value.get_field_type(),
-            LiteralValuePresenter::NumericField(value) => value.get_field_type(),
-            LiteralValuePresenter::RadioButtonField(value) => value.get_field_type(),
-            LiteralValuePresenter::RelationField(value) => value.get_field_type(),
-            LiteralValuePresenter::SingleLineField(value) => value.get_field_type(),
-            LiteralValuePresenter::TableRowField(value) => value.get_field_type(),
-            LiteralValuePresenter::UserBoundaryField(value) => value.get_field_type(),
-
-            // list field
-            LiteralValuePresenter::BooleanListField(value) => value.get_field_type(),
-            LiteralValuePresenter::CascaderListField(value) => value.get_field_type(),
-            LiteralValuePresenter::DateTimeListField(value) => value.get_field_type(),
-            LiteralValuePresenter::FileListField(value) => value.get_field_type(),
-            LiteralValuePresenter::MultipleLineListField(value) => value.get_field_type(),
-            LiteralValuePresenter::NumericListField(value) => value.get_field_type(),
-            LiteralValuePresenter::RelationListField(value) => value.get_field_type(),
-            LiteralValuePresenter::SingleLineListField(value) => value.get_field_type(),
-            LiteralValuePresenter::TableRowListField(value) => value.get_field_type(),
-        }
-    }
-
-    pub fn from_json(json: &Value) -> Result<Self, DecodeError> {
+    pub fn from_json(json: &Value) -> Result<Self, DecodeError<'_>> {
         if !json.is_object() {
             return Err(DecodeError::InvalidJsonObject(json));
         }
@@ -94,369 +133,56 @@ impl LiteralValuePresenter {
         }
     }
 
-    pub fn to_json(&self) -> Value {
-        let value = match self {
-            LiteralValuePresenter::BooleanField(value) => value.to_json(),
-            LiteralValuePresenter::CascaderField(value) => value.to_json(),
-            LiteralValuePresenter::CheckboxField(value) => value.to_json(),
-            LiteralValuePresenter::DateTimeField(value) => value.to_json(),
-            LiteralValuePresenter::FileField(value) => value.to_json(),
-            LiteralValuePresenter::MultipleLineField(value) => value.to_json(),
-            LiteralValuePresenter::NumericField(value) => value.to_json(),
-            LiteralValuePresenter::RadioButtonField(value) => value.to_json(),
-            LiteralValuePresenter::RelationField(value) => value.to_json(),
-            LiteralValuePresenter::SingleLineField(value) => value.to_json(),
-            LiteralValuePresenter::TableRowField(value) => value.to_json(),
-            LiteralValuePresenter::UserBoundaryField(value) => value.to_json(),
-
-            // list field
-            LiteralValuePresenter::BooleanListField(value) => value.to_json(),
-            LiteralValuePresenter::CascaderListField(value) => value.to_json(),
-            LiteralValuePresenter::DateTimeListField(value) => value.to_json(),
-            LiteralValuePresenter::FileListField(value) => value.to_json(),
-            LiteralValuePresenter::MultipleLineListField(value) => value.to_json(),
-            LiteralValuePresenter::NumericListField(value) => value.to_json(),
-            LiteralValuePresenter::RelationListField(value) => value.to_json(),
-            LiteralValuePresenter::SingleLineListField(value) => value.to_json(),
-            LiteralValuePresenter::TableRowListField(value) => value.to_json(),
-        };
-
-        json!({
-            "type": "LITERAL",
-            "field_type": self.get_field_type().to_str(),
-            "value": value,
-        })
-    }
-
-    pub fn as_boolean_field_value(&self) -> Option<&BooleanFieldValue> {
-        match self {
-            LiteralValuePresenter::BooleanField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_cascade_field_value(&self) -> Option<&CascaderFieldValue> {
-        match self {
-            LiteralValuePresenter::CascaderField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_checkbox_field_value(&self) -> Option<&CheckboxFieldValue> {
-        match self {
-            LiteralValuePresenter::CheckboxField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_date_time_field_value(&self) -> Option<&DateTimeFieldValue> {
-        match self {
-            LiteralValuePresenter::DateTimeField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_file_field_value(&self) -> Option<&FileFieldValue> {
-        match self {
-            LiteralValuePresenter::FileField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_multiple_line_field_value(&self) -> Option<&MultipleLineFieldValue> {
-        match self {
-            LiteralValuePresenter::MultipleLineField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_numeric_field_value(&self) -> Option<&NumericFieldValue> {
-        match self {
-            LiteralValuePresenter::NumericField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_radio_button_field_value(&self) -> Option<&RadioButtonFieldValue> {
-        match self {
-            LiteralValuePresenter::RadioButtonField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_relation_field_value(&self) -> Option<&RelationFieldValue> {
-        match self {
-            LiteralValuePresenter::RelationField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_single_line_field_value(&self) -> Option<&SingleLineFieldValue> {
-        match self {
-            LiteralValuePresenter::SingleLineField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_table_row_field_value(&self) -> Option<&TableRowFieldValue> {
-        match self {
-            LiteralValuePresenter::TableRowField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_user_boundary_field_value(&self) -> Option<&UserBoundaryFieldValue> {
-        match self {
-            LiteralValuePresenter::UserBoundaryField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_boolean_list_field_value(&self) -> Option<&BooleanListFieldValue> {
-        match self {
-            LiteralValuePresenter::BooleanListField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_cascader_list_field_value(&self) -> Option<&CascaderListFieldValue> {
-        match self {
-            LiteralValuePresenter::CascaderListField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_date_time_list_field_value(&self) -> Option<&DateTimeListFieldValue> {
-        match self {
-            LiteralValuePresenter::DateTimeListField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_file_list_field_value(&self) -> Option<&FileListFieldValue> {
-        match self {
-            LiteralValuePresenter::FileListField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_multiple_line_list_field_value(&self) -> Option<&MultipleLineListFieldValue> {
-        match self {
-            LiteralValuePresenter::MultipleLineListField(value) => Some(value),
-            _ => None,
-        }
-    }
-
-    pub fn as_numeric_list_field_value(&self) -> Option<&NumericListFieldValue> {
-        match self {
-            LiteralValuePresenter::NumericListField(value) => Some(value),
-            _ => None,
+    pub fn from_json_lenient(json: &Value) -> Result<(Self, Vec<Coercion>), DecodeError<'_>> {
+        if !json.is_object() {
+            return Err(DecodeError::InvalidJsonObject(json));
         }
-    }
 
-    pub fn as_relation_list_field_value(&self) -> Option<&RelationListFieldValue> {
-        match self {
-            LiteralValuePresenter::RelationListField(value) => Some(value),
-            _ => None,
-        }
-    }
+        match json.get("field_type") {
+            Some(field_type_value) => match field_type_value {
+                Value::String(ref field_type) => match FieldType::parse_str(field_type) {
+                    Ok(field_type) => {
+                        let mut coercions = Vec::new();
+                        let mut coerced_json = json.clone();
 
-    pub fn as_single_line_list_field_value(&self) -> Option<&SingleLineListFieldValue> {
-        match self {
-            LiteralValuePresenter::SingleLineListField(value) => Some(value),
-            _ => None,
-        }
-    }
+                        if let Some(value) = json.get("value") {
+                            coerced_json["value"] = coerce(&field_type, value, &mut coercions);
+                        }
 
-    pub fn as_table_row_list_field_value(&self) -> Option<&TableRowListFieldValue> {
-        match self {
-            LiteralValuePresenter::TableRowListField(value) => Some(value),
-            _ => None,
+                        make_literal_field_value(&field_type, &coerced_json)
+                            .map(|vp| (vp, coercions))
+                            .map_err(|_err| DecodeError::InvalidValue {
+                                field_type,
+                                value: json,
+                            })
+                    }
+                    Err(_err) => Err(DecodeError::UnsupportedFieldType(json)),
+                },
+                _other => Err(DecodeError::UnsupportedFieldType(json)),
+            },
+            None => Err(DecodeError::NoFieldType),
         }
     }
-}
-
-fn make_literal_field_value(
-    field_type: &FieldType,
-    value: &Value,
-) -> Result<LiteralValuePresenter, ParseLiteralValueError> {
-    if !value.is_object() {
-        return Err(ParseLiteralValueError);
-    }
-
-    match field_type {
-        FieldType::BooleanField => match value.get("value") {
-            Some(value) => {
-                BooleanFieldValue::from_json(value).map(LiteralValuePresenter::BooleanField)
-            }
-            None => Ok(LiteralValuePresenter::BooleanField(BooleanFieldValue::Nil)),
-        },
-        FieldType::CascaderField => match value.get("value") {
-            Some(value) => {
-                CascaderFieldValue::from_json(value).map(LiteralValuePresenter::CascaderField)
-            }
-            None => Ok(LiteralValuePresenter::CascaderField(
-                CascaderFieldValue::Nil,
-            )),
-        },
-        FieldType::CheckboxField => match value.get("value") {
-            Some(value) => {
-                CheckboxFieldValue::from_json(value).map(LiteralValuePresenter::CheckboxField)
-            }
-            None => Ok(LiteralValuePresenter::CheckboxField(
-                CheckboxFieldValue::Nil,
-            )),
-        },
-        FieldType::DateTimeField => match value.get("value") {
-            Some(value) => {
-                DateTimeFieldValue::from_json(value).map(LiteralValuePresenter::DateTimeField)
-            }
-            None => Ok(LiteralValuePresenter::DateTimeField(
-                DateTimeFieldValue::Nil,
-            )),
-        },
-        FieldType::FileField => match value.get("value") {
-            Some(value) => FileFieldValue::from_json(value).map(LiteralValuePresenter::FileField),
-            None => Ok(LiteralValuePresenter::FileField(FileFieldValue::Nil)),
-        },
-        FieldType::MultipleLineField => match value.get("value") {
-            Some(value) => MultipleLineFieldValue::from_json(value)
-                .map(LiteralValuePresenter::MultipleLineField),
-            None => Ok(LiteralValuePresenter::MultipleLineField(
-                MultipleLineFieldValue::Nil,
-            )),
-        },
-        FieldType::NumericField => match value.get("value") {
-            Some(value) => {
-                NumericFieldValue::from_json(value).map(LiteralValuePresenter::NumericField)
-            }
-            None => Ok(LiteralValuePresenter::NumericField(NumericFieldValue::Nil)),
-        },
-        FieldType::RadioButtonField => match value.get("value") {
-            Some(value) => RadioButtonFieldValue::from_json(value)
-                .and_then(|field_value| match field_value {
-                    RadioButtonFieldValue::Nil => Ok(field_value),
-                    RadioButtonFieldValue::Value(ref options_value) => {
-                        if options_value.count_options() <= 1 {
-                            Ok(field_value)
-                        } else {
-                            Err(ParseLiteralValueError)
-                        }
-                    }
-                })
-                .map(LiteralValuePresenter::RadioButtonField),
-            None => Ok(LiteralValuePresenter::RadioButtonField(
-                RadioButtonFieldValue::Nil,
-            )),
-        },
-        FieldType::RelationField => match value.get("value") {
-            Some(value) => {
-                RelationFieldValue::from_json(value).map(LiteralValuePresenter::RelationField)
-            }
-            None => Ok(LiteralValuePresenter::RelationField(
-                RelationFieldValue::Nil,
-            )),
-        },
-        FieldType::SingleLineField => match value.get("value") {
-            Some(value) => {
-                SingleLineFieldValue::from_json(value).map(LiteralValuePresenter::SingleLineField)
-            }
-            None => Ok(LiteralValuePresenter::SingleLineField(
-                SingleLineFieldValue::Nil,
-            )),
-        },
-        FieldType::TableRowField => match value.get("value") {
-            Some(value) => {
-                TableRowFieldValue::from_json(value).map(LiteralValuePresenter::TableRowField)
-            }
-            None => Ok(LiteralValuePresenter::TableRowField(
-                TableRowFieldValue::Nil,
-            )),
-        },
-        FieldType::UserBoundaryField => match value.get("value") {
-            Some(value) => UserBoundaryFieldValue::from_json(value)
-                .map(LiteralValuePresenter::UserBoundaryField),
-            None => Ok(LiteralValuePresenter::UserBoundaryField(
-                UserBoundaryFieldValue::Nil,
-            )),
-        },
 
-        // list field
-        FieldType::BooleanListField => match value.get("value") {
-            Some(value) => {
-                BooleanListFieldValue::from_json(value).map(LiteralValuePresenter::BooleanListField)
-            }
-            None => Ok(LiteralValuePresenter::BooleanListField(
-                BooleanListFieldValue::Nil,
-            )),
-        },
-        FieldType::CascaderListField => match value.get("value") {
-            Some(value) => CascaderListFieldValue::from_json(value)
-                .map(LiteralValuePresenter::CascaderListField),
-            None => Ok(LiteralValuePresenter::CascaderListField(
-                CascaderListFieldValue::Nil,
-            )),
-        },
-        FieldType::DateTimeListField => match value.get("value") {
-            Some(value) => DateTimeListFieldValue::from_json(value)
-                .map(LiteralValuePresenter::DateTimeListField),
-            None => Ok(LiteralValuePresenter::DateTimeListField(
-                DateTimeListFieldValue::Nil,
-            )),
-        },
-        FieldType::FileListField => match value.get("value") {
-            Some(value) => {
-                FileListFieldValue::from_json(value).map(LiteralValuePresenter::FileListField)
-            }
-            None => Ok(LiteralValuePresenter::FileListField(
-                FileListFieldValue::Nil,
-            )),
-        },
-        FieldType::MultipleLineListField => match value.get("value") {
-            Some(value) => MultipleLineListFieldValue::from_json(value)
-                .map(LiteralValuePresenter::MultipleLineListField),
-            None => Ok(LiteralValuePresenter::MultipleLineListField(
-                MultipleLineListFieldValue::Nil,
-            )),
-        },
-        FieldType::NumericListField => match value.get("value") {
-            Some(value) => {
-                NumericListFieldValue::from_json(value).map(LiteralValuePresenter::NumericListField)
-            }
-            None => Ok(LiteralValuePresenter::NumericListField(
-                NumericListFieldValue::Nil,
-            )),
-        },
-        FieldType::RelationListField => match value.get("value") {
-            Some(value) => RelationListFieldValue::from_json(value)
-                .map(LiteralValuePresenter::RelationListField),
-            None => Ok(LiteralValuePresenter::RelationListField(
-                RelationListFieldValue::Nil,
-            )),
-        },
-        FieldType::SingleLineListField => match value.get("value") {
-            Some(value) => SingleLineListFieldValue::from_json(value)
-                .map(LiteralValuePresenter::SingleLineListField),
-            None => Ok(LiteralValuePresenter::SingleLineListField(
-                SingleLineListFieldValue::Nil,
-            )),
-        },
-        FieldType::TableRowListField => match value.get("value") {
-            Some(value) => TableRowListFieldValue::from_json(value)
-                .map(LiteralValuePresenter::TableRowListField),
-            None => Ok(LiteralValuePresenter::TableRowListField(
-                TableRowListFieldValue::Nil,
-            )),
-        },
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": "LITERAL",
+            "field_type": self.get_field_type().to_str(),
+            "value": self.value_to_json(),
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::value_presenter::coercion::CoercionKind;
     use crate::value_presenter::value::{
         cascader_value::CascaderValue,
         file_object::FileObject,
+        json_codec::JsonCodec,
+        naive_date::NaiveDate,
         naive_date_time::NaiveDateTime,
+        naive_time::NaiveTime,
         number::Number,
         options_value::OptionsValue,
         prosemirror::ProsemirrorState,
@@ -479,6 +205,103 @@ mod tests {
         assert!(field_type != FieldType::BooleanField);
     }
 
+    #[test]
+    fn test_from_json_lenient() {
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "numeric_field",
+                "value": "42"
+            });
+
+            let (vp, coercions) = LiteralValuePresenter::from_json_lenient(&json).unwrap();
+
+            assert!(matches!(
+                vp,
+                LiteralValuePresenter::NumericField(NumericFieldValue::Value(Number::Integer(42)))
+            ));
+            assert_eq!(
+                coercions,
+                vec![Coercion {
+                    field_type: FieldType::NumericField,
+                    kind: CoercionKind::StringToNumber
+                }]
+            );
+        }
+
+        // single value to list
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "date_time_list_field",
+                "value": "2022/04/29 07:34:10"
+            });
+
+            let (vp, coercions) = LiteralValuePresenter::from_json_lenient(&json).unwrap();
+
+            assert_eq!(
+                vp,
+                LiteralValuePresenter::DateTimeListField(DateTimeListFieldValue::Value(vec![
+                    DateTimeFieldValue::Value(NaiveDateTime::new(2022, 4, 29, 7, 34, 10, 0))
+                ]))
+            );
+            assert_eq!(coercions.len(), 2);
+        }
+
+        // valid value is left untouched
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "single_line_field",
+                "value": "42"
+            });
+
+            let (vp, coercions) = LiteralValuePresenter::from_json_lenient(&json).unwrap();
+
+            assert_eq!(
+                vp,
+                LiteralValuePresenter::SingleLineField(SingleLineFieldValue::Value(
+                    "42".to_string()
+                ))
+            );
+            assert!(coercions.is_empty());
+        }
+
+        // value is not present
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "numeric_field",
+            });
+
+            let (vp, _coercions) = LiteralValuePresenter::from_json_lenient(&json).unwrap();
+
+            assert!(matches!(
+                vp,
+                LiteralValuePresenter::NumericField(NumericFieldValue::Nil)
+            ));
+        }
+
+        // invalid value
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "numeric_field",
+                "value": "forty-two"
+            });
+
+            let result = LiteralValuePresenter::from_json_lenient(&json);
+
+            assert!(matches!(
+                result,
+                Err(DecodeError::InvalidValue {
+                    field_type: FieldType::NumericField,
+                    value: _
+                })
+            ));
+        }
+    }
+
     // test boolean_field
 
     #[test]
@@ -573,7 +396,7 @@ mod tests {
             let str = vp.to_json().to_string();
             let expected = json!({"type": "LITERAL", "field_type": "BOOLEAN_FIELD", "value": true});
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         // null value
@@ -582,7 +405,7 @@ mod tests {
             let str = vp.to_json().to_string();
             let expected = json!({"type": "LITERAL", "field_type": "BOOLEAN_FIELD", "value": null});
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -643,7 +466,7 @@ mod tests {
             let json = json!({
                 "type": "LITERAL",
                 "field_type": "cascader_field",
-                "value": 123 as i64
+                "value": 123_i64
             });
 
             let result = LiteralValuePresenter::from_json(&json);
@@ -663,8 +486,8 @@ mod tests {
         {
             let vp =
                 LiteralValuePresenter::CascaderField(CascaderFieldValue::Value(CascaderValue {
-                    options_table_uuid: Uuid("00000000-0000-0000-0000-000000000000".to_string()),
-                    row_uuid: Uuid("00000000-0000-0000-0000-000000000001".to_string()),
+                    options_table_uuid: Uuid::new("00000000-0000-0000-0000-000000000000").unwrap(),
+                    row_uuid: Uuid::new("00000000-0000-0000-0000-000000000001").unwrap(),
                 }));
             let str = vp.to_json().to_string();
             let expected = json!({
@@ -676,7 +499,7 @@ mod tests {
                 }
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -767,7 +590,7 @@ mod tests {
                 "value": {"options": ["option1", "option2"], "other": "other"}
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         // empty options and other
@@ -784,7 +607,7 @@ mod tests {
                 "value": {"options": [], "other": null}
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         // null value
@@ -797,7 +620,7 @@ mod tests {
                 "value": null
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -831,6 +654,28 @@ mod tests {
             ));
         }
 
+        // with offset
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "date_time_field",
+                "value": "2022-04-29T15:34:10.420159+08:00"
+            });
+
+            let vp = LiteralValuePresenter::from_json(&json).unwrap();
+
+            assert!(matches!(
+                vp,
+                LiteralValuePresenter::DateTimeField(DateTimeFieldValue::OffsetValue(ref value))
+                if value.offset_seconds == 8 * 3600
+            ));
+            assert_eq!(vp.to_json()["value"], json["value"]);
+            assert_eq!(
+                vp.as_date_time_field_value().unwrap().to_naive(),
+                DateTimeFieldValue::Value(expected.clone())
+            );
+        }
+
         // null value
         {
             let json = json!({
@@ -898,7 +743,7 @@ mod tests {
             let str = vp.to_json().to_string();
             let expected = json!({"type": "LITERAL", "field_type": "DATE_TIME_FIELD", "value": "2020-01-01T00:00:00"});
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         // with ms
@@ -916,7 +761,7 @@ mod tests {
             let str = vp.to_json().to_string();
             let expected = json!({"type": "LITERAL", "field_type": "DATE_TIME_FIELD", "value": "2020-01-01T00:00:00.123456"});
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         {
@@ -925,7 +770,97 @@ mod tests {
             let expected =
                 json!({"type": "LITERAL", "field_type": "DATE_TIME_FIELD", "value": null});
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
+        }
+    }
+
+    // test date_field / time_field
+    #[test]
+    fn test_make_literal_date_and_time_field_presenter() {
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "DATE_FIELD",
+                "value": "2022-04-29"
+            });
+
+            let vp = LiteralValuePresenter::from_json(&json).unwrap();
+
+            assert_eq!(
+                vp,
+                LiteralValuePresenter::DateField(DateFieldValue::Value(NaiveDate::new(
+                    2022, 4, 29
+                )))
+            );
+            assert_eq!(vp.to_json(), json);
+        }
+
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "TIME_FIELD",
+                "value": "07:34:10.420159"
+            });
+
+            let vp = LiteralValuePresenter::from_json(&json).unwrap();
+
+            assert_eq!(
+                vp,
+                LiteralValuePresenter::TimeField(TimeFieldValue::Value(NaiveTime::new(
+                    7, 34, 10, 420159000
+                )))
+            );
+            assert_eq!(vp.to_json(), json);
+        }
+
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "DATE_LIST_FIELD",
+                "value": ["2022-04-29", null]
+            });
+
+            let vp = LiteralValuePresenter::from_json(&json).unwrap();
+
+            assert_eq!(
+                vp,
+                LiteralValuePresenter::DateListField(DateListFieldValue::Value(vec![
+                    DateFieldValue::Value(NaiveDate::new(2022, 4, 29)),
+                    DateFieldValue::Nil
+                ]))
+            );
+        }
+
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "TIME_LIST_FIELD",
+                "value": null
+            });
+
+            let vp = LiteralValuePresenter::from_json(&json).unwrap();
+
+            assert_eq!(
+                vp,
+                LiteralValuePresenter::TimeListField(TimeListFieldValue::Nil)
+            );
+        }
+
+        // invalid value
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "DATE_FIELD",
+                "value": "2022-04-29T07:34:10"
+            });
+
+            assert!(matches!(
+                LiteralValuePresenter::from_json(&json),
+                Err(DecodeError::InvalidValue {
+                    field_type: FieldType::DateField,
+                    ..
+                })
+            ));
         }
     }
 
@@ -939,7 +874,7 @@ mod tests {
                 "value": {
                     "object_uuid": "12345678-1234-1234-1234-1234567890ab",
                     "filename": "file.txt",
-                    "filesize": 12345 as i64,
+                    "filesize": 12345_i64,
                     "mimetype": "text/plain",
                 }
             });
@@ -1007,7 +942,7 @@ mod tests {
     fn test_literal_file_field_value_presenter_to_json() {
         {
             let vp = LiteralValuePresenter::FileField(FileFieldValue::Value(FileObject {
-                object_uuid: Uuid("12345678-1234-1234-1234-1234567890ab".to_string()),
+                object_uuid: Uuid::new("12345678-1234-1234-1234-1234567890ab").unwrap(),
                 filename: "file.txt".to_string(),
                 filesize: 12345,
                 mimetype: "text/plain".to_string(),
@@ -1021,12 +956,12 @@ mod tests {
                 "value": {
                     "object_uuid": "12345678-1234-1234-1234-1234567890ab",
                     "filename": "file.txt",
-                    "filesize": 12345 as u64,
+                    "filesize": 12345_u64,
                     "mimetype": "text/plain"
                 }
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         // null
@@ -1041,7 +976,7 @@ mod tests {
                 "value": null
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -1054,8 +989,11 @@ mod tests {
                 "field_type": "multiple_line_field",
                 "value": {
                     "doc": {
-                        "type": "text",
-                        "value": "Hello, world!"
+                        "type": "doc",
+                        "content": [{
+                            "type": "paragraph",
+                            "content": [{ "type": "text", "text": "Hello, world!" }]
+                        }]
                     }
                 }
             });
@@ -1123,14 +1061,16 @@ mod tests {
     fn test_literal_multiple_line_field_value_presenter_to_json() {
         {
             let vp = LiteralValuePresenter::MultipleLineField(MultipleLineFieldValue::Value(
-                ProsemirrorState {
-                    doc: json!( {
-                        "doc": {
-                            "type": "text",
-                            "value": "Hello, world!"
-                        }
-                    }),
-                },
+                ProsemirrorState::from_json(&json!({
+                    "doc": {
+                        "type": "doc",
+                        "content": [{
+                            "type": "paragraph",
+                            "content": [{ "type": "text", "text": "Hello, world!" }]
+                        }]
+                    }
+                }))
+                .unwrap(),
             ));
 
             let str = vp.to_json().to_string();
@@ -1140,13 +1080,16 @@ mod tests {
                 "field_type": "MULTIPLE_LINE_FIELD",
                 "value": {
                     "doc": {
-                        "type": "text",
-                        "value": "Hello, world!"
+                        "type": "doc",
+                        "content": [{
+                            "type": "paragraph",
+                            "content": [{ "type": "text", "text": "Hello, world!" }]
+                        }]
                     }
                 }
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         {
@@ -1160,7 +1103,7 @@ mod tests {
                 "value": null
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -1171,7 +1114,7 @@ mod tests {
             let json = json!({
                 "type": "LITERAL",
                 "field_type": "numeric_field",
-                "value": 123 as i64
+                "value": 123_i64
             });
 
             let vp = LiteralValuePresenter::from_json(&json).unwrap();
@@ -1187,7 +1130,7 @@ mod tests {
             let json = json!({
                 "type": "LITERAL",
                 "field_type": "numeric_field",
-                "value": 123.123 as f64
+                "value": 123.123_f64
             });
 
             let vp = LiteralValuePresenter::from_json(&json).unwrap();
@@ -1268,7 +1211,7 @@ mod tests {
             )
             .unwrap();
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         // float
@@ -1286,7 +1229,7 @@ mod tests {
             )
             .unwrap();
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         // null
@@ -1304,7 +1247,7 @@ mod tests {
             )
             .unwrap();
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -1421,7 +1364,7 @@ mod tests {
                 "value": {"options": ["option"], "other": null}
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         // with other
@@ -1439,7 +1382,7 @@ mod tests {
                 "value": {"options": [], "other": "other"}
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         // empty options and other
@@ -1457,7 +1400,7 @@ mod tests {
                 "value": {"options": [], "other": null}
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         // null value
@@ -1470,7 +1413,7 @@ mod tests {
                 "value": null
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -1552,7 +1495,7 @@ mod tests {
             let vp =
                 LiteralValuePresenter::RelationField(RelationFieldValue::Value(RelationValue {
                     resource_type: ResourceType::DatabaseRow,
-                    resource_uuid: Uuid("00000000-0000-0000-0000-000000000000".to_string()),
+                    resource_uuid: Uuid::new("00000000-0000-0000-0000-000000000000").unwrap(),
                 }));
 
             let str = vp.to_json().to_string();
@@ -1566,7 +1509,7 @@ mod tests {
                 }
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         // null
@@ -1581,7 +1524,7 @@ mod tests {
                 "value": null
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -1666,7 +1609,7 @@ mod tests {
             let expected =
                 json!({"type": "LITERAL", "field_type": "SINGLE_LINE_FIELD", "value": "hello"});
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         // null value
@@ -1676,7 +1619,7 @@ mod tests {
             let expected =
                 json!({"type": "LITERAL", "field_type": "SINGLE_LINE_FIELD", "value": null});
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -1691,7 +1634,7 @@ mod tests {
                 "value": "67e55044-10b1-426f-9247-bb680e5fe0c8"
             });
 
-            let expected_uuid = Uuid("67e55044-10b1-426f-9247-bb680e5fe0c8".to_string());
+            let expected_uuid = Uuid::new("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
 
             let result = LiteralValuePresenter::from_json(&json).unwrap();
 
@@ -1757,14 +1700,14 @@ mod tests {
     fn test_literal_table_row_field_value_presenter_to_json() {
         {
             let uuid_str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
-            let vp = LiteralValuePresenter::TableRowField(TableRowFieldValue::Value(Uuid(
-                uuid_str.to_string(),
-            )));
+            let vp = LiteralValuePresenter::TableRowField(TableRowFieldValue::Value(
+                Uuid::new(uuid_str).unwrap(),
+            ));
             let str = vp.to_json().to_string();
             let expected =
                 json!({"type": "LITERAL", "field_type": "TABLE_ROW_FIELD", "value": uuid_str});
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         // null value
@@ -1774,7 +1717,7 @@ mod tests {
             let expected =
                 json!({"type": "LITERAL", "field_type": "TABLE_ROW_FIELD", "value": null});
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -1858,15 +1801,15 @@ mod tests {
         {
             let vp = LiteralValuePresenter::UserBoundaryField(UserBoundaryFieldValue::Value(
                 UserBoundary {
-                    user_uuids: vec![Uuid("00000000-0000-0000-0000-ffff00000000".to_string())],
+                    user_uuids: vec![Uuid::new("00000000-0000-0000-0000-ffff00000000").unwrap()],
                     simple_department_uuids: vec![
-                        Uuid("00000000-0000-0000-0000-ffff00000001".to_string()),
-                        Uuid("00000000-0000-0000-0000-ffff00000002".to_string()),
+                        Uuid::new("00000000-0000-0000-0000-ffff00000001").unwrap(),
+                        Uuid::new("00000000-0000-0000-0000-ffff00000002").unwrap(),
                     ],
                     penetrating_department_uuids: vec![
-                        Uuid("00000000-0000-0000-0000-ffff00000003".to_string()),
-                        Uuid("00000000-0000-0000-0000-ffff00000004".to_string()),
-                        Uuid("00000000-0000-0000-0000-ffff00000005".to_string()),
+                        Uuid::new("00000000-0000-0000-0000-ffff00000003").unwrap(),
+                        Uuid::new("00000000-0000-0000-0000-ffff00000004").unwrap(),
+                        Uuid::new("00000000-0000-0000-0000-ffff00000005").unwrap(),
                     ],
                 },
             ));
@@ -1890,7 +1833,7 @@ mod tests {
                 }
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         // null value
@@ -1903,7 +1846,7 @@ mod tests {
                 "value": null
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -1999,7 +1942,7 @@ mod tests {
                 "value": [true, false, null]
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         {
@@ -2013,39 +1956,165 @@ mod tests {
                 "value": null
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
+        }
+    }
+
+    // test cascader_list_field
+    #[test]
+    fn test_make_literal_cascader_list_field_presenter() {
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "cascader_list_field",
+                "value": [
+                    {
+                        "options_table_uuid": "00000000-0000-0000-0000-ffff00000000",
+                        "row_uuid": "00000000-0000-0000-0000-ffff00000001",
+                    },
+                    null
+                ],
+            });
+
+            let result = LiteralValuePresenter::from_json(&json).unwrap();
+
+            let expected = vec![
+                CascaderFieldValue::Value(CascaderValue {
+                    options_table_uuid: Uuid::new("00000000-0000-0000-0000-ffff00000000").unwrap(),
+                    row_uuid: Uuid::new("00000000-0000-0000-0000-ffff00000001").unwrap(),
+                }),
+                CascaderFieldValue::Nil,
+            ];
+
+            assert!(matches!(
+                result,
+                LiteralValuePresenter::CascaderListField(CascaderListFieldValue::Value(values)) if values.as_slice() ==  expected
+            ));
+        }
+
+        // null value
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "cascader_list_field",
+                "value": null
+            });
+
+            let result = LiteralValuePresenter::from_json(&json).unwrap();
+
+            assert!(matches!(
+                result,
+                LiteralValuePresenter::CascaderListField(CascaderListFieldValue::Nil)
+            ));
+        }
+
+        // value is not present
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "cascader_list_field",
+            });
+
+            let result = LiteralValuePresenter::from_json(&json).unwrap();
+
+            assert!(matches!(
+                result,
+                LiteralValuePresenter::CascaderListField(CascaderListFieldValue::Nil)
+            ));
+        }
+
+        // invalid value
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "cascader_list_field",
+                "value": 123_i32
+            });
+
+            let result = LiteralValuePresenter::from_json(&json);
+
+            assert!(matches!(
+                result,
+                Err(DecodeError::InvalidValue {
+                    field_type: _,
+                    value: _
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_literal_cascader_list_field_value_presenter_to_json() {
+        {
+            let vp = LiteralValuePresenter::CascaderListField(CascaderListFieldValue::Value(vec![
+                CascaderFieldValue::Value(CascaderValue {
+                    options_table_uuid: Uuid::new("00000000-0000-0000-0000-ffff00000000").unwrap(),
+                    row_uuid: Uuid::new("00000000-0000-0000-0000-ffff00000001").unwrap(),
+                }),
+                CascaderFieldValue::Nil,
+            ]));
+
+            let str = vp.to_json().to_string();
+
+            let expected = json!({
+                "type": "LITERAL",
+                "field_type": "CASCADER_LIST_FIELD",
+                "value": [
+                    {
+                        "options_table_uuid": "00000000-0000-0000-0000-ffff00000000",
+                        "row_uuid": "00000000-0000-0000-0000-ffff00000001",
+                    },
+                    null
+                ],
+            });
+
+            assert_eq!(str, expected.to_string());
+        }
+
+        {
+            let vp = LiteralValuePresenter::CascaderListField(CascaderListFieldValue::Nil);
+
+            let str = vp.to_json().to_string();
+
+            let expected = json!({
+                "type": "LITERAL",
+                "field_type": "CASCADER_LIST_FIELD",
+                "value": null
+            });
+
+            assert_eq!(str, expected.to_string());
         }
     }
 
-    // test cascader_list_field
+    // test checkbox_list_field
     #[test]
-    fn test_make_literal_cascader_list_field_presenter() {
+    fn test_make_literal_checkbox_list_field_presenter() {
         {
             let json = json!({
                 "type": "LITERAL",
-                "field_type": "cascader_list_field",
+                "field_type": "checkbox_list_field",
                 "value": [
                     {
-                        "options_table_uuid": "00000000-0000-0000-0000-ffff00000000",
-                        "row_uuid": "00000000-0000-0000-0000-ffff00000001",
+                        "options": ["option1", "option2"],
+                        "other": "other"
                     },
                     null
-                ],
+                ]
             });
 
             let result = LiteralValuePresenter::from_json(&json).unwrap();
 
             let expected = vec![
-                CascaderFieldValue::Value(CascaderValue {
-                    options_table_uuid: Uuid("00000000-0000-0000-0000-ffff00000000".to_string()),
-                    row_uuid: Uuid("00000000-0000-0000-0000-ffff00000001".to_string()),
+                CheckboxFieldValue::Value(OptionsValue {
+                    options: vec!["option1".to_string(), "option2".to_string()],
+                    other: Some("other".to_string()),
                 }),
-                CascaderFieldValue::Nil,
+                CheckboxFieldValue::Nil,
             ];
 
             assert!(matches!(
                 result,
-                LiteralValuePresenter::CascaderListField(CascaderListFieldValue::Value(values)) if values.as_slice() ==  expected
+                LiteralValuePresenter::CheckboxListField(CheckboxListFieldValue::Value(values)) if values.as_slice() == expected
             ));
         }
 
@@ -2053,7 +2122,7 @@ mod tests {
         {
             let json = json!({
                 "type": "LITERAL",
-                "field_type": "cascader_list_field",
+                "field_type": "checkbox_list_field",
                 "value": null
             });
 
@@ -2061,7 +2130,7 @@ mod tests {
 
             assert!(matches!(
                 result,
-                LiteralValuePresenter::CascaderListField(CascaderListFieldValue::Nil)
+                LiteralValuePresenter::CheckboxListField(CheckboxListFieldValue::Nil)
             ));
         }
 
@@ -2069,14 +2138,14 @@ mod tests {
         {
             let json = json!({
                 "type": "LITERAL",
-                "field_type": "cascader_list_field",
+                "field_type": "checkbox_list_field",
             });
 
             let result = LiteralValuePresenter::from_json(&json).unwrap();
 
             assert!(matches!(
                 result,
-                LiteralValuePresenter::CascaderListField(CascaderListFieldValue::Nil)
+                LiteralValuePresenter::CheckboxListField(CheckboxListFieldValue::Nil)
             ));
         }
 
@@ -2084,8 +2153,8 @@ mod tests {
         {
             let json = json!({
                 "type": "LITERAL",
-                "field_type": "cascader_list_field",
-                "value": 123 as i32
+                "field_type": "checkbox_list_field",
+                "value": 123
             });
 
             let result = LiteralValuePresenter::from_json(&json);
@@ -2101,45 +2170,45 @@ mod tests {
     }
 
     #[test]
-    fn test_literal_cascader_list_field_value_presenter_to_json() {
+    fn test_literal_checkbox_list_field_value_presenter_to_json() {
         {
-            let vp = LiteralValuePresenter::CascaderListField(CascaderListFieldValue::Value(vec![
-                CascaderFieldValue::Value(CascaderValue {
-                    options_table_uuid: Uuid("00000000-0000-0000-0000-ffff00000000".to_string()),
-                    row_uuid: Uuid("00000000-0000-0000-0000-ffff00000001".to_string()),
+            let vp = LiteralValuePresenter::CheckboxListField(CheckboxListFieldValue::Value(vec![
+                CheckboxFieldValue::Value(OptionsValue {
+                    options: vec!["option1".to_string(), "option2".to_string()],
+                    other: Some("other".to_string()),
                 }),
-                CascaderFieldValue::Nil,
+                CheckboxFieldValue::Nil,
             ]));
 
             let str = vp.to_json().to_string();
 
             let expected = json!({
                 "type": "LITERAL",
-                "field_type": "CASCADER_LIST_FIELD",
+                "field_type": "CHECKBOX_LIST_FIELD",
                 "value": [
                     {
-                        "options_table_uuid": "00000000-0000-0000-0000-ffff00000000",
-                        "row_uuid": "00000000-0000-0000-0000-ffff00000001",
+                        "options": ["option1", "option2"],
+                        "other": "other"
                     },
                     null
-                ],
+                ]
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         {
-            let vp = LiteralValuePresenter::CascaderListField(CascaderListFieldValue::Nil);
+            let vp = LiteralValuePresenter::CheckboxListField(CheckboxListFieldValue::Nil);
 
             let str = vp.to_json().to_string();
 
             let expected = json!({
                 "type": "LITERAL",
-                "field_type": "CASCADER_LIST_FIELD",
+                "field_type": "CHECKBOX_LIST_FIELD",
                 "value": null
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -2238,7 +2307,7 @@ mod tests {
                 ]
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         {
@@ -2252,7 +2321,7 @@ mod tests {
                 "value": null
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -2267,7 +2336,7 @@ mod tests {
                     {
                         "object_uuid": "00000000-0000-0000-0000-ffff00000000",
                         "filename": "test.txt",
-                        "filesize": 123 as i64,
+                        "filesize": 123_i64,
                         "mimetype": "text/plain",
                     },
                     null
@@ -2278,7 +2347,7 @@ mod tests {
 
             let expected = vec![
                 FileFieldValue::Value(FileObject {
-                    object_uuid: Uuid("00000000-0000-0000-0000-ffff00000000".to_string()),
+                    object_uuid: Uuid::new("00000000-0000-0000-0000-ffff00000000").unwrap(),
                     filename: "test.txt".to_string(),
                     filesize: 123,
                     mimetype: "text/plain".to_string(),
@@ -2348,7 +2417,7 @@ mod tests {
         {
             let vp = LiteralValuePresenter::FileListField(FileListFieldValue::Value(vec![
                 FileFieldValue::Value(FileObject {
-                    object_uuid: Uuid("00000000-0000-0000-0000-ffff00000000".to_string()),
+                    object_uuid: Uuid::new("00000000-0000-0000-0000-ffff00000000").unwrap(),
                     filename: "test.txt".to_string(),
                     filesize: 123,
                     mimetype: "text/plain".to_string(),
@@ -2373,7 +2442,7 @@ mod tests {
                 ]
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         {
@@ -2387,7 +2456,7 @@ mod tests {
                 "value": null
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -2401,8 +2470,11 @@ mod tests {
                 "value": [
                     {
                         "doc": {
-                            "type": "text",
-                            "value": "Hello, world!"
+                            "type": "doc",
+                            "content": [{
+                                "type": "paragraph",
+                                "content": [{ "type": "text", "text": "Hello, world!" }]
+                            }]
                         }
                     },
                     null
@@ -2412,14 +2484,18 @@ mod tests {
             let result = LiteralValuePresenter::from_json(&json).unwrap();
 
             let expected = vec![
-                MultipleLineFieldValue::Value(ProsemirrorState {
-                    doc: json!({
+                MultipleLineFieldValue::Value(
+                    ProsemirrorState::from_json(&json!({
                         "doc": {
-                            "type": "text",
-                            "value": "Hello, world!"
+                            "type": "doc",
+                            "content": [{
+                                "type": "paragraph",
+                                "content": [{ "type": "text", "text": "Hello, world!" }]
+                            }]
                         }
-                    }),
-                }),
+                    }))
+                    .unwrap(),
+                ),
                 MultipleLineFieldValue::Nil,
             ];
 
@@ -2485,14 +2561,18 @@ mod tests {
         {
             let vp = LiteralValuePresenter::MultipleLineListField(
                 MultipleLineListFieldValue::Value(vec![
-                    MultipleLineFieldValue::Value(ProsemirrorState {
-                        doc: json!({
+                    MultipleLineFieldValue::Value(
+                        ProsemirrorState::from_json(&json!({
                             "doc": {
-                                "type": "text",
-                                "value": "Hello, world!"
+                                "type": "doc",
+                                "content": [{
+                                    "type": "paragraph",
+                                    "content": [{ "type": "text", "text": "Hello, world!" }]
+                                }]
                             }
-                        }),
-                    }),
+                        }))
+                        .unwrap(),
+                    ),
                     MultipleLineFieldValue::Nil,
                 ]),
             );
@@ -2505,15 +2585,18 @@ mod tests {
                 "value": [
                     {
                         "doc": {
-                            "type": "text",
-                            "value": "Hello, world!"
+                            "type": "doc",
+                            "content": [{
+                                "type": "paragraph",
+                                "content": [{ "type": "text", "text": "Hello, world!" }]
+                            }]
                         }
                     },
                     null
                 ]
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         {
@@ -2527,7 +2610,7 @@ mod tests {
                 "value": null
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -2540,7 +2623,7 @@ mod tests {
                 "field_type": "numeric_list_field",
                 "value": [
                     1,
-                    1.2 as f64,
+                    1.2_f64,
                     null,
                 ]
             });
@@ -2631,7 +2714,7 @@ mod tests {
                 ]
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         {
@@ -2645,7 +2728,159 @@ mod tests {
                 "value": null
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
+        }
+    }
+
+    // test radio_button_list_field
+    #[test]
+    fn test_make_literal_radio_button_list_field_presenter() {
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "radio_button_list_field",
+                "value": [
+                    {
+                        "options": ["option1"],
+                        "other": null
+                    },
+                    null
+                ]
+            });
+
+            let result = LiteralValuePresenter::from_json(&json).unwrap();
+
+            let expected = vec![
+                RadioButtonFieldValue::Value(OptionsValue {
+                    options: vec!["option1".to_string()],
+                    other: None,
+                }),
+                RadioButtonFieldValue::Nil,
+            ];
+
+            assert!(matches!(
+                result,
+                LiteralValuePresenter::RadioButtonListField(RadioButtonListFieldValue::Value(values)) if values.as_slice() == expected
+            ));
+        }
+
+        // null value
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "radio_button_list_field",
+                "value": null
+            });
+
+            let result = LiteralValuePresenter::from_json(&json).unwrap();
+
+            assert!(matches!(
+                result,
+                LiteralValuePresenter::RadioButtonListField(RadioButtonListFieldValue::Nil)
+            ));
+        }
+
+        // value is not present
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "radio_button_list_field",
+            });
+
+            let result = LiteralValuePresenter::from_json(&json).unwrap();
+
+            assert!(matches!(
+                result,
+                LiteralValuePresenter::RadioButtonListField(RadioButtonListFieldValue::Nil)
+            ));
+        }
+
+        // invalid value
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "radio_button_list_field",
+                "value": 123
+            });
+
+            let result = LiteralValuePresenter::from_json(&json);
+
+            assert!(matches!(
+                result,
+                Err(DecodeError::InvalidValue {
+                    field_type: _,
+                    value: _
+                })
+            ));
+        }
+
+        // more than one option
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "radio_button_list_field",
+                "value": [
+                    {
+                        "options": ["option1", "option2"],
+                        "other": null
+                    }
+                ]
+            });
+
+            let result = LiteralValuePresenter::from_json(&json);
+
+            assert!(matches!(
+                result,
+                Err(DecodeError::InvalidValue {
+                    field_type: _,
+                    value: _
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_literal_radio_button_list_field_value_presenter_to_json() {
+        {
+            let vp = LiteralValuePresenter::RadioButtonListField(RadioButtonListFieldValue::Value(
+                vec![
+                    RadioButtonFieldValue::Value(OptionsValue {
+                        options: vec!["option1".to_string()],
+                        other: None,
+                    }),
+                    RadioButtonFieldValue::Nil,
+                ],
+            ));
+
+            let str = vp.to_json().to_string();
+
+            let expected = json!({
+                "type": "LITERAL",
+                "field_type": "RADIO_BUTTON_LIST_FIELD",
+                "value": [
+                    {
+                        "options": ["option1"],
+                        "other": null
+                    },
+                    null
+                ]
+            });
+
+            assert_eq!(str, expected.to_string());
+        }
+
+        {
+            let vp = LiteralValuePresenter::RadioButtonListField(RadioButtonListFieldValue::Nil);
+
+            let str = vp.to_json().to_string();
+
+            let expected = json!({
+                "type": "LITERAL",
+                "field_type": "RADIO_BUTTON_LIST_FIELD",
+                "value": null
+            });
+
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -2754,7 +2989,7 @@ mod tests {
 
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         {
@@ -2768,7 +3003,7 @@ mod tests {
                 "value": null
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
     }
 
@@ -2788,7 +3023,9 @@ mod tests {
             let result = LiteralValuePresenter::from_json(&json).unwrap();
 
             let expected = vec![
-                TableRowFieldValue::Value(Uuid("00000000-0000-0000-0000-000000000000".to_string())),
+                TableRowFieldValue::Value(
+                    Uuid::new("00000000-0000-0000-0000-000000000000").unwrap(),
+                ),
                 TableRowFieldValue::Nil,
             ];
 
@@ -2834,7 +3071,7 @@ mod tests {
             let json = json!({
                 "type": "LITERAL",
                 "field_type": "table_row_list_field",
-                "value": 123 as i64
+                "value": 123_i64
             });
 
             let result = LiteralValuePresenter::from_json(&json);
@@ -2853,7 +3090,9 @@ mod tests {
     fn test_literal_table_row_list_field_value_presenter_to_json() {
         {
             let vp = LiteralValuePresenter::TableRowListField(TableRowListFieldValue::Value(vec![
-                TableRowFieldValue::Value(Uuid("00000000-0000-0000-0000-000000000000".to_string())),
+                TableRowFieldValue::Value(
+                    Uuid::new("00000000-0000-0000-0000-000000000000").unwrap(),
+                ),
                 TableRowFieldValue::Nil,
             ]));
 
@@ -2869,7 +3108,7 @@ mod tests {
                 ]
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
         }
 
         {
@@ -2883,7 +3122,139 @@ mod tests {
                 "value": null
             });
 
-            assert!(str == expected.to_string());
+            assert_eq!(str, expected.to_string());
+        }
+    }
+
+    // test user_boundary_list_field
+    #[test]
+    fn test_make_literal_user_boundary_list_field_presenter() {
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "user_boundary_list_field",
+                "value": [
+                    {
+                        "user_uuids": ["00000000-0000-0000-0000-000000000000"],
+                        "simple_department_uuids": [],
+                        "penetrating_department_uuids": []
+                    },
+                    null
+                ]
+            });
+
+            let result = LiteralValuePresenter::from_json(&json).unwrap();
+
+            let expected = vec![
+                UserBoundaryFieldValue::Value(UserBoundary {
+                    user_uuids: vec![Uuid::new("00000000-0000-0000-0000-000000000000").unwrap()],
+                    simple_department_uuids: vec![],
+                    penetrating_department_uuids: vec![],
+                }),
+                UserBoundaryFieldValue::Nil,
+            ];
+
+            assert!(matches!(
+                result,
+                LiteralValuePresenter::UserBoundaryListField(UserBoundaryListFieldValue::Value(values)) if values.as_slice() == expected
+            ));
+        }
+
+        // null value
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "user_boundary_list_field",
+                "value": null
+            });
+
+            let result = LiteralValuePresenter::from_json(&json).unwrap();
+
+            assert!(matches!(
+                result,
+                LiteralValuePresenter::UserBoundaryListField(UserBoundaryListFieldValue::Nil)
+            ));
+        }
+
+        // value is not present
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "user_boundary_list_field",
+            });
+
+            let result = LiteralValuePresenter::from_json(&json).unwrap();
+
+            assert!(matches!(
+                result,
+                LiteralValuePresenter::UserBoundaryListField(UserBoundaryListFieldValue::Nil)
+            ));
+        }
+
+        // invalid value
+        {
+            let json = json!({
+                "type": "LITERAL",
+                "field_type": "user_boundary_list_field",
+                "value": 123
+            });
+
+            let result = LiteralValuePresenter::from_json(&json);
+
+            assert!(matches!(
+                result,
+                Err(DecodeError::InvalidValue {
+                    field_type: _,
+                    value: _
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_literal_user_boundary_list_field_value_presenter_to_json() {
+        {
+            let vp = LiteralValuePresenter::UserBoundaryListField(
+                UserBoundaryListFieldValue::Value(vec![
+                    UserBoundaryFieldValue::Value(UserBoundary {
+                        user_uuids: vec![Uuid::new("00000000-0000-0000-0000-000000000000").unwrap()],
+                        simple_department_uuids: vec![],
+                        penetrating_department_uuids: vec![],
+                    }),
+                    UserBoundaryFieldValue::Nil,
+                ]),
+            );
+
+            let str = vp.to_json().to_string();
+
+            let expected = json!({
+                "type": "LITERAL",
+                "field_type": "USER_BOUNDARY_LIST_FIELD",
+                "value": [
+                    {
+                        "user_uuids": ["00000000-0000-0000-0000-000000000000"],
+                        "simple_department_uuids": [],
+                        "penetrating_department_uuids": []
+                    },
+                    null
+                ]
+            });
+
+            assert_eq!(str, expected.to_string());
+        }
+
+        {
+            let vp = LiteralValuePresenter::UserBoundaryListField(UserBoundaryListFieldValue::Nil);
+
+            let str = vp.to_json().to_string();
+
+            let expected = json!({
+                "type": "LITERAL",
+                "field_type": "USER_BOUNDARY_LIST_FIELD",
+                "value": null
+            });
+
+            assert_eq!(str, expected.to_string());
         }
     }
 }
@@ -1,6 +1,13 @@
+pub mod aggregate;
+pub mod cast;
+pub mod coercion;
+pub mod convert;
+pub mod csv;
 pub mod error;
 pub mod field_type;
+pub mod options_schema;
 pub mod value;
+pub mod xlsx;
 
 pub mod literal_list_value;
 pub mod literal_naive_value;
@@ -10,6 +17,7 @@ pub mod literal_value_presenter;
 
 use serde_json::Value;
 
+use coercion::Coercion;
 use error::DecodeError;
 use field_type::FieldType;
 use literal_value_presenter::LiteralValuePresenter;
@@ -30,7 +38,7 @@ impl ValuePresenter {
 }
 
 impl ValuePresenter {
-    pub fn from_json(json: &Value) -> Result<Self, DecodeError> {
+    pub fn from_json(json: &Value) -> Result<Self, DecodeError<'_>> {
         if !json.is_object() {
             return Err(DecodeError::InvalidJsonObject(json));
         }
@@ -50,6 +58,28 @@ impl ValuePresenter {
         }
     }
 
+    pub fn from_json_lenient(json: &Value) -> Result<(Self, Vec<Coercion>), DecodeError<'_>> {
+        if !json.is_object() {
+            return Err(DecodeError::InvalidJsonObject(json));
+        }
+
+        match json.get("type") {
+            Some(value) => match value {
+                Value::String(ref type_name) => match type_name.as_str() {
+                    "literal" | "LITERAL" => match LiteralValuePresenter::from_json_lenient(json) {
+                        Ok((literal_vp, coercions)) => {
+                            Ok((ValuePresenter::Literal(literal_vp), coercions))
+                        }
+                        Err(error) => Err(error),
+                    },
+                    _ => Err(DecodeError::UnsupportedType(json)),
+                },
+                _ => Err(DecodeError::UnsupportedType(json)),
+            },
+            None => Err(DecodeError::NoType),
+        }
+    }
+
     pub fn to_json(&self) -> Value {
         match self {
             ValuePresenter::Literal(vp) => vp.to_json(),
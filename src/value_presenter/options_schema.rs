@@ -0,0 +1,315 @@
+//! Option definitions of checkbox and radio button fields.
+//!
+//! An `OptionsSchema` lists the options a field allows, whether an "other"
+//! option can be given and how many options can be selected. Validating a
+//! value reports every way it breaks the schema, e.g.
+//!
+//! ```ignore
+//! let schema = OptionsSchema::radio_button(vec!["Red".to_string(), "Blue".to_string()])
+//!     .with_min_selections(1);
+//!
+//! schema.validate_radio_button(&value)?;
+//! ```
+
+use serde_json::{json, Value};
+
+use super::{
+    literal_naive_value::{CheckboxFieldValue, RadioButtonFieldValue},
+    value::{json_codec::JsonCodec, options_value::OptionsValue},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionsSchema {
+    pub options: Vec<String>,
+    /// Whether an option outside of `options` can be given as `other`.
+    pub allow_other: bool,
+    pub min_selections: usize,
+    pub max_selections: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptionsError {
+    UnknownOption(String),
+    DuplicateOption(String),
+    OtherNotAllowed,
+    EmptyOther,
+    TooFewSelections { min: usize, count: usize },
+    TooManySelections { max: usize, count: usize },
+}
+
+#[derive(Debug)]
+pub enum ParseOptionsSchemaError {
+    InvalidJson,
+    InvalidOptions,
+    InvalidAllowOther,
+    InvalidMinSelections,
+    InvalidMaxSelections,
+}
+
+impl OptionsSchema {
+    /// A schema of checkbox options, allowing any number of them to be
+    /// selected and no other option.
+    pub fn checkbox(options: Vec<String>) -> Self {
+        Self {
+            options,
+            allow_other: false,
+            min_selections: 0,
+            max_selections: None,
+        }
+    }
+
+    /// A schema of radio button options, allowing at most one of them to be
+    /// selected and no other option.
+    pub fn radio_button(options: Vec<String>) -> Self {
+        Self {
+            max_selections: Some(1),
+            ..Self::checkbox(options)
+        }
+    }
+
+    pub fn with_other(mut self, allow_other: bool) -> Self {
+        self.allow_other = allow_other;
+        self
+    }
+
+    pub fn with_min_selections(mut self, min_selections: usize) -> Self {
+        self.min_selections = min_selections;
+        self
+    }
+
+    pub fn with_max_selections(mut self, max_selections: usize) -> Self {
+        self.max_selections = Some(max_selections);
+        self
+    }
+
+    /// Validates the options of `value`, `other` counting as a selection.
+    pub fn validate(&self, value: &OptionsValue) -> Result<(), Vec<OptionsError>> {
+        let mut errors = vec![];
+
+        for (index, option) in value.options.iter().enumerate() {
+            if !self.options.contains(option) {
+                errors.push(OptionsError::UnknownOption(option.clone()));
+            } else if value.options[..index].contains(option) {
+                errors.push(OptionsError::DuplicateOption(option.clone()));
+            }
+        }
+
+        match &value.other {
+            Some(_other) if !self.allow_other => errors.push(OptionsError::OtherNotAllowed),
+            Some(other) if other.trim().is_empty() => errors.push(OptionsError::EmptyOther),
+            _ => (),
+        }
+
+        self.validate_count(value.count_options(), &mut errors);
+        into_result(errors)
+    }
+
+    /// Validates a checkbox value, a `Nil` value having no selection.
+    pub fn validate_checkbox(&self, value: &CheckboxFieldValue) -> Result<(), Vec<OptionsError>> {
+        match value {
+            CheckboxFieldValue::Value(value) => self.validate(value),
+            CheckboxFieldValue::Nil => self.validate_nil(),
+        }
+    }
+
+    /// Validates a radio button value, a `Nil` value having no selection.
+    pub fn validate_radio_button(
+        &self,
+        value: &RadioButtonFieldValue,
+    ) -> Result<(), Vec<OptionsError>> {
+        match value {
+            RadioButtonFieldValue::Value(value) => self.validate(value),
+            RadioButtonFieldValue::Nil => self.validate_nil(),
+        }
+    }
+
+    fn validate_nil(&self) -> Result<(), Vec<OptionsError>> {
+        let mut errors = vec![];
+        self.validate_count(0, &mut errors);
+        into_result(errors)
+    }
+
+    fn validate_count(&self, count: usize, errors: &mut Vec<OptionsError>) {
+        if count < self.min_selections {
+            errors.push(OptionsError::TooFewSelections {
+                min: self.min_selections,
+                count,
+            });
+        }
+
+        match self.max_selections {
+            Some(max) if count > max => errors.push(OptionsError::TooManySelections { max, count }),
+            _ => (),
+        }
+    }
+}
+
+fn into_result(errors: Vec<OptionsError>) -> Result<(), Vec<OptionsError>> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+impl JsonCodec for OptionsSchema {
+    type Err = ParseOptionsSchemaError;
+
+    fn from_json(value: &Value) -> Result<Self, Self::Err> {
+        if !value.is_object() {
+            return Err(ParseOptionsSchemaError::InvalidJson);
+        }
+
+        let options = match value.get("options") {
+            Some(Value::Array(options)) => options
+                .iter()
+                .map(|option| option.as_str().map(String::from))
+                .collect::<Option<Vec<_>>>()
+                .ok_or(ParseOptionsSchemaError::InvalidOptions)?,
+            _ => return Err(ParseOptionsSchemaError::InvalidOptions),
+        };
+
+        let allow_other = match value.get("allow_other") {
+            Some(Value::Bool(allow_other)) => *allow_other,
+            Some(Value::Null) | None => false,
+            Some(_) => return Err(ParseOptionsSchemaError::InvalidAllowOther),
+        };
+
+        let min_selections = match value.get("min_selections") {
+            Some(Value::Null) | None => 0,
+            Some(min_selections) => min_selections
+                .as_u64()
+                .ok_or(ParseOptionsSchemaError::InvalidMinSelections)?
+                as usize,
+        };
+
+        let max_selections = match value.get("max_selections") {
+            Some(Value::Null) | None => None,
+            Some(max_selections) => Some(
+                max_selections
+                    .as_u64()
+                    .ok_or(ParseOptionsSchemaError::InvalidMaxSelections)? as usize,
+            ),
+        };
+
+        Ok(OptionsSchema {
+            options,
+            allow_other,
+            min_selections,
+            max_selections,
+        })
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "options": self.options,
+            "allow_other": self.allow_other,
+            "min_selections": self.min_selections,
+            "max_selections": self.max_selections,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(options: &[&str]) -> Vec<String> {
+        options.iter().map(|option| option.to_string()).collect()
+    }
+
+    #[test]
+    fn test_validate() {
+        let schema = OptionsSchema::checkbox(options(&["a", "b", "c"]))
+            .with_min_selections(1)
+            .with_max_selections(2);
+
+        assert_eq!(
+            schema.validate(&OptionsValue::from_strs(&["a", "c"], None)),
+            Ok(())
+        );
+        assert_eq!(
+            schema.validate(&OptionsValue::from_strs(&["a", "x", "a"], Some("y"))),
+            Err(vec![
+                OptionsError::UnknownOption("x".to_string()),
+                OptionsError::DuplicateOption("a".to_string()),
+                OptionsError::OtherNotAllowed,
+                OptionsError::TooManySelections { max: 2, count: 4 },
+            ])
+        );
+        assert_eq!(
+            schema.validate_checkbox(&CheckboxFieldValue::Nil),
+            Err(vec![OptionsError::TooFewSelections { min: 1, count: 0 }])
+        );
+
+        let schema = schema.with_other(true);
+        assert_eq!(
+            schema.validate(&OptionsValue::from_strs(&["b"], Some("z"))),
+            Ok(())
+        );
+        assert_eq!(
+            schema.validate(&OptionsValue::from_strs(&[], Some(" "))),
+            Err(vec![OptionsError::EmptyOther])
+        );
+    }
+
+    #[test]
+    fn test_validate_radio_button() {
+        let schema = OptionsSchema::radio_button(options(&["a", "b"]));
+
+        assert_eq!(
+            schema.validate_radio_button(&RadioButtonFieldValue::Value(OptionsValue::from_strs(
+                &["b"],
+                None
+            ))),
+            Ok(())
+        );
+        assert_eq!(
+            schema.validate_radio_button(&RadioButtonFieldValue::Nil),
+            Ok(())
+        );
+        assert_eq!(
+            schema.validate_radio_button(&RadioButtonFieldValue::Value(OptionsValue::from_strs(
+                &["a", "b"],
+                None
+            ))),
+            Err(vec![OptionsError::TooManySelections { max: 1, count: 2 }])
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let json = json!({
+            "options": ["a", "b"],
+            "allow_other": true,
+            "min_selections": 1,
+            "max_selections": null,
+        });
+
+        let schema = OptionsSchema::from_json(&json).unwrap();
+        assert_eq!(
+            schema,
+            OptionsSchema::checkbox(options(&["a", "b"]))
+                .with_other(true)
+                .with_min_selections(1)
+        );
+        assert_eq!(schema.to_json(), json);
+
+        assert_eq!(
+            OptionsSchema::from_json(&json!({ "options": ["a"] })).unwrap(),
+            OptionsSchema::checkbox(options(&["a"]))
+        );
+        assert!(matches!(
+            OptionsSchema::from_json(&json!({ "options": [1] })),
+            Err(ParseOptionsSchemaError::InvalidOptions)
+        ));
+        assert!(matches!(
+            OptionsSchema::from_json(&json!({ "options": [], "max_selections": -1 })),
+            Err(ParseOptionsSchemaError::InvalidMaxSelections)
+        ));
+        assert!(matches!(
+            OptionsSchema::from_json(&json!([])),
+            Err(ParseOptionsSchemaError::InvalidJson)
+        ));
+    }
+}
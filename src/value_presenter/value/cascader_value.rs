@@ -81,7 +81,7 @@ mod tests {
                 Ok(CascaderValue {
                     options_table_uuid,
                     row_uuid,
-                }) if options_table_uuid == Uuid("00000000-0000-0000-0000-ffff00000000".to_string()) && row_uuid == Uuid("00000000-0000-0000-0000-ffff00000001".to_string())
+                }) if options_table_uuid == Uuid::new("00000000-0000-0000-0000-ffff00000000").unwrap() && row_uuid == Uuid::new("00000000-0000-0000-0000-ffff00000001").unwrap()
             ));
         }
     }
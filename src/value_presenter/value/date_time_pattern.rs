@@ -0,0 +1,216 @@
+use time::{
+    format_description::{self, BorrowedFormatItem},
+    formatting::Formattable,
+    macros::format_description,
+    parsing::Parsable,
+    Date, PrimitiveDateTime,
+};
+
+use super::naive_date_time::{NaiveDateTime, ParseNaiveDateTimeError};
+
+/// Named `time` format descriptions for common date and time layouts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateTimePattern {
+    /// `2022-04-29T07:34:10`
+    Iso8601,
+    /// `2022-04-29 07:34:10`
+    DateTime,
+    /// `2022-04-29 07:34`
+    DateTimeMinute,
+    /// `2022-04-29`
+    Date,
+    /// `2022/04/29 07:34:10`
+    SlashDateTime,
+    /// `2022/04/29 07:34`
+    SlashDateTimeMinute,
+    /// `2022/04/29`
+    SlashDate,
+    /// `20220429`
+    CompactDate,
+    /// `04/29/2022`
+    UsDate,
+    /// `2022年04月29日`
+    ChineseDate,
+    /// `2022年04月29日 07:34`
+    ChineseDateTimeMinute,
+}
+
+impl DateTimePattern {
+    pub const ALL: [DateTimePattern; 11] = [
+        DateTimePattern::Iso8601,
+        DateTimePattern::DateTime,
+        DateTimePattern::DateTimeMinute,
+        DateTimePattern::Date,
+        DateTimePattern::SlashDateTime,
+        DateTimePattern::SlashDateTimeMinute,
+        DateTimePattern::SlashDate,
+        DateTimePattern::CompactDate,
+        DateTimePattern::UsDate,
+        DateTimePattern::ChineseDate,
+        DateTimePattern::ChineseDateTimeMinute,
+    ];
+
+    pub fn format_description(self) -> &'static [BorrowedFormatItem<'static>] {
+        match self {
+            DateTimePattern::Iso8601 => {
+                format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]")
+            }
+            DateTimePattern::DateTime => {
+                format_description!("[year]-[month]-[day] [hour]:[minute]:[second]")
+            }
+            DateTimePattern::DateTimeMinute => {
+                format_description!("[year]-[month]-[day] [hour]:[minute]")
+            }
+            DateTimePattern::Date => format_description!("[year]-[month]-[day]"),
+            DateTimePattern::SlashDateTime => {
+                format_description!("[year]/[month]/[day] [hour]:[minute]:[second]")
+            }
+            DateTimePattern::SlashDateTimeMinute => {
+                format_description!("[year]/[month]/[day] [hour]:[minute]")
+            }
+            DateTimePattern::SlashDate => format_description!("[year]/[month]/[day]"),
+            DateTimePattern::CompactDate => format_description!("[year][month][day]"),
+            DateTimePattern::UsDate => format_description!("[month]/[day]/[year]"),
+            DateTimePattern::ChineseDate => format_description!("[year]年[month]月[day]日"),
+            DateTimePattern::ChineseDateTimeMinute => {
+                format_description!("[year]年[month]月[day]日 [hour]:[minute]")
+            }
+        }
+    }
+}
+
+impl NaiveDateTime {
+    /// Parses `str` with a `time` format description. A description without
+    /// time components gives midnight.
+    pub fn parse_with<F>(str: &str, format: &F) -> Result<Self, ParseNaiveDateTimeError>
+    where
+        F: Parsable + ?Sized,
+    {
+        PrimitiveDateTime::parse(str, format)
+            .or_else(|_| Date::parse(str, format).map(Date::midnight))
+            .map(NaiveDateTime::from)
+            .map_err(|_err| ParseNaiveDateTimeError)
+    }
+
+    /// Parses `str` with a format description written as a string, e.g.
+    /// `"[year]/[month]/[day]"`. An invalid description fails to parse.
+    pub fn parse_with_pattern(str: &str, pattern: &str) -> Result<Self, ParseNaiveDateTimeError> {
+        let format = format_description::parse_borrowed::<1>(pattern)
+            .map_err(|_err| ParseNaiveDateTimeError)?;
+
+        NaiveDateTime::parse_with(str, format.as_slice())
+    }
+
+    /// Parses `str` with the first of `patterns` that matches.
+    pub fn parse_any(
+        str: &str,
+        patterns: &[DateTimePattern],
+    ) -> Result<Self, ParseNaiveDateTimeError> {
+        patterns
+            .iter()
+            .find_map(|pattern| NaiveDateTime::parse_with(str, pattern.format_description()).ok())
+            .ok_or(ParseNaiveDateTimeError)
+    }
+
+    /// Formats with a `time` format description, `None` if the date or time is
+    /// invalid.
+    pub fn format_with<F>(&self, format: &F) -> Option<String>
+    where
+        F: Formattable + ?Sized,
+    {
+        self.to_primitive_date_time()?.format(format).ok()
+    }
+
+    /// Formats with a format description written as a string, `None` if the
+    /// description is invalid.
+    pub fn format_with_pattern(&self, pattern: &str) -> Option<String> {
+        let format = format_description::parse_borrowed::<1>(pattern).ok()?;
+
+        self.format_with(format.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let expected = NaiveDateTime::new(2022, 4, 29, 7, 34, 0, 0);
+        let date = NaiveDateTime::new(2022, 4, 29, 0, 0, 0, 0);
+
+        assert_eq!(
+            NaiveDateTime::parse_with(
+                "2022-04-29 07:34",
+                DateTimePattern::DateTimeMinute.format_description()
+            )
+            .unwrap(),
+            expected
+        );
+        assert_eq!(
+            NaiveDateTime::parse_with(
+                "20220429",
+                DateTimePattern::CompactDate.format_description()
+            )
+            .unwrap(),
+            date
+        );
+        assert_eq!(
+            NaiveDateTime::parse_with_pattern(
+                "29.04.2022 07:34",
+                "[day].[month].[year] [hour]:[minute]"
+            )
+            .unwrap(),
+            expected
+        );
+        assert!(NaiveDateTime::parse_with_pattern("2022/04/29", "[year]-[month]-[day]").is_err());
+
+        // invalid pattern
+        assert!(NaiveDateTime::parse_with_pattern("2022/04/29", "[year/[month]").is_err());
+    }
+
+    #[test]
+    fn test_parse_any() {
+        let date = NaiveDateTime::new(2022, 4, 29, 0, 0, 0, 0);
+
+        assert_eq!(
+            NaiveDateTime::parse_any("2022/04/29", &DateTimePattern::ALL).unwrap(),
+            date
+        );
+        assert_eq!(
+            NaiveDateTime::parse_any("04/29/2022", &DateTimePattern::ALL).unwrap(),
+            date
+        );
+        assert_eq!(
+            NaiveDateTime::parse_any("2022年04月29日 07:34", &DateTimePattern::ALL).unwrap(),
+            NaiveDateTime::new(2022, 4, 29, 7, 34, 0, 0)
+        );
+        assert!(NaiveDateTime::parse_any("2022/04/29", &[DateTimePattern::Date]).is_err());
+    }
+
+    #[test]
+    fn test_format() {
+        let dt = NaiveDateTime::new(2022, 4, 29, 7, 34, 10, 0);
+
+        assert_eq!(
+            dt.format_with(DateTimePattern::ChineseDate.format_description()),
+            Some("2022年04月29日".to_string())
+        );
+        assert_eq!(
+            dt.format_with(DateTimePattern::SlashDateTime.format_description()),
+            Some("2022/04/29 07:34:10".to_string())
+        );
+        assert_eq!(
+            dt.format_with_pattern("[month]/[day] [hour]h"),
+            Some("04/29 07h".to_string())
+        );
+        assert_eq!(dt.format_with_pattern("[month"), None);
+
+        // invalid date
+        assert_eq!(
+            NaiveDateTime::new(2022, 2, 30, 0, 0, 0, 0)
+                .format_with(DateTimePattern::Date.format_description()),
+            None
+        );
+    }
+}
@@ -1,6 +1,10 @@
 use serde_json::{json, Value};
 
-use super::{json_codec::JsonCodec, uuid::Uuid};
+use super::{
+    json_codec::JsonCodec,
+    media_type::{MediaType, ParseMediaTypeError},
+    uuid::Uuid,
+};
 
 #[derive(Debug)]
 pub enum ParseFileObjectError {
@@ -28,6 +32,40 @@ impl FileObject {
             mimetype,
         }
     }
+
+    /// Parses `mimetype`.
+    pub fn media_type(&self) -> Result<MediaType, ParseMediaTypeError> {
+        self.mimetype.parse()
+    }
+
+    /// Returns the parsed `mimetype`, or the media type of the filename's
+    /// extension when `mimetype` is invalid or `application/octet-stream`.
+    pub fn guess_media_type(&self) -> Option<MediaType> {
+        match self.media_type() {
+            Ok(media_type) if !media_type.matches("application/octet-stream") => Some(media_type),
+            _ => MediaType::from_filename(&self.filename),
+        }
+    }
+
+    /// Opens a reader over the contents of the file object.
+    #[cfg(feature = "files")]
+    pub fn open(&self) -> crate::files::FileReader {
+        crate::files::FileReader::new(self)
+    }
+
+    /// Reads all the contents of the file object, failing with
+    /// `UnexpectedEof` if the host has fewer bytes than `filesize`.
+    #[cfg(feature = "files")]
+    pub fn read(&self) -> std::io::Result<Vec<u8>> {
+        self.open().read_remaining()
+    }
+
+    /// Uploads `bytes` as a new file object, e.g. to return a generated report
+    /// as a `FileFieldValue`.
+    #[cfg(feature = "files")]
+    pub fn create(filename: &str, mimetype: &str, bytes: &[u8]) -> std::io::Result<Self> {
+        crate::files::create(filename, mimetype, bytes)
+    }
 }
 
 impl JsonCodec for FileObject {
@@ -111,7 +149,7 @@ mod tests {
                     filename,
                     filesize,
                     mimetype,
-                }) if object_uuid == Uuid("00000000-0000-0000-0000-ffff00000000".to_string()) && filename == "test.txt" && filesize == 123 && mimetype == "text/plain"
+                }) if object_uuid == Uuid::new("00000000-0000-0000-0000-ffff00000000").unwrap() && filename == "test.txt" && filesize == 123 && mimetype == "text/plain"
             ));
         }
     }
@@ -130,7 +168,7 @@ mod tests {
 
         // invalid object_uuid
         {
-            let json = json!({"object_uuid": "invalid", "filename": "test.txt", "filesize": 123 as i64, "mimetype": "text/plain"});
+            let json = json!({"object_uuid": "invalid", "filename": "test.txt", "filesize": 123_i64, "mimetype": "text/plain"});
 
             let file_object = FileObject::from_json(&json);
             assert!(matches!(
@@ -141,7 +179,7 @@ mod tests {
 
         // invalid filename
         {
-            let json = json!({"object_uuid": "00000000-0000-0000-0000-ffff00000000", "filename": null,  "filesize": 123 as i64, "mimetype": "text/plain"});
+            let json = json!({"object_uuid": "00000000-0000-0000-0000-ffff00000000", "filename": null,  "filesize": 123_i64, "mimetype": "text/plain"});
 
             let file_object = FileObject::from_json(&json);
             assert!(matches!(
@@ -163,7 +201,7 @@ mod tests {
 
         // invalid mimetype
         {
-            let json = json!({"object_uuid": "00000000-0000-0000-0000-ffff00000000", "filename": "test.txt", "filesize": 123 as i64, "mimetype": null});
+            let json = json!({"object_uuid": "00000000-0000-0000-0000-ffff00000000", "filename": "test.txt", "filesize": 123_i64, "mimetype": null});
 
             let file_object = FileObject::from_json(&json);
             assert!(matches!(
@@ -173,11 +211,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_media_type() {
+        let file_object = |filename: &str, mimetype: &str| {
+            FileObject::new(Uuid::nil(), filename.to_string(), 123, mimetype.to_string())
+        };
+
+        assert_eq!(
+            file_object("test.txt", "text/plain; charset=utf-8")
+                .media_type()
+                .unwrap()
+                .param("charset"),
+            Some("utf-8")
+        );
+        assert!(file_object("test.txt", "").media_type().is_err());
+
+        assert_eq!(
+            file_object("test.csv", "text/plain").guess_media_type(),
+            MediaType::new("text/plain").ok()
+        );
+        assert_eq!(
+            file_object("test.csv", "application/octet-stream").guess_media_type(),
+            MediaType::new("text/csv").ok()
+        );
+        assert_eq!(
+            file_object("test.png", "").guess_media_type(),
+            MediaType::new("image/png").ok()
+        );
+        assert_eq!(file_object("test", "").guess_media_type(), None);
+    }
+
     #[test]
     fn test_to_json() {
         {
             let file_object = FileObject {
-                object_uuid: Uuid("00000000-0000-0000-0000-ffff00000000".to_string()),
+                object_uuid: Uuid::new("00000000-0000-0000-0000-ffff00000000").unwrap(),
                 filename: "test.txt".to_string(),
                 filesize: 123,
                 mimetype: "text/plain".to_string(),
@@ -0,0 +1,460 @@
+use core::{fmt, str::FromStr};
+
+use serde_json::Value;
+
+use super::json_codec::JsonCodec;
+
+/// A media type such as `text/csv; charset=utf-8`, encoded in JSON as a
+/// string. The type, subtype and parameter names are lowercased when parsed,
+/// parameter values are kept as they are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaType {
+    type_name: String,
+    subtype: String,
+    params: Vec<(String, String)>,
+}
+
+#[derive(Debug)]
+pub struct ParseMediaTypeError;
+
+/// Extensions and the media types of their files, the first extension of a
+/// media type being its preferred one.
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("txt", "text/plain"),
+    ("csv", "text/csv"),
+    ("tsv", "text/tab-separated-values"),
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    ("css", "text/css"),
+    ("js", "text/javascript"),
+    ("md", "text/markdown"),
+    ("json", "application/json"),
+    ("xml", "application/xml"),
+    ("pdf", "application/pdf"),
+    ("zip", "application/zip"),
+    ("gz", "application/gzip"),
+    ("doc", "application/msword"),
+    (
+        "docx",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    ("xls", "application/vnd.ms-excel"),
+    (
+        "xlsx",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    ),
+    ("ppt", "application/vnd.ms-powerpoint"),
+    (
+        "pptx",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    ),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("bmp", "image/bmp"),
+    ("tiff", "image/tiff"),
+    ("tif", "image/tiff"),
+    ("svg", "image/svg+xml"),
+    ("ico", "image/vnd.microsoft.icon"),
+    ("mp3", "audio/mpeg"),
+    ("wav", "audio/wav"),
+    ("ogg", "audio/ogg"),
+    ("mp4", "video/mp4"),
+    ("webm", "video/webm"),
+    ("mov", "video/quicktime"),
+];
+
+impl JsonCodec for MediaType {
+    type Err = ParseMediaTypeError;
+
+    fn from_json(value: &Value) -> Result<Self, Self::Err> {
+        match value.as_str() {
+            Some(str) => str.parse(),
+            None => Err(ParseMediaTypeError),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl MediaType {
+    pub fn new(str: &str) -> Result<MediaType, ParseMediaTypeError> {
+        str.parse()
+    }
+
+    /// The type, e.g. `image` for `image/png`.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// The subtype, e.g. `svg+xml` for `image/svg+xml`.
+    pub fn subtype(&self) -> &str {
+        &self.subtype
+    }
+
+    /// The structured syntax suffix of the subtype, e.g. `xml` for
+    /// `image/svg+xml`.
+    pub fn suffix(&self) -> Option<&str> {
+        self.subtype
+            .rfind('+')
+            .map(|index| &self.subtype[index + 1..])
+    }
+
+    /// The type and subtype without parameters, e.g. `text/csv`.
+    pub fn essence(&self) -> String {
+        format!("{}/{}", self.type_name, self.subtype)
+    }
+
+    pub fn params(&self) -> &[(String, String)] {
+        &self.params
+    }
+
+    /// Returns the value of a parameter, its name being case-insensitive.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _value)| param.eq_ignore_ascii_case(name))
+            .map(|(_param, value)| value.as_str())
+    }
+
+    /// Returns whether the essence matches `pattern`, which is a media type
+    /// without parameters, `type/*` or `*/*`.
+    pub fn matches(&self, pattern: &str) -> bool {
+        let (type_name, subtype) = match pattern.find('/') {
+            Some(index) => (&pattern[..index], &pattern[index + 1..]),
+            None => return false,
+        };
+
+        (type_name == "*" || type_name.eq_ignore_ascii_case(&self.type_name))
+            && (subtype == "*" || subtype.eq_ignore_ascii_case(&self.subtype))
+    }
+
+    pub fn is_text(&self) -> bool {
+        self.type_name == "text"
+    }
+
+    pub fn is_image(&self) -> bool {
+        self.type_name == "image"
+    }
+
+    pub fn is_audio(&self) -> bool {
+        self.type_name == "audio"
+    }
+
+    pub fn is_video(&self) -> bool {
+        self.type_name == "video"
+    }
+
+    pub fn is_pdf(&self) -> bool {
+        self.matches("application/pdf")
+    }
+
+    /// Returns whether this is `application/json` or a `+json` type.
+    pub fn is_json(&self) -> bool {
+        self.matches("application/json") || self.suffix() == Some("json")
+    }
+
+    pub fn is_csv(&self) -> bool {
+        self.matches("text/csv")
+    }
+
+    /// Returns the media type of files with the extension, e.g. `csv` or
+    /// `.CSV`.
+    pub fn from_extension(extension: &str) -> Option<MediaType> {
+        let extension = extension.strip_prefix('.').unwrap_or(extension);
+
+        EXTENSIONS
+            .iter()
+            .find(|(other, _media_type)| other.eq_ignore_ascii_case(extension))
+            .map(|(_extension, media_type)| essence(media_type))
+    }
+
+    /// Returns the media type of a file from the extension of its name.
+    pub fn from_filename(filename: &str) -> Option<MediaType> {
+        match filename.rfind('.') {
+            Some(index) if index > 0 => MediaType::from_extension(&filename[index + 1..]),
+            _ => None,
+        }
+    }
+
+    /// The preferred extension of files of this media type.
+    pub fn extension(&self) -> Option<&'static str> {
+        let essence = self.essence();
+
+        EXTENSIONS
+            .iter()
+            .find(|(_extension, media_type)| *media_type == essence)
+            .map(|(extension, _media_type)| *extension)
+    }
+
+    /// Detects the media type of contents from their leading magic bytes.
+    /// Office Open XML documents are detected as `application/zip`.
+    pub fn sniff(bytes: &[u8]) -> Option<MediaType> {
+        let media_type = if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            "image/png"
+        } else if bytes.starts_with(b"\xff\xd8\xff") {
+            "image/jpeg"
+        } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            "image/gif"
+        } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+            "image/webp"
+        } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WAVE") {
+            "audio/wav"
+        } else if bytes.starts_with(b"BM") && bytes.len() >= 14 {
+            "image/bmp"
+        } else if bytes.starts_with(b"II*\x00") || bytes.starts_with(b"MM\x00*") {
+            "image/tiff"
+        } else if bytes.starts_with(b"%PDF-") {
+            "application/pdf"
+        } else if bytes.starts_with(b"PK\x03\x04") || bytes.starts_with(b"PK\x05\x06") {
+            "application/zip"
+        } else if bytes.starts_with(b"\x1f\x8b") {
+            "application/gzip"
+        } else if bytes.starts_with(b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1") {
+            // OLE compound files, e.g. legacy Office documents
+            "application/x-ole-storage"
+        } else if bytes.starts_with(b"ID3") || bytes.starts_with(b"\xff\xfb") {
+            "audio/mpeg"
+        } else if bytes.starts_with(b"OggS") {
+            "audio/ogg"
+        } else if bytes.get(4..8) == Some(b"ftyp") {
+            "video/mp4"
+        } else if bytes.starts_with(b"\x1a\x45\xdf\xa3") {
+            "video/webm"
+        } else {
+            return None;
+        };
+
+        Some(essence(media_type))
+    }
+}
+
+/// Builds a media type from a known valid essence.
+fn essence(media_type: &str) -> MediaType {
+    media_type.parse().unwrap()
+}
+
+impl FromStr for MediaType {
+    type Err = ParseMediaTypeError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        let mut chars = str.trim().chars().peekable();
+
+        let type_name = parse_token(&mut chars)?;
+        if chars.next() != Some('/') {
+            return Err(ParseMediaTypeError);
+        }
+        let subtype = parse_token(&mut chars)?;
+
+        let mut params = vec![];
+
+        loop {
+            skip_whitespace(&mut chars);
+
+            match chars.next() {
+                Some(';') => skip_whitespace(&mut chars),
+                Some(_) => return Err(ParseMediaTypeError),
+                None => break,
+            }
+
+            // allows empty parameters, e.g. `text/plain;`
+            if matches!(chars.peek(), None | Some(';')) {
+                continue;
+            }
+
+            let name = parse_token(&mut chars)?;
+            if chars.next() != Some('=') {
+                return Err(ParseMediaTypeError);
+            }
+
+            let value = if chars.peek() == Some(&'"') {
+                chars.next();
+                parse_quoted(&mut chars)?
+            } else {
+                parse_token(&mut chars)?
+            };
+
+            params.push((name.to_ascii_lowercase(), value));
+        }
+
+        Ok(MediaType {
+            type_name: type_name.to_ascii_lowercase(),
+            subtype: subtype.to_ascii_lowercase(),
+            params,
+        })
+    }
+}
+
+type Chars<'a> = core::iter::Peekable<core::str::Chars<'a>>;
+
+fn is_token_char(char: char) -> bool {
+    char.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(char)
+}
+
+fn parse_token(chars: &mut Chars) -> Result<String, ParseMediaTypeError> {
+    let mut token = String::new();
+
+    while let Some(&char) = chars.peek() {
+        if !is_token_char(char) {
+            break;
+        }
+
+        token.push(char);
+        chars.next();
+    }
+
+    if token.is_empty() {
+        return Err(ParseMediaTypeError);
+    }
+
+    Ok(token)
+}
+
+/// Parses a quoted string after its opening quote.
+fn parse_quoted(chars: &mut Chars) -> Result<String, ParseMediaTypeError> {
+    let mut value = String::new();
+
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(value),
+            Some('\\') => match chars.next() {
+                Some(char) => value.push(char),
+                None => return Err(ParseMediaTypeError),
+            },
+            Some(char) => value.push(char),
+            None => return Err(ParseMediaTypeError),
+        }
+    }
+}
+
+fn skip_whitespace(chars: &mut Chars) {
+    while matches!(chars.peek(), Some(' ') | Some('\t')) {
+        chars.next();
+    }
+}
+
+impl fmt::Display for MediaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.type_name, self.subtype)?;
+
+        for (name, value) in &self.params {
+            if !value.is_empty() && value.chars().all(is_token_char) {
+                write!(f, "; {}={}", name, value)?;
+            } else {
+                write!(f, "; {}=\"", name)?;
+
+                for char in value.chars() {
+                    if char == '"' || char == '\\' {
+                        write!(f, "\\")?;
+                    }
+
+                    write!(f, "{}", char)?;
+                }
+
+                write!(f, "\"")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_new() {
+        let media_type =
+            MediaType::new(" Text/CSV ; Charset=UTF-8;header=\"present; yes\" ").unwrap();
+
+        assert_eq!(media_type.type_name(), "text");
+        assert_eq!(media_type.subtype(), "csv");
+        assert_eq!(media_type.essence(), "text/csv");
+        assert_eq!(media_type.param("charset"), Some("UTF-8"));
+        assert_eq!(media_type.param("header"), Some("present; yes"));
+        assert_eq!(
+            media_type.to_string(),
+            "text/csv; charset=UTF-8; header=\"present; yes\""
+        );
+        assert!(media_type.is_csv() && media_type.is_text());
+
+        assert!(MediaType::new("text/plain;").is_ok());
+        assert!(MediaType::new("").is_err());
+        assert!(MediaType::new("text").is_err());
+        assert!(MediaType::new("text/").is_err());
+        assert!(MediaType::new("text/plain charset=utf-8").is_err());
+        assert!(MediaType::new("text/plain; charset").is_err());
+        assert!(MediaType::new("text/plain; name=\"unterminated").is_err());
+    }
+
+    #[test]
+    fn test_helpers() {
+        let svg = MediaType::new("image/svg+xml").unwrap();
+        assert!(svg.is_image() && !svg.is_json());
+        assert_eq!(svg.suffix(), Some("xml"));
+        assert!(svg.matches("image/*") && svg.matches("*/*") && svg.matches("IMAGE/SVG+XML"));
+        assert!(!svg.matches("image/png") && !svg.matches("image"));
+
+        assert!(MediaType::new("application/ld+json").unwrap().is_json());
+        assert!(MediaType::new("application/PDF").unwrap().is_pdf());
+    }
+
+    #[test]
+    fn test_extensions() {
+        assert_eq!(
+            MediaType::from_extension(".JPG"),
+            MediaType::new("image/jpeg").ok()
+        );
+        assert_eq!(
+            MediaType::from_filename("report.2024.xlsx").map(|media_type| media_type.essence()),
+            Some("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet".to_string())
+        );
+        assert_eq!(MediaType::from_filename(".gitignore"), None);
+        assert_eq!(MediaType::from_filename("README"), None);
+
+        assert_eq!(
+            MediaType::new("image/jpeg; q=1").unwrap().extension(),
+            Some("jpg")
+        );
+        assert_eq!(
+            MediaType::new("application/x-unknown").unwrap().extension(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sniff() {
+        let sniff = |bytes: &[u8]| MediaType::sniff(bytes).map(|media_type| media_type.essence());
+
+        assert_eq!(
+            sniff(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+            Some("image/png".to_string())
+        );
+        assert_eq!(sniff(b"\xff\xd8\xff\xe0"), Some("image/jpeg".to_string()));
+        assert_eq!(
+            sniff(b"RIFF\0\0\0\0WEBPVP8 "),
+            Some("image/webp".to_string())
+        );
+        assert_eq!(sniff(b"%PDF-1.7\n"), Some("application/pdf".to_string()));
+        assert_eq!(
+            sniff(b"PK\x03\x04\x14\0"),
+            Some("application/zip".to_string())
+        );
+        assert_eq!(sniff(b"\0\0\0\x18ftypmp42"), Some("video/mp4".to_string()));
+        assert_eq!(sniff(b"id,name\n"), None);
+        assert_eq!(sniff(b""), None);
+    }
+
+    #[test]
+    fn test_json() {
+        let media_type = MediaType::from_json(&json!("text/plain; charset=utf-8")).unwrap();
+        assert_eq!(media_type.to_json(), json!("text/plain; charset=utf-8"));
+        assert!(MediaType::from_json(&json!(1)).is_err());
+    }
+}
@@ -1,9 +1,14 @@
 pub mod json_codec;
 
 pub mod cascader_value;
+pub mod date_time_pattern;
 pub mod file_object;
+pub mod media_type;
+pub mod naive_date;
 pub mod naive_date_time;
+pub mod naive_time;
 pub mod number;
+pub mod offset_date_time;
 pub mod options_value;
 pub mod prosemirror;
 pub mod relation_value;
@@ -0,0 +1,122 @@
+use core::convert::TryFrom;
+
+use serde_json::Value;
+use time::{macros::format_description, Date, Month};
+
+use super::{json_codec::JsonCodec, naive_date_time::InvalidNaiveDateTimeError};
+
+#[derive(Debug)]
+pub struct ParseNaiveDateError;
+
+/// A calendar date without a time, e.g. `2022-04-29`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NaiveDate {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl NaiveDate {
+    /// Creates a value without validating it, see `try_new`.
+    pub fn new(year: i32, month: u8, day: u8) -> Self {
+        Self { year, month, day }
+    }
+
+    /// Creates a value, failing if the date is invalid.
+    pub fn try_new(year: i32, month: u8, day: u8) -> Result<Self, InvalidNaiveDateTimeError> {
+        let date = NaiveDate::new(year, month, day);
+
+        Date::try_from(&date).map(|_date| date)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        Date::try_from(self).is_ok()
+    }
+
+    /// Returns the `time` representation, `None` if the date is invalid.
+    pub fn to_date(&self) -> Option<Date> {
+        Date::try_from(self).ok()
+    }
+}
+
+impl TryFrom<&NaiveDate> for Date {
+    type Error = InvalidNaiveDateTimeError;
+
+    fn try_from(date: &NaiveDate) -> Result<Self, Self::Error> {
+        Ok(Date::from_calendar_date(
+            date.year,
+            Month::try_from(date.month)?,
+            date.day,
+        )?)
+    }
+}
+
+impl From<Date> for NaiveDate {
+    fn from(date: Date) -> Self {
+        NaiveDate {
+            year: date.year(),
+            month: date.month() as u8,
+            day: date.day(),
+        }
+    }
+}
+
+impl JsonCodec for NaiveDate {
+    type Err = ParseNaiveDateError;
+
+    fn from_json(value: &Value) -> Result<Self, Self::Err> {
+        match value.as_str() {
+            Some(str) => match Date::parse(str, format_description!("[year]-[month]-[day]")) {
+                Ok(date) => Ok(NaiveDate::from(date)),
+                Err(_err) => Err(ParseNaiveDateError),
+            },
+            None => Err(ParseNaiveDateError),
+        }
+    }
+
+    /// Encodes an invalid date as `null`.
+    fn to_json(&self) -> Value {
+        self.to_date()
+            .and_then(|date| {
+                date.format(format_description!("[year]-[month]-[day]"))
+                    .ok()
+            })
+            .map(Value::String)
+            .unwrap_or(Value::Null)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_json() {
+        assert_eq!(
+            NaiveDate::from_json(&json!("2022-04-29")).unwrap(),
+            NaiveDate::new(2022, 4, 29)
+        );
+        assert!(NaiveDate::from_json(&json!("2022-04-29T07:34:10")).is_err());
+        assert!(NaiveDate::from_json(&json!("2022-02-30")).is_err());
+        assert!(NaiveDate::from_json(&json!(20220429)).is_err());
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(NaiveDate::new(2022, 4, 29).to_json(), json!("2022-04-29"));
+        assert_eq!(NaiveDate::new(2022, 2, 30).to_json(), Value::Null);
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(
+            NaiveDate::try_new(2024, 2, 29),
+            Ok(NaiveDate::new(2024, 2, 29))
+        );
+        assert_eq!(
+            NaiveDate::try_new(2023, 2, 29),
+            Err(InvalidNaiveDateTimeError { component: "day" })
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use core::convert::TryFrom;
+use core::{convert::TryFrom, fmt};
 
 use serde_json::Value;
 use time::{
@@ -6,12 +6,36 @@ use time::{
     PrimitiveDateTime, Time,
 };
 
-use super::json_codec::JsonCodec;
+pub use time::{Duration, Weekday};
+
+use super::{json_codec::JsonCodec, naive_date::NaiveDate, naive_time::NaiveTime};
 
 #[derive(Debug)]
 pub struct ParseNaiveDateTimeError;
 
+/// A component of a `NaiveDateTime` is out of range, e.g. month 13 or
+/// February 30.
 #[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidNaiveDateTimeError {
+    /// The name of the invalid component, e.g. `"month"` or `"day"`.
+    pub component: &'static str,
+}
+
+impl fmt::Display for InvalidNaiveDateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {} in date time", self.component)
+    }
+}
+
+impl From<time::error::ComponentRange> for InvalidNaiveDateTimeError {
+    fn from(err: time::error::ComponentRange) -> Self {
+        InvalidNaiveDateTimeError {
+            component: err.name(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct NaiveDateTime {
     pub year: i32,
     pub month: u8,
@@ -23,6 +47,7 @@ pub struct NaiveDateTime {
 }
 
 impl NaiveDateTime {
+    /// Creates a value without validating it, see `try_new`.
     pub fn new(
         year: i32,
         month: u8,
@@ -42,6 +67,209 @@ impl NaiveDateTime {
             nanosecond,
         }
     }
+
+    /// Creates a value, failing if the date or time is invalid.
+    pub fn try_new(
+        year: i32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        nanosecond: u32,
+    ) -> Result<Self, InvalidNaiveDateTimeError> {
+        let date_time = NaiveDateTime::new(year, month, day, hour, minute, second, nanosecond);
+
+        PrimitiveDateTime::try_from(&date_time).map(|_pdt| date_time)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        PrimitiveDateTime::try_from(self).is_ok()
+    }
+
+    /// Returns the `time` representation, `None` if the date or time is invalid.
+    pub fn to_primitive_date_time(&self) -> Option<PrimitiveDateTime> {
+        PrimitiveDateTime::try_from(self).ok()
+    }
+
+    pub fn from_date_and_time(date: &NaiveDate, time: &NaiveTime) -> Self {
+        NaiveDateTime::new(
+            date.year,
+            date.month,
+            date.day,
+            time.hour,
+            time.minute,
+            time.second,
+            time.nanosecond,
+        )
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        NaiveDate::new(self.year, self.month, self.day)
+    }
+
+    pub fn time(&self) -> NaiveTime {
+        NaiveTime::new(self.hour, self.minute, self.second, self.nanosecond)
+    }
+}
+
+/// Calendar helpers. Each returns `None` if `self` is not a valid date and time
+/// or the result is out of range.
+impl NaiveDateTime {
+    pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+        self.to_primitive_date_time()?
+            .checked_add(duration)
+            .map(NaiveDateTime::from)
+    }
+
+    pub fn checked_sub(&self, duration: Duration) -> Option<Self> {
+        self.to_primitive_date_time()?
+            .checked_sub(duration)
+            .map(NaiveDateTime::from)
+    }
+
+    /// Returns `self - other`, negative if `other` is later.
+    pub fn duration_since(&self, other: &NaiveDateTime) -> Option<Duration> {
+        Some(self.to_primitive_date_time()? - other.to_primitive_date_time()?)
+    }
+
+    pub fn weekday(&self) -> Option<Weekday> {
+        Some(self.to_date()?.weekday())
+    }
+
+    /// Returns the ISO 8601 week-numbering year and week, e.g. `2021-01-01` is
+    /// in week 53 of 2020.
+    pub fn iso_week(&self) -> Option<(i32, u8)> {
+        let (year, week, _weekday) = self.to_date()?.to_iso_week_date();
+
+        Some((year, week))
+    }
+
+    pub fn start_of_day(&self) -> Option<Self> {
+        Some(start_of(self.to_date()?))
+    }
+
+    pub fn end_of_day(&self) -> Option<Self> {
+        Some(end_of(self.to_date()?))
+    }
+
+    /// Returns the start of the week, weeks starting on Monday.
+    pub fn start_of_week(&self) -> Option<Self> {
+        Some(start_of(monday_of(self.to_date()?)?))
+    }
+
+    /// Returns the end of the week, weeks ending on Sunday.
+    pub fn end_of_week(&self) -> Option<Self> {
+        let sunday = monday_of(self.to_date()?)?.checked_add(Duration::days(6))?;
+
+        Some(end_of(sunday))
+    }
+
+    pub fn start_of_month(&self) -> Option<Self> {
+        Some(start_of(self.to_date()?.replace_day(1).ok()?))
+    }
+
+    pub fn end_of_month(&self) -> Option<Self> {
+        let date = self.to_date()?;
+        let last_day = date.month().length(date.year());
+
+        Some(end_of(date.replace_day(last_day).ok()?))
+    }
+
+    /// Whether the date is a business day, i.e. Monday to Friday. Holidays are
+    /// not taken into account.
+    pub fn is_business_day(&self) -> Option<bool> {
+        Some(is_business_day(self.to_date()?))
+    }
+
+    /// Moves `days` business days forwards, or backwards if `days` is negative,
+    /// keeping the time of day. Starting on a weekend, the first business day
+    /// reached counts as one.
+    pub fn add_business_days(&self, days: i64) -> Option<Self> {
+        let pdt = self.to_primitive_date_time()?;
+        let step = Duration::days(days.signum());
+        let mut date = pdt.date();
+
+        // Move onto the business day a weekend start behaves like, so whole
+        // weeks can be skipped below.
+        while days != 0 && !is_business_day(date) {
+            date = date.checked_sub(step)?;
+        }
+
+        let weeks = Duration::seconds((days / 5).checked_mul(7 * 24 * 60 * 60)?);
+        date = date.checked_add(weeks)?;
+
+        for _ in 0..(days % 5).abs() {
+            date = date.checked_add(step)?;
+
+            while !is_business_day(date) {
+                date = date.checked_add(step)?;
+            }
+        }
+
+        Some(NaiveDateTime::from(PrimitiveDateTime::new(
+            date,
+            pdt.time(),
+        )))
+    }
+
+    /// Counts the business days from the date of `self` up to, but excluding,
+    /// the date of `other`. Negative if `other` is earlier.
+    pub fn business_days_until(&self, other: &NaiveDateTime) -> Option<i64> {
+        Some(business_days_before(other.to_date()?) - business_days_before(self.to_date()?))
+    }
+
+    fn to_date(&self) -> Option<Date> {
+        Some(self.to_primitive_date_time()?.date())
+    }
+}
+
+impl From<Date> for NaiveDateTime {
+    fn from(date: Date) -> Self {
+        start_of(date)
+    }
+}
+
+impl TryFrom<&NaiveDateTime> for PrimitiveDateTime {
+    type Error = InvalidNaiveDateTimeError;
+
+    fn try_from(date_time: &NaiveDateTime) -> Result<Self, Self::Error> {
+        let date = Date::from_calendar_date(
+            date_time.year,
+            Month::try_from(date_time.month)?,
+            date_time.day,
+        )?;
+        let time = Time::from_hms_nano(
+            date_time.hour,
+            date_time.minute,
+            date_time.second,
+            date_time.nanosecond,
+        )?;
+
+        Ok(PrimitiveDateTime::new(date, time))
+    }
+}
+
+impl TryFrom<NaiveDateTime> for PrimitiveDateTime {
+    type Error = InvalidNaiveDateTimeError;
+
+    fn try_from(date_time: NaiveDateTime) -> Result<Self, Self::Error> {
+        PrimitiveDateTime::try_from(&date_time)
+    }
+}
+
+impl From<PrimitiveDateTime> for NaiveDateTime {
+    fn from(pdt: PrimitiveDateTime) -> Self {
+        NaiveDateTime {
+            year: pdt.year(),
+            month: pdt.month() as u8,
+            day: pdt.day(),
+            hour: pdt.hour(),
+            minute: pdt.minute(),
+            second: pdt.second(),
+            nanosecond: pdt.nanosecond(),
+        }
+    }
 }
 
 impl JsonCodec for NaiveDateTime {
@@ -53,15 +281,7 @@ impl JsonCodec for NaiveDateTime {
                 let str = normalize(str);
 
                 match PrimitiveDateTime::parse(&str, &Rfc3339) {
-                    Ok(pdt) => Ok(NaiveDateTime {
-                        year: pdt.year(),
-                        month: pdt.month() as u8,
-                        day: pdt.day(),
-                        hour: pdt.hour(),
-                        minute: pdt.minute(),
-                        second: pdt.second(),
-                        nanosecond: pdt.nanosecond(),
-                    }),
+                    Ok(pdt) => Ok(NaiveDateTime::from(pdt)),
                     Err(_err) => Err(ParseNaiveDateTimeError),
                 }
             }
@@ -69,14 +289,12 @@ impl JsonCodec for NaiveDateTime {
         }
     }
 
+    /// Encodes an invalid date or time as `null`.
     fn to_json(&self) -> Value {
-        let date =
-            Date::from_calendar_date(self.year, Month::try_from(self.month).unwrap(), self.day)
-                .unwrap();
-        let time =
-            Time::from_hms_nano(self.hour, self.minute, self.second, self.nanosecond).unwrap();
-
-        let pdt = PrimitiveDateTime::new(date, time);
+        let pdt = match self.to_primitive_date_time() {
+            Some(pdt) => pdt,
+            None => return Value::Null,
+        };
 
         let format = if pdt.nanosecond() == 0 {
             format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]")
@@ -86,10 +304,46 @@ impl JsonCodec for NaiveDateTime {
             )
         };
 
-        Value::String(pdt.format(&format).unwrap())
+        pdt.format(&format)
+            .map(Value::String)
+            .unwrap_or(Value::Null)
     }
 }
 
+fn start_of(date: Date) -> NaiveDateTime {
+    NaiveDateTime::from(date.midnight())
+}
+
+fn end_of(date: Date) -> NaiveDateTime {
+    NaiveDateTime::new(
+        date.year(),
+        date.month() as u8,
+        date.day(),
+        23,
+        59,
+        59,
+        999_999_999,
+    )
+}
+
+fn monday_of(date: Date) -> Option<Date> {
+    date.checked_sub(Duration::days(
+        date.weekday().number_days_from_monday() as i64
+    ))
+}
+
+fn is_business_day(date: Date) -> bool {
+    !matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday)
+}
+
+/// Counts the business days before `date`, starting from Julian day 0, which
+/// is a Monday.
+fn business_days_before(date: Date) -> i64 {
+    let julian_day = date.to_julian_day() as i64;
+
+    julian_day.div_euclid(7) * 5 + julian_day.rem_euclid(7).min(5)
+}
+
 fn normalize(str: &str) -> String {
     if str.ends_with('Z') {
         str.to_string()
@@ -152,7 +406,7 @@ mod tests {
             let json = json!("2022-04-29 07:34");
             let pdt = NaiveDateTime::from_json(&json);
 
-            assert!(matches!(pdt, Err(_)));
+            assert!(pdt.is_err());
         }
     }
 
@@ -172,5 +426,165 @@ mod tests {
 
             assert_eq!(pdt.unwrap().to_json(), json);
         }
+
+        // invalid date
+        {
+            let dt = NaiveDateTime::new(2022, 2, 30, 7, 34, 10, 0);
+
+            assert_eq!(dt.to_json(), Value::Null);
+        }
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(
+            NaiveDateTime::try_new(2024, 2, 29, 23, 59, 59, 999_999_999),
+            Ok(NaiveDateTime::new(2024, 2, 29, 23, 59, 59, 999_999_999))
+        );
+        assert_eq!(
+            NaiveDateTime::try_new(2022, 13, 1, 0, 0, 0, 0),
+            Err(InvalidNaiveDateTimeError { component: "month" })
+        );
+        assert_eq!(
+            NaiveDateTime::try_new(2022, 2, 30, 0, 0, 0, 0),
+            Err(InvalidNaiveDateTimeError { component: "day" })
+        );
+        assert_eq!(
+            NaiveDateTime::try_new(2022, 2, 28, 24, 0, 0, 0),
+            Err(InvalidNaiveDateTimeError { component: "hour" })
+        );
+        assert!(!NaiveDateTime::new(2022, 0, 1, 0, 0, 0, 0).is_valid());
+    }
+
+    #[test]
+    fn test_date_and_time() {
+        let dt = NaiveDateTime::new(2022, 4, 29, 7, 34, 10, 420159000);
+
+        assert_eq!(dt.date(), NaiveDate::new(2022, 4, 29));
+        assert_eq!(dt.time(), NaiveTime::new(7, 34, 10, 420159000));
+        assert_eq!(
+            NaiveDateTime::from_date_and_time(&dt.date(), &dt.time()),
+            dt
+        );
+    }
+
+    #[test]
+    fn test_try_from() {
+        let dt = NaiveDateTime::new(2022, 4, 29, 7, 34, 10, 420159000);
+        let pdt = PrimitiveDateTime::try_from(&dt).unwrap();
+
+        assert_eq!(NaiveDateTime::from(pdt), dt);
+        assert_eq!(
+            PrimitiveDateTime::try_from(NaiveDateTime::new(2022, 4, 31, 0, 0, 0, 0)),
+            Err(InvalidNaiveDateTimeError { component: "day" })
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let dt = NaiveDateTime::new(2022, 4, 29, 7, 34, 10, 0);
+
+        assert_eq!(
+            dt.checked_add(Duration::hours(20)),
+            Some(NaiveDateTime::new(2022, 4, 30, 3, 34, 10, 0))
+        );
+        assert_eq!(
+            dt.checked_sub(Duration::days(60)),
+            Some(NaiveDateTime::new(2022, 2, 28, 7, 34, 10, 0))
+        );
+        assert_eq!(
+            NaiveDateTime::new(2022, 5, 1, 0, 0, 0, 0).duration_since(&dt),
+            Some(Duration::seconds(40 * 3600 + 25 * 60 + 50))
+        );
+        assert!(dt < NaiveDateTime::new(2022, 4, 29, 7, 34, 11, 0));
+
+        // invalid date
+        let invalid = NaiveDateTime::new(2022, 2, 30, 0, 0, 0, 0);
+
+        assert_eq!(invalid.checked_add(Duration::days(1)), None);
+        assert_eq!(invalid.duration_since(&dt), None);
+    }
+
+    #[test]
+    fn test_calendar() {
+        // Friday
+        let dt = NaiveDateTime::new(2022, 4, 29, 7, 34, 10, 420159000);
+
+        assert_eq!(dt.weekday(), Some(Weekday::Friday));
+        assert_eq!(dt.iso_week(), Some((2022, 17)));
+        assert_eq!(
+            NaiveDateTime::new(2021, 1, 1, 0, 0, 0, 0).iso_week(),
+            Some((2020, 53))
+        );
+        assert_eq!(
+            dt.start_of_day(),
+            Some(NaiveDateTime::new(2022, 4, 29, 0, 0, 0, 0))
+        );
+        assert_eq!(
+            dt.end_of_day(),
+            Some(NaiveDateTime::new(2022, 4, 29, 23, 59, 59, 999_999_999))
+        );
+        assert_eq!(
+            dt.start_of_week(),
+            Some(NaiveDateTime::new(2022, 4, 25, 0, 0, 0, 0))
+        );
+        assert_eq!(
+            dt.end_of_week(),
+            Some(NaiveDateTime::new(2022, 5, 1, 23, 59, 59, 999_999_999))
+        );
+        assert_eq!(
+            dt.start_of_month(),
+            Some(NaiveDateTime::new(2022, 4, 1, 0, 0, 0, 0))
+        );
+        assert_eq!(
+            NaiveDateTime::new(2024, 2, 10, 0, 0, 0, 0).end_of_month(),
+            Some(NaiveDateTime::new(2024, 2, 29, 23, 59, 59, 999_999_999))
+        );
+    }
+
+    #[test]
+    fn test_business_days() {
+        // Friday
+        let friday = NaiveDateTime::new(2022, 4, 29, 7, 34, 10, 0);
+        let saturday = NaiveDateTime::new(2022, 4, 30, 7, 34, 10, 0);
+
+        assert_eq!(friday.is_business_day(), Some(true));
+        assert_eq!(saturday.is_business_day(), Some(false));
+
+        assert_eq!(friday.add_business_days(0), Some(friday.clone()));
+        assert_eq!(
+            friday.add_business_days(1),
+            Some(NaiveDateTime::new(2022, 5, 2, 7, 34, 10, 0))
+        );
+        assert_eq!(
+            friday.add_business_days(11),
+            Some(NaiveDateTime::new(2022, 5, 16, 7, 34, 10, 0))
+        );
+        assert_eq!(
+            friday.add_business_days(-5),
+            Some(NaiveDateTime::new(2022, 4, 22, 7, 34, 10, 0))
+        );
+        assert_eq!(
+            saturday.add_business_days(1),
+            Some(NaiveDateTime::new(2022, 5, 2, 7, 34, 10, 0))
+        );
+        assert_eq!(
+            saturday.add_business_days(5),
+            Some(NaiveDateTime::new(2022, 5, 6, 7, 34, 10, 0))
+        );
+        assert_eq!(
+            saturday.add_business_days(-1),
+            Some(NaiveDateTime::new(2022, 4, 29, 7, 34, 10, 0))
+        );
+        assert_eq!(friday.add_business_days(i64::MAX), None);
+        assert_eq!(friday.add_business_days(i64::MIN), None);
+        assert_eq!(friday.add_business_days(1_000_000_000), None);
+
+        let next_friday = NaiveDateTime::new(2022, 5, 6, 0, 0, 0, 0);
+
+        assert_eq!(friday.business_days_until(&next_friday), Some(5));
+        assert_eq!(saturday.business_days_until(&next_friday), Some(4));
+        assert_eq!(next_friday.business_days_until(&friday), Some(-5));
+        assert_eq!(friday.business_days_until(&friday), Some(0));
     }
 }
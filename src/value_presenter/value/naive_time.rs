@@ -0,0 +1,154 @@
+use core::convert::TryFrom;
+
+use serde_json::Value;
+use time::{macros::format_description, Time};
+
+use super::{json_codec::JsonCodec, naive_date_time::InvalidNaiveDateTimeError};
+
+#[derive(Debug)]
+pub struct ParseNaiveTimeError;
+
+/// A time of day without a date, e.g. `07:34:10`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NaiveTime {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub nanosecond: u32,
+}
+
+impl NaiveTime {
+    /// Creates a value without validating it, see `try_new`.
+    pub fn new(hour: u8, minute: u8, second: u8, nanosecond: u32) -> Self {
+        Self {
+            hour,
+            minute,
+            second,
+            nanosecond,
+        }
+    }
+
+    /// Creates a value, failing if the time is invalid.
+    pub fn try_new(
+        hour: u8,
+        minute: u8,
+        second: u8,
+        nanosecond: u32,
+    ) -> Result<Self, InvalidNaiveDateTimeError> {
+        let time = NaiveTime::new(hour, minute, second, nanosecond);
+
+        Time::try_from(&time).map(|_time| time)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        Time::try_from(self).is_ok()
+    }
+
+    /// Returns the `time` representation, `None` if the time is invalid.
+    pub fn to_time(&self) -> Option<Time> {
+        Time::try_from(self).ok()
+    }
+}
+
+impl TryFrom<&NaiveTime> for Time {
+    type Error = InvalidNaiveDateTimeError;
+
+    fn try_from(time: &NaiveTime) -> Result<Self, Self::Error> {
+        Ok(Time::from_hms_nano(
+            time.hour,
+            time.minute,
+            time.second,
+            time.nanosecond,
+        )?)
+    }
+}
+
+impl From<Time> for NaiveTime {
+    fn from(time: Time) -> Self {
+        NaiveTime {
+            hour: time.hour(),
+            minute: time.minute(),
+            second: time.second(),
+            nanosecond: time.nanosecond(),
+        }
+    }
+}
+
+impl JsonCodec for NaiveTime {
+    type Err = ParseNaiveTimeError;
+
+    fn from_json(value: &Value) -> Result<Self, Self::Err> {
+        match value.as_str() {
+            Some(str) => match Time::parse(
+                str,
+                format_description!("[hour]:[minute]:[second][optional [.[subsecond]]]"),
+            ) {
+                Ok(time) => Ok(NaiveTime::from(time)),
+                Err(_err) => Err(ParseNaiveTimeError),
+            },
+            None => Err(ParseNaiveTimeError),
+        }
+    }
+
+    /// Encodes an invalid time as `null`.
+    fn to_json(&self) -> Value {
+        let time = match self.to_time() {
+            Some(time) => time,
+            None => return Value::Null,
+        };
+
+        let format = if time.nanosecond() == 0 {
+            format_description!("[hour]:[minute]:[second]")
+        } else {
+            format_description!("[hour]:[minute]:[second].[subsecond digits:1+]")
+        };
+
+        time.format(&format)
+            .map(Value::String)
+            .unwrap_or(Value::Null)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_json() {
+        assert_eq!(
+            NaiveTime::from_json(&json!("07:34:10")).unwrap(),
+            NaiveTime::new(7, 34, 10, 0)
+        );
+        assert_eq!(
+            NaiveTime::from_json(&json!("07:34:10.420159")).unwrap(),
+            NaiveTime::new(7, 34, 10, 420159000)
+        );
+        assert!(NaiveTime::from_json(&json!("24:00:00")).is_err());
+        assert!(NaiveTime::from_json(&json!("2022-04-29T07:34:10")).is_err());
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(NaiveTime::new(7, 34, 10, 0).to_json(), json!("07:34:10"));
+        assert_eq!(
+            NaiveTime::new(7, 34, 10, 420159000).to_json(),
+            json!("07:34:10.420159")
+        );
+        assert_eq!(NaiveTime::new(7, 60, 0, 0).to_json(), Value::Null);
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(
+            NaiveTime::try_new(23, 59, 59, 999_999_999),
+            Ok(NaiveTime::new(23, 59, 59, 999_999_999))
+        );
+        assert_eq!(
+            NaiveTime::try_new(7, 34, 60, 0),
+            Err(InvalidNaiveDateTimeError {
+                component: "second"
+            })
+        );
+    }
+}
@@ -1,11 +1,38 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+pub use bigdecimal::{BigDecimal, RoundingMode};
+use bigdecimal::{ToPrimitive, Zero};
 use serde_json::{Number as JsonNumber, Value};
 
 use super::json_codec::JsonCodec;
 
-#[derive(Debug, Clone, PartialEq)]
+/// A numeric field value.
+///
+/// Integers that fit in an `i64` are held as `Integer`, and numbers whose text
+/// is exactly how an `f64` is written back are held as `Float`. Any other
+/// number, e.g. an integer above `i64::MAX`, a fraction with more significant
+/// digits than an `f64` keeps or one with trailing zeros like `19.90`, is held
+/// as `Decimal` so that its digits round-trip unchanged.
+///
+/// A `Decimal` is written in plain notation, or as `<digits>e+<exponent>` when
+/// it has fewer digits than its integer part, e.g. `2.50E3` is written as
+/// `250e+1`.
+///
+/// Numbers compare by value regardless of the variant, e.g.
+/// `Number::Integer(1) == Number::Float(1.0)`.
+///
+/// JSON has no representation for a NaN or infinite `Float`, `to_json` encodes
+/// it as `null`; see `NonFiniteNumberPolicy` for handling it in outputs.
+#[derive(Debug, Clone)]
 pub enum Number {
     Integer(i64),
     Float(f64),
+    Decimal(BigDecimal),
 }
 
 #[derive(Debug)]
@@ -15,26 +42,453 @@ impl JsonCodec for Number {
     type Err = ParseNumberError;
 
     fn from_json(value: &Value) -> Result<Self, Self::Err> {
-        match value.as_u64() {
-            Some(number) => Ok(Number::Integer(number as i64)),
-            None => match value.as_i64() {
-                Some(number) => Ok(Number::Integer(number)),
-                None => match value.as_f64() {
-                    Some(number) => Ok(Number::Float(number)),
-                    None => Err(ParseNumberError),
-                },
-            },
+        match value {
+            Value::Number(number) => Number::parse_str(number.as_str()),
+            _ => Err(ParseNumberError),
         }
     }
 
     fn to_json(&self) -> Value {
         match self {
             Number::Integer(i) => Value::Number(JsonNumber::from(*i)),
-            Number::Float(f) => Value::Number(JsonNumber::from_f64(*f).unwrap()),
+            Number::Float(f) => match JsonNumber::from_f64(*f) {
+                Some(number) => Value::Number(number),
+                None => Value::Null,
+            },
+            // `decimal_to_string` only writes digits, a sign, a point and an
+            // exponent, which always parse as a JSON number
+            Number::Decimal(d) => {
+                JsonNumber::from_str(&decimal_to_string(d)).map_or(Value::Null, Value::Number)
+            }
         }
     }
 }
 
+impl Number {
+    /// Parses the textual form of a number, keeping it exact: see `Number` for
+    /// how the variant is chosen.
+    pub fn parse_str(s: &str) -> Result<Self, ParseNumberError> {
+        let s = s.trim();
+
+        if let Ok(i) = s.parse::<i64>() {
+            return Ok(Number::Integer(i));
+        }
+
+        let decimal = BigDecimal::from_str(s).map_err(|_err| ParseNumberError)?;
+
+        if !s.contains(['.', 'e', 'E']) {
+            return Ok(Number::Decimal(decimal));
+        }
+
+        // a float is written back in its shortest form, so text in any other
+        // form, e.g. with trailing zeros, is kept as a decimal
+        match s.parse::<f64>() {
+            Ok(f) if matches!(JsonNumber::from_f64(f), Some(number) if number.to_string() == s) => {
+                Ok(Number::Float(f))
+            }
+            _ => Ok(Number::Decimal(decimal)),
+        }
+    }
+
+    /// Returns the exact decimal value, `None` for a non-finite float.
+    ///
+    /// A float converts from its shortest textual form, so `0.1` becomes the
+    /// decimal `0.1` rather than the binary approximation of it.
+    pub fn to_decimal(&self) -> Option<BigDecimal> {
+        match self {
+            Number::Integer(i) => Some(BigDecimal::from(*i)),
+            Number::Float(f) => float_to_decimal(*f),
+            Number::Decimal(d) => Some(d.clone()),
+        }
+    }
+
+    /// Returns the number as an `f64`, which may lose precision for a
+    /// `Decimal`. Returns `None` if it is out of the `f64` range.
+    pub fn to_f64(&self) -> Option<f64> {
+        match self {
+            Number::Integer(i) => Some(*i as f64),
+            Number::Float(f) => Some(*f),
+            Number::Decimal(d) => d.to_f64().filter(|f| f.is_finite()),
+        }
+    }
+
+    /// Adds in decimal arithmetic, without going through `f64`.
+    pub fn add_exact(&self, other: &Number) -> Option<Number> {
+        Some(Number::from_decimal(
+            self.to_decimal()? + other.to_decimal()?,
+        ))
+    }
+
+    /// Subtracts in decimal arithmetic, without going through `f64`.
+    pub fn sub_exact(&self, other: &Number) -> Option<Number> {
+        Some(Number::from_decimal(
+            self.to_decimal()? - other.to_decimal()?,
+        ))
+    }
+
+    /// Multiplies in decimal arithmetic, without going through `f64`.
+    pub fn mul_exact(&self, other: &Number) -> Option<Number> {
+        Some(Number::from_decimal(
+            self.to_decimal()? * other.to_decimal()?,
+        ))
+    }
+
+    /// Divides in decimal arithmetic, without going through `f64`. A quotient
+    /// that does not terminate is rounded to 100 significant digits.
+    ///
+    /// Returns `None` when dividing by zero.
+    pub fn div_exact(&self, other: &Number) -> Option<Number> {
+        let divisor = other.to_decimal()?;
+
+        if divisor.is_zero() {
+            return None;
+        }
+
+        Some(Number::from_decimal(self.to_decimal()? / divisor))
+    }
+
+    /// Converts a decimal to an `Integer` when it is integral and fits in an
+    /// `i64`, otherwise keeps it as a `Decimal`.
+    pub fn from_decimal(decimal: BigDecimal) -> Number {
+        if decimal.is_integer() {
+            if let Some(i) = decimal.to_i64() {
+                return Number::Integer(i);
+            }
+        }
+
+        Number::Decimal(decimal)
+    }
+}
+
+/// Controls how `Number::format` renders a number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// The number of fractional digits to render, padding with zeros or
+    /// rounding with `rounding_mode` as needed. `None` renders every digit.
+    pub decimals: Option<u32>,
+    pub rounding_mode: RoundingMode,
+    /// Separator inserted between groups of three integer digits.
+    pub thousands_separator: Option<char>,
+    pub decimal_separator: char,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat {
+            decimals: None,
+            rounding_mode: RoundingMode::HalfUp,
+            thousands_separator: None,
+            decimal_separator: '.',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Operator {
+    fn apply_integer(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Operator::Add => a.checked_add(b),
+            Operator::Sub => a.checked_sub(b),
+            Operator::Mul => a.checked_mul(b),
+            Operator::Div => match a.checked_rem(b) {
+                Some(0) => a.checked_div(b),
+                _ => None,
+            },
+        }
+    }
+
+    fn apply_float(self, a: f64, b: f64) -> f64 {
+        match self {
+            Operator::Add => a + b,
+            Operator::Sub => a - b,
+            Operator::Mul => a * b,
+            Operator::Div => a / b,
+        }
+    }
+
+    fn apply_decimal(self, a: BigDecimal, b: BigDecimal) -> Option<BigDecimal> {
+        match self {
+            Operator::Add => Some(a + b),
+            Operator::Sub => Some(a - b),
+            Operator::Mul => Some(a * b),
+            Operator::Div if b.is_zero() => None,
+            Operator::Div => Some(a / b),
+        }
+    }
+}
+
+impl Number {
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Number::Integer(i) => *i == 0,
+            Number::Float(f) => *f == 0.0,
+            Number::Decimal(d) => d.is_zero(),
+        }
+    }
+
+    /// Returns `false` only for a `Float` that is infinite or NaN.
+    pub fn is_finite(&self) -> bool {
+        match self {
+            Number::Float(f) => f.is_finite(),
+            _ => true,
+        }
+    }
+
+    /// Adds two numbers. An `Integer` result that would overflow is promoted to
+    /// a `Float`. Returns `None` if the result is not finite.
+    pub fn checked_add(&self, rhs: &Number) -> Option<Number> {
+        self.apply(rhs, Operator::Add).into_finite()
+    }
+
+    /// Subtracts two numbers. An `Integer` result that would overflow is
+    /// promoted to a `Float`. Returns `None` if the result is not finite.
+    pub fn checked_sub(&self, rhs: &Number) -> Option<Number> {
+        self.apply(rhs, Operator::Sub).into_finite()
+    }
+
+    /// Multiplies two numbers. An `Integer` result that would overflow is
+    /// promoted to a `Float`. Returns `None` if the result is not finite.
+    pub fn checked_mul(&self, rhs: &Number) -> Option<Number> {
+        self.apply(rhs, Operator::Mul).into_finite()
+    }
+
+    /// Divides two numbers. Dividing integers gives an `Integer` when the
+    /// division is exact and a `Float` otherwise. Returns `None` when dividing
+    /// by zero or if the result is not finite.
+    pub fn checked_div(&self, rhs: &Number) -> Option<Number> {
+        if rhs.is_zero() {
+            return None;
+        }
+
+        self.apply(rhs, Operator::Div).into_finite()
+    }
+
+    /// Rounds to `decimals` fractional digits, keeping the variant.
+    ///
+    /// A `Float` is rounded from its shortest textual form, so `2.675` rounds
+    /// half up to `2.68` even though the nearest `f64` is slightly below it.
+    pub fn round(&self, decimals: u32, mode: RoundingMode) -> Number {
+        match self {
+            Number::Integer(_) => self.clone(),
+            Number::Float(f) => match float_to_decimal(*f) {
+                Some(d) => {
+                    let rounded = d.with_scale_round(decimals as i64, mode);
+
+                    match rounded.to_plain_string().parse::<f64>() {
+                        Ok(f) => Number::Float(f),
+                        Err(_err) => self.clone(),
+                    }
+                }
+                None => self.clone(),
+            },
+            Number::Decimal(d) => Number::Decimal(d.with_scale_round(decimals as i64, mode)),
+        }
+    }
+
+    /// Renders the number as text, e.g. `1,234,567.80`, for example to output
+    /// it as a `SingleLineField`. A non-finite `Float` renders as `NaN`, `inf`
+    /// or `-inf`.
+    pub fn format(&self, format: &NumberFormat) -> String {
+        let decimal = match self.to_decimal() {
+            Some(decimal) => decimal,
+            None => return self.to_string(),
+        };
+
+        let decimal = match format.decimals {
+            Some(decimals) => decimal.with_scale_round(decimals as i64, format.rounding_mode),
+            None => decimal,
+        };
+
+        let plain = decimal.to_plain_string();
+        let (sign, digits) = match plain.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", plain.as_str()),
+        };
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits, None),
+        };
+
+        let mut result = String::from(sign);
+
+        for (index, digit) in integer.chars().enumerate() {
+            if let Some(separator) = format.thousands_separator {
+                if index > 0 && (integer.len() - index) % 3 == 0 {
+                    result.push(separator);
+                }
+            }
+
+            result.push(digit);
+        }
+
+        if let Some(fraction) = fraction {
+            result.push(format.decimal_separator);
+            result.push_str(fraction);
+        }
+
+        result
+    }
+
+    fn apply(&self, rhs: &Number, operator: Operator) -> Number {
+        match (self, rhs) {
+            (Number::Integer(a), Number::Integer(b)) => match operator.apply_integer(*a, *b) {
+                Some(i) => Number::Integer(i),
+                None => Number::Float(operator.apply_float(*a as f64, *b as f64)),
+            },
+            (Number::Decimal(_), _) | (_, Number::Decimal(_)) => {
+                match (self.to_decimal(), rhs.to_decimal()) {
+                    (Some(a), Some(b)) => match operator.apply_decimal(a, b) {
+                        Some(d) => Number::from_decimal(d),
+                        None => self.apply_float(rhs, operator),
+                    },
+                    _ => self.apply_float(rhs, operator),
+                }
+            }
+            _ => self.apply_float(rhs, operator),
+        }
+    }
+
+    fn apply_float(&self, rhs: &Number, operator: Operator) -> Number {
+        Number::Float(operator.apply_float(self.as_f64_lossy(), rhs.as_f64_lossy()))
+    }
+
+    fn as_f64_lossy(&self) -> f64 {
+        match self {
+            Number::Integer(i) => *i as f64,
+            Number::Float(f) => *f,
+            Number::Decimal(d) => d.to_f64().unwrap_or(f64::NAN),
+        }
+    }
+
+    fn into_finite(self) -> Option<Number> {
+        if self.is_finite() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Number) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Number) -> Option<Ordering> {
+        match (self, other) {
+            (Number::Integer(a), Number::Integer(b)) => a.partial_cmp(b),
+            (Number::Float(a), Number::Float(b)) => a.partial_cmp(b),
+            _ => match (self.to_decimal(), other.to_decimal()) {
+                (Some(a), Some(b)) => a.partial_cmp(&b),
+                _ => self.as_f64_lossy().partial_cmp(&other.as_f64_lossy()),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Integer(i) => write!(f, "{}", i),
+            Number::Float(float) => write!(f, "{}", float),
+            Number::Decimal(d) => write!(f, "{}", d),
+        }
+    }
+}
+
+impl Neg for Number {
+    type Output = Number;
+
+    fn neg(self) -> Number {
+        -&self
+    }
+}
+
+impl Neg for &Number {
+    type Output = Number;
+
+    fn neg(self) -> Number {
+        match self {
+            Number::Integer(i) => match i.checked_neg() {
+                Some(i) => Number::Integer(i),
+                None => Number::Float(-(*i as f64)),
+            },
+            Number::Float(f) => Number::Float(-f),
+            Number::Decimal(d) => Number::Decimal(-d),
+        }
+    }
+}
+
+macro_rules! impl_binary_operator {
+    ($($trait:ident, $method:ident => $operator:ident;)*) => {
+        $(
+            impl $trait for Number {
+                type Output = Number;
+
+                fn $method(self, rhs: Number) -> Number {
+                    self.apply(&rhs, Operator::$operator)
+                }
+            }
+
+            impl<'a> $trait<&'a Number> for &'a Number {
+                type Output = Number;
+
+                fn $method(self, rhs: &'a Number) -> Number {
+                    self.apply(rhs, Operator::$operator)
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_operator! {
+    Add, add => Add;
+    Sub, sub => Sub;
+    Mul, mul => Mul;
+    Div, div => Div;
+}
+
+/// Writes `decimal` keeping its scale: trailing zeros of the fraction are
+/// written, and a negative scale is written as a positive exponent.
+fn decimal_to_string(decimal: &BigDecimal) -> String {
+    let (digits, scale) = decimal.as_bigint_and_exponent();
+    let digits = digits.to_string();
+    let (sign, digits) = match digits.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", digits.as_str()),
+    };
+
+    if scale <= 0 {
+        return match scale {
+            0 => format!("{}{}", sign, digits),
+            _ => format!("{}{}e+{}", sign, digits, -scale),
+        };
+    }
+
+    let scale = scale as usize;
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+
+    format!("{}{}.{}", sign, integer, fraction)
+}
+
+fn float_to_decimal(f: f64) -> Option<BigDecimal> {
+    if f.is_finite() {
+        BigDecimal::from_str(&f.to_string()).ok()
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,6 +522,271 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_json_decimal() {
+        // u64 above i64::MAX
+        {
+            let json: Value = serde_json::from_str("18446744073709551615").unwrap();
+
+            let number = Number::from_json(&json).unwrap();
+
+            assert_eq!(
+                number,
+                Number::Decimal(BigDecimal::from_str("18446744073709551615").unwrap())
+            );
+            assert_eq!(number.to_json().to_string(), "18446744073709551615");
+        }
+
+        // more significant digits than f64 keeps
+        {
+            let json: Value = serde_json::from_str("12345678901234567.89").unwrap();
+
+            let number = Number::from_json(&json).unwrap();
+
+            assert!(matches!(number, Number::Decimal(_)));
+            assert_eq!(number.to_json().to_string(), "12345678901234567.89");
+        }
+
+        // more fractional digits than f64 keeps
+        {
+            let json: Value = serde_json::from_str("19.990000000000000000001").unwrap();
+
+            let number = Number::from_json(&json).unwrap();
+
+            assert_eq!(number.to_json().to_string(), "19.990000000000000000001");
+        }
+
+        // text an f64 would write differently is kept
+        for (text, expected) in [
+            ("19.90", "19.90"),
+            ("-0.500", "-0.500"),
+            ("0.00000001", "0.00000001"),
+            ("1E+2", "1e+2"),
+            ("2.50E3", "250e+1"),
+            ("1e400", "1e+400"),
+        ] {
+            let json: Value = serde_json::from_str(text).unwrap();
+
+            let number = Number::from_json(&json).unwrap();
+
+            assert!(matches!(number, Number::Decimal(_)), "{}", text);
+            assert_eq!(number.to_json().to_string(), expected);
+        }
+
+        // text an f64 writes the same way is a float
+        for text in ["1.5", "-0.0", "1e-8", "1.5e-7"] {
+            let json: Value = serde_json::from_str(text).unwrap();
+
+            let number = Number::from_json(&json).unwrap();
+
+            assert!(matches!(number, Number::Float(_)), "{}", text);
+            assert_eq!(number.to_json().to_string(), text);
+        }
+
+        // not a number
+        {
+            assert!(Number::from_json(&Value::String("1".to_string())).is_err());
+        }
+    }
+
+    #[test]
+    fn test_exact_arithmetic() {
+        let a = Number::Float(0.1);
+        let b = Number::Float(0.2);
+
+        assert_eq!(
+            a.add_exact(&b),
+            Some(Number::Decimal(BigDecimal::from_str("0.3").unwrap()))
+        );
+        assert_eq!(
+            Number::Float(1.5).add_exact(&Number::Float(1.5)),
+            Some(Number::Integer(3))
+        );
+        assert_eq!(
+            Number::Integer(i64::MAX).add_exact(&Number::Integer(1)),
+            Some(Number::Decimal(
+                BigDecimal::from_str("9223372036854775808").unwrap()
+            ))
+        );
+        assert_eq!(
+            Number::Integer(3).sub_exact(&Number::Float(0.5)),
+            Some(Number::Decimal(BigDecimal::from_str("2.5").unwrap()))
+        );
+        assert_eq!(
+            Number::Float(19.99).mul_exact(&Number::Integer(3)),
+            Some(Number::Decimal(BigDecimal::from_str("59.97").unwrap()))
+        );
+        assert_eq!(
+            Number::Integer(1).div_exact(&Number::Integer(4)),
+            Some(Number::Decimal(BigDecimal::from_str("0.25").unwrap()))
+        );
+        assert_eq!(Number::Integer(1).div_exact(&Number::Integer(0)), None);
+        assert_eq!(Number::Float(f64::NAN).add_exact(&Number::Integer(1)), None);
+    }
+
+    #[test]
+    fn test_operators() {
+        assert_eq!(Number::Integer(1) + Number::Integer(2), Number::Integer(3));
+        assert!(matches!(
+            Number::Integer(1) + Number::Float(0.5),
+            Number::Float(f) if f == 1.5
+        ));
+        assert!(matches!(
+            Number::Integer(i64::MAX) + Number::Integer(1),
+            Number::Float(f) if f == i64::MAX as f64 + 1.0
+        ));
+        assert_eq!(Number::Integer(5) - Number::Integer(7), Number::Integer(-2));
+        assert!(matches!(
+            Number::Integer(i64::MAX) * Number::Integer(2),
+            Number::Float(_)
+        ));
+        assert!(matches!(
+            Number::Integer(6) / Number::Integer(3),
+            Number::Integer(2)
+        ));
+        assert!(matches!(
+            Number::Integer(1) / Number::Integer(2),
+            Number::Float(f) if f == 0.5
+        ));
+        assert!(matches!(
+            Number::Integer(1) / Number::Integer(0),
+            Number::Float(f) if f.is_infinite()
+        ));
+        assert!(matches!(
+            Number::Decimal(BigDecimal::from_str("0.1").unwrap()) + Number::Float(0.2),
+            Number::Decimal(_)
+        ));
+        assert_eq!(
+            &Number::Decimal(BigDecimal::from_str("0.1").unwrap()) + &Number::Float(0.2),
+            Number::Float(0.3)
+        );
+        assert!(matches!(-Number::Integer(1), Number::Integer(-1)));
+        assert!(matches!(-Number::Integer(i64::MIN), Number::Float(_)));
+    }
+
+    #[test]
+    fn test_checked_operators() {
+        assert_eq!(
+            Number::Integer(1).checked_add(&Number::Integer(2)),
+            Some(Number::Integer(3))
+        );
+        assert!(matches!(
+            Number::Integer(i64::MIN).checked_sub(&Number::Integer(1)),
+            Some(Number::Float(_))
+        ));
+        assert_eq!(
+            Number::Float(f64::MAX).checked_mul(&Number::Integer(2)),
+            None
+        );
+        assert_eq!(Number::Integer(1).checked_div(&Number::Integer(0)), None);
+        assert_eq!(Number::Integer(1).checked_div(&Number::Float(0.0)), None);
+        assert_eq!(
+            Number::Integer(1).checked_div(&Number::Integer(4)),
+            Some(Number::Float(0.25))
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(Number::Integer(1), Number::Float(1.0));
+        assert_eq!(
+            Number::Integer(1),
+            Number::Decimal(BigDecimal::from_str("1.00").unwrap())
+        );
+        assert_ne!(Number::Integer(1), Number::Float(1.1));
+        assert!(Number::Integer(1) < Number::Float(1.1));
+        assert!(Number::Float(-0.5) < Number::Integer(0));
+        assert!(
+            Number::Decimal(BigDecimal::from_str("9223372036854775808").unwrap())
+                > Number::Integer(i64::MAX)
+        );
+        assert!(Number::Float(f64::INFINITY) > Number::Integer(i64::MAX));
+        assert_eq!(
+            Number::Float(f64::NAN).partial_cmp(&Number::Integer(1)),
+            None
+        );
+        assert_ne!(Number::Float(f64::NAN), Number::Float(f64::NAN));
+    }
+
+    #[test]
+    fn test_round() {
+        assert!(matches!(
+            Number::Float(2.675).round(2, RoundingMode::HalfUp),
+            Number::Float(f) if f == 2.68
+        ));
+        assert!(matches!(
+            Number::Float(2.665).round(2, RoundingMode::HalfEven),
+            Number::Float(f) if f == 2.66
+        ));
+        assert!(matches!(
+            Number::Float(-1.5).round(0, RoundingMode::Floor),
+            Number::Float(f) if f == -2.0
+        ));
+        assert!(matches!(
+            Number::Integer(7).round(2, RoundingMode::HalfUp),
+            Number::Integer(7)
+        ));
+        assert_eq!(
+            Number::Decimal(BigDecimal::from_str("1.005").unwrap())
+                .round(2, RoundingMode::Down)
+                .to_string(),
+            "1.00"
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        let format = NumberFormat {
+            decimals: Some(2),
+            thousands_separator: Some(','),
+            ..NumberFormat::default()
+        };
+
+        assert_eq!(Number::Integer(1234567).format(&format), "1,234,567.00");
+        assert_eq!(Number::Float(-1234.567).format(&format), "-1,234.57");
+        assert_eq!(Number::Float(0.5).format(&format), "0.50");
+        assert_eq!(Number::Integer(123).format(&format), "123.00");
+        assert_eq!(Number::Float(-0.001).format(&format), "0.00");
+
+        let format = NumberFormat {
+            thousands_separator: Some('.'),
+            decimal_separator: ',',
+            ..NumberFormat::default()
+        };
+
+        assert_eq!(Number::Float(1234567.891).format(&format), "1.234.567,891");
+        assert_eq!(
+            Number::Decimal(BigDecimal::from_str("12345678901234567.89").unwrap()).format(&format),
+            "12.345.678.901.234.567,89"
+        );
+
+        assert_eq!(Number::Integer(-42).format(&NumberFormat::default()), "-42");
+        assert_eq!(
+            Number::Float(f64::NAN).format(&NumberFormat::default()),
+            "NaN"
+        );
+    }
+
+    #[test]
+    fn test_parse_str() {
+        assert!(matches!(Number::parse_str("42"), Ok(Number::Integer(42))));
+        assert!(matches!(Number::parse_str(" -1 "), Ok(Number::Integer(-1))));
+        assert!(matches!(Number::parse_str("1.5"), Ok(Number::Float(f)) if f == 1.5));
+        assert!(Number::parse_str("NaN").is_err());
+        assert!(Number::parse_str("inf").is_err());
+        assert!(Number::parse_str("abc").is_err());
+        assert!(matches!(Number::parse_str("1e3"), Ok(Number::Decimal(_))));
+        assert_eq!(Number::parse_str("1e3").unwrap(), Number::Float(1000.0));
+        assert!(matches!(
+            Number::parse_str("9223372036854775808"),
+            Ok(Number::Decimal(_))
+        ));
+        assert!(matches!(
+            Number::parse_str("0.1000000000000000000001"),
+            Ok(Number::Decimal(_))
+        ));
+    }
+
     #[test]
     fn test_to_json() {
         {
@@ -84,5 +803,11 @@ mod tests {
             let number = Number::Integer(-1);
             assert!(number.to_json() == Value::Number(JsonNumber::from(-1)));
         }
+
+        // non-finite
+        {
+            assert_eq!(Number::Float(f64::NAN).to_json(), Value::Null);
+            assert_eq!(Number::Float(f64::INFINITY).to_json(), Value::Null);
+        }
     }
 }
@@ -0,0 +1,173 @@
+use serde_json::Value;
+use time::{
+    format_description::well_known::Rfc3339, Duration, OffsetDateTime as TimeOffsetDateTime,
+    UtcOffset,
+};
+
+use super::{json_codec::JsonCodec, naive_date_time::NaiveDateTime};
+
+#[derive(Debug)]
+pub struct ParseOffsetDateTimeError;
+
+/// A date and time with an explicit UTC offset, e.g. `2022-04-29T15:34:10+08:00`.
+///
+/// `date_time` is the local date and time at `offset_seconds` east of UTC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OffsetDateTime {
+    pub date_time: NaiveDateTime,
+    pub offset_seconds: i32,
+}
+
+impl OffsetDateTime {
+    pub fn new(date_time: NaiveDateTime, offset_seconds: i32) -> Self {
+        Self {
+            date_time,
+            offset_seconds,
+        }
+    }
+
+    /// Converts a date and time in UTC to the local date and time at
+    /// `offset_seconds`. Returns `None` if the offset is out of range or the
+    /// date and time is invalid.
+    pub fn from_utc(date_time: &NaiveDateTime, offset_seconds: i32) -> Option<Self> {
+        UtcOffset::from_whole_seconds(offset_seconds).ok()?;
+
+        date_time
+            .to_primitive_date_time()?
+            .checked_add(Duration::seconds(offset_seconds as i64))
+            .map(|pdt| OffsetDateTime::new(NaiveDateTime::from(pdt), offset_seconds))
+    }
+
+    /// Returns the date and time in UTC, `None` if it is invalid.
+    pub fn to_utc(&self) -> Option<NaiveDateTime> {
+        self.date_time
+            .to_primitive_date_time()?
+            .checked_sub(Duration::seconds(self.offset_seconds as i64))
+            .map(NaiveDateTime::from)
+    }
+
+    /// Returns the same instant at another offset, e.g. `8 * 3600` for China
+    /// Standard Time.
+    pub fn to_offset(&self, offset_seconds: i32) -> Option<Self> {
+        OffsetDateTime::from_utc(&self.to_utc()?, offset_seconds)
+    }
+}
+
+impl JsonCodec for OffsetDateTime {
+    type Err = ParseOffsetDateTimeError;
+
+    fn from_json(value: &Value) -> Result<Self, Self::Err> {
+        match value.as_str() {
+            Some(str) => match TimeOffsetDateTime::parse(str, &Rfc3339) {
+                Ok(odt) => Ok(OffsetDateTime {
+                    date_time: NaiveDateTime::new(
+                        odt.year(),
+                        odt.month() as u8,
+                        odt.day(),
+                        odt.hour(),
+                        odt.minute(),
+                        odt.second(),
+                        odt.nanosecond(),
+                    ),
+                    offset_seconds: odt.offset().whole_seconds(),
+                }),
+                Err(_err) => Err(ParseOffsetDateTimeError),
+            },
+            None => Err(ParseOffsetDateTimeError),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self.date_time.to_json() {
+            Value::String(str) => {
+                let sign = if self.offset_seconds < 0 { '-' } else { '+' };
+                let offset_seconds = self.offset_seconds.abs();
+
+                Value::String(format!(
+                    "{}{}{:02}:{:02}",
+                    str,
+                    sign,
+                    offset_seconds / 3600,
+                    offset_seconds % 3600 / 60
+                ))
+            }
+            value => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_json() {
+        {
+            let json = json!("2022-04-29T15:34:10.420159+08:00");
+            let odt = OffsetDateTime::from_json(&json).unwrap();
+
+            assert_eq!(
+                odt,
+                OffsetDateTime::new(
+                    NaiveDateTime::new(2022, 4, 29, 15, 34, 10, 420159000),
+                    8 * 3600
+                )
+            );
+        }
+
+        {
+            let json = json!("2022-04-29T07:34:10-05:30");
+            let odt = OffsetDateTime::from_json(&json).unwrap();
+
+            assert_eq!(odt.offset_seconds, -(5 * 3600 + 30 * 60));
+        }
+
+        // without offset
+        {
+            let json = json!("2022-04-29T07:34:10");
+
+            assert!(OffsetDateTime::from_json(&json).is_err());
+        }
+    }
+
+    #[test]
+    fn test_to_json() {
+        {
+            let json = json!("2022-04-29T15:34:10.420159+08:00");
+            let odt = OffsetDateTime::from_json(&json).unwrap();
+
+            assert_eq!(odt.to_json(), json);
+        }
+
+        {
+            let odt = OffsetDateTime::new(NaiveDateTime::new(2022, 4, 29, 7, 34, 10, 0), -19800);
+
+            assert_eq!(odt.to_json(), json!("2022-04-29T07:34:10-05:30"));
+        }
+    }
+
+    #[test]
+    fn test_convert_offset() {
+        let odt = OffsetDateTime::new(NaiveDateTime::new(2022, 5, 1, 2, 0, 0, 0), 8 * 3600);
+
+        assert_eq!(
+            odt.to_utc(),
+            Some(NaiveDateTime::new(2022, 4, 30, 18, 0, 0, 0))
+        );
+        assert_eq!(
+            odt.to_offset(-5 * 3600),
+            Some(OffsetDateTime::new(
+                NaiveDateTime::new(2022, 4, 30, 13, 0, 0, 0),
+                -5 * 3600
+            ))
+        );
+        assert_eq!(
+            OffsetDateTime::from_utc(&NaiveDateTime::new(2022, 4, 30, 18, 0, 0, 0), 8 * 3600),
+            Some(odt.clone())
+        );
+
+        // out of range offset
+        assert_eq!(odt.to_offset(30 * 3600), None);
+    }
+}
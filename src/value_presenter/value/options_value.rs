@@ -10,6 +10,15 @@ pub struct OptionsValue {
     pub other: Option<OptionValue>,
 }
 
+/// The `other` options of both sides of a union, which differ and are not
+/// selected on the other side. A value holds a single `other`, so neither
+/// can be kept as one without dropping the other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtherConflict {
+    pub lhs: OptionValue,
+    pub rhs: OptionValue,
+}
+
 #[derive(Debug)]
 pub enum ParseOptionsValueError {
     InvalidJson,
@@ -70,10 +79,112 @@ impl JsonCodec for OptionsValue {
     }
 }
 
+/// Set operations treat `other` as one more selected option, so an option
+/// given as `other` is contained in the value and matches the same option of
+/// another value.
 impl OptionsValue {
     pub fn count_options(&self) -> usize {
         self.options.len() + self.other.is_some() as usize
     }
+
+    /// Returns the selected options followed by `other`.
+    pub fn selected(&self) -> impl Iterator<Item = &OptionValue> {
+        self.options.iter().chain(self.other.iter())
+    }
+
+    /// Returns whether `option` is one of the options or `other`.
+    pub fn contains(&self, option: &str) -> bool {
+        self.selected().any(|selected| selected == option)
+    }
+
+    /// Returns the options of `self` followed by the options of `rhs` it does
+    /// not contain. `other` is kept, or taken from `rhs` when `self` has none.
+    /// Fails when both have an `other` that the union does not contain
+    /// otherwise, as moving one of them into `options` would select an
+    /// option a schema does not list.
+    pub fn union(&self, rhs: &OptionsValue) -> Result<OptionsValue, OtherConflict> {
+        let mut union = self.clone();
+
+        for option in &rhs.options {
+            if !union.contains(option) {
+                union.options.push(option.clone());
+            }
+        }
+
+        match (&union.other, &rhs.other) {
+            (_, Some(other)) if union.contains(other) => (),
+            (Some(lhs), Some(rhs)) => {
+                return Err(OtherConflict {
+                    lhs: lhs.clone(),
+                    rhs: rhs.clone(),
+                })
+            }
+            (None, other) => union.other = other.clone(),
+            (Some(_other), None) => (),
+        }
+
+        Ok(union)
+    }
+
+    /// Returns the options of `self`, and `other`, that `rhs` contains.
+    pub fn intersection(&self, rhs: &OptionsValue) -> OptionsValue {
+        self.filter(|option| rhs.contains(option))
+    }
+
+    /// Returns the options of `self`, and `other`, that `rhs` does not contain.
+    pub fn difference(&self, rhs: &OptionsValue) -> OptionsValue {
+        self.filter(|option| !rhs.contains(option))
+    }
+
+    /// Removes repeated options, and `other` if it is one of the options.
+    pub fn dedup(&mut self) {
+        let mut options: Vec<OptionValue> = vec![];
+
+        for option in self.options.drain(..) {
+            if !options.contains(&option) {
+                options.push(option);
+            }
+        }
+
+        if matches!(&self.other, Some(other) if options.contains(other)) {
+            self.other = None;
+        }
+
+        self.options = options;
+    }
+
+    /// Returns whether both values select the same options, regardless of
+    /// their order, repetitions and whether an option is given as `other`.
+    pub fn set_eq(&self, rhs: &OptionsValue) -> bool {
+        self.selected().all(|option| rhs.contains(option))
+            && rhs.selected().all(|option| self.contains(option))
+    }
+
+    fn filter<F>(&self, mut predicate: F) -> OptionsValue
+    where
+        F: FnMut(&str) -> bool,
+    {
+        OptionsValue {
+            options: self
+                .options
+                .iter()
+                .filter(|option| predicate(option))
+                .cloned()
+                .collect(),
+            other: self.other.clone().filter(|other| predicate(other)),
+        }
+    }
+}
+
+#[cfg(test)]
+impl OptionsValue {
+    /// Builds a value from string slices in tests.
+    pub(crate) fn from_strs(options: &[&str], other: Option<&str>) -> Self {
+        OptionsValue {
+            options: options.iter().map(|option| option.to_string()).collect(),
+            other: other.map(String::from),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -88,7 +199,7 @@ mod tests {
             other: None,
         };
 
-        assert!(options_value.options.len() != 0);
+        assert!(!options_value.options.is_empty());
     }
 
     #[test]
@@ -100,7 +211,7 @@ mod tests {
             other: None,
         };
 
-        assert!(options_value.options.len() != 0);
+        assert!(!options_value.options.is_empty());
     }
 
     #[test]
@@ -112,7 +223,7 @@ mod tests {
             other,
         };
 
-        assert!(options_value.options.len() == 0);
+        assert!(options_value.options.is_empty());
         assert!(options_value.other.unwrap() == "other");
     }
 
@@ -147,7 +258,7 @@ mod tests {
 
             let other = String::from("other");
 
-            assert!(options_value.options.len() == 0);
+            assert!(options_value.options.is_empty());
             assert!(options_value.other == Some(other));
         }
 
@@ -162,7 +273,7 @@ mod tests {
 
             let other = String::from("other");
 
-            assert!(options_value.options.len() == 0);
+            assert!(options_value.options.is_empty());
             assert!(options_value.other == Some(other));
         }
 
@@ -176,7 +287,7 @@ mod tests {
 
             let other = String::from("other");
 
-            assert!(options_value.options.len() == 0);
+            assert!(options_value.options.is_empty());
             assert!(options_value.other == Some(other));
         }
 
@@ -189,7 +300,7 @@ mod tests {
 
             let options_value = OptionsValue::from_json(&json).unwrap();
 
-            assert!(options_value.other == None);
+            assert!(options_value.other.is_none());
         }
 
         // other is not present
@@ -200,7 +311,7 @@ mod tests {
 
             let options_value = OptionsValue::from_json(&json).unwrap();
 
-            assert!(options_value.other == None);
+            assert!(options_value.other.is_none());
         }
 
         // invalid options
@@ -212,7 +323,7 @@ mod tests {
 
             let result = OptionsValue::from_json(&json);
 
-            assert!(matches!(result, Err(_)));
+            assert!(result.is_err());
         }
 
         // invalid other
@@ -224,7 +335,7 @@ mod tests {
 
             let result = OptionsValue::from_json(&json);
 
-            assert!(matches!(result, Err(_)));
+            assert!(result.is_err());
         }
     }
 
@@ -270,4 +381,90 @@ mod tests {
             assert!(options_value.count_options() == 0);
         }
     }
+
+    #[test]
+    fn test_contains() {
+        let value = OptionsValue::from_strs(&["a", "b"], Some("x"));
+
+        assert!(value.contains("a") && value.contains("x"));
+        assert!(!value.contains("c"));
+        assert_eq!(value.selected().collect::<Vec<_>>(), vec!["a", "b", "x"]);
+    }
+
+    #[test]
+    fn test_union() {
+        assert_eq!(
+            OptionsValue::from_strs(&["a", "b"], None)
+                .union(&OptionsValue::from_strs(&["c", "a"], Some("x"))),
+            Ok(OptionsValue::from_strs(&["a", "b", "c"], Some("x")))
+        );
+        assert_eq!(
+            OptionsValue::from_strs(&[], None)
+                .union(&OptionsValue::from_strs(&["a", "a"], Some("a"))),
+            Ok(OptionsValue::from_strs(&["a"], None))
+        );
+
+        // the other option of `rhs` is already selected
+        assert_eq!(
+            OptionsValue::from_strs(&["a", "y"], Some("x"))
+                .union(&OptionsValue::from_strs(&["x", "b"], Some("y"))),
+            Ok(OptionsValue::from_strs(&["a", "y", "b"], Some("x")))
+        );
+        assert_eq!(
+            OptionsValue::from_strs(&["a"], Some("x"))
+                .union(&OptionsValue::from_strs(&[], Some("x"))),
+            Ok(OptionsValue::from_strs(&["a"], Some("x")))
+        );
+
+        // both sides have a different other option
+        assert_eq!(
+            OptionsValue::from_strs(&["a"], Some("x"))
+                .union(&OptionsValue::from_strs(&["x", "b"], Some("y"))),
+            Err(OtherConflict {
+                lhs: "x".to_string(),
+                rhs: "y".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_intersection_and_difference() {
+        let value = OptionsValue::from_strs(&["a", "b", "c"], Some("x"));
+        let allowed = OptionsValue::from_strs(&["c", "a"], Some("x"));
+
+        assert_eq!(
+            value.intersection(&allowed),
+            OptionsValue::from_strs(&["a", "c"], Some("x"))
+        );
+        assert_eq!(
+            value.difference(&allowed),
+            OptionsValue::from_strs(&["b"], None)
+        );
+        assert_eq!(
+            OptionsValue::from_strs(&["a"], Some("y")).difference(&allowed),
+            OptionsValue::from_strs(&[], Some("y"))
+        );
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut value = OptionsValue::from_strs(&["a", "b", "a"], Some("b"));
+        value.dedup();
+        assert_eq!(value, OptionsValue::from_strs(&["a", "b"], None));
+
+        let mut value = OptionsValue::from_strs(&["a"], Some("x"));
+        value.dedup();
+        assert_eq!(value, OptionsValue::from_strs(&["a"], Some("x")));
+    }
+
+    #[test]
+    fn test_set_eq() {
+        assert!(OptionsValue::from_strs(&["a", "b"], None)
+            .set_eq(&OptionsValue::from_strs(&["b", "a", "b"], None)));
+        assert!(OptionsValue::from_strs(&["a"], Some("x"))
+            .set_eq(&OptionsValue::from_strs(&["x"], Some("a"))));
+        assert!(!OptionsValue::from_strs(&["a"], None)
+            .set_eq(&OptionsValue::from_strs(&["a", "b"], None)));
+        assert!(OptionsValue::from_strs(&[], None).set_eq(&OptionsValue::from_strs(&[], None)));
+    }
 }
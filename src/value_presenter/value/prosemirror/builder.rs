@@ -0,0 +1,395 @@
+//! Building ProseMirror documents in code.
+//!
+//! `Node` constructors build single nodes, and `DocBuilder` appends blocks to
+//! a document, e.g.
+//!
+//! ```ignore
+//! let state = DocBuilder::new()
+//!     .heading(1, "Weekly report")
+//!     .paragraph("All tasks are done.")
+//!     .bullet_list(&["Design", "Build"])
+//!     .block(Node::paragraph(vec![
+//!         Node::text("See "),
+//!         Node::link("Jet", "https://jet.work"),
+//!     ]))
+//!     .build();
+//! ```
+
+use serde_json::{Map, Value};
+
+use super::{
+    node::{sort_marks, Mark, Node, NodeType},
+    ProsemirrorState,
+};
+
+impl Node {
+    pub fn paragraph(content: Vec<Node>) -> Self {
+        Node {
+            content,
+            ..Node::new(NodeType::Paragraph)
+        }
+    }
+
+    /// Builds a heading, `level` being clamped to `1..=6`.
+    pub fn heading(level: u8, content: Vec<Node>) -> Self {
+        Node {
+            attrs: Some(attrs("level", Value::from(level.clamp(1, 6)))),
+            content,
+            ..Node::new(NodeType::Heading)
+        }
+    }
+
+    /// Builds a bullet list with an item for each list of blocks.
+    pub fn bullet_list(items: Vec<Vec<Node>>) -> Self {
+        Node {
+            content: items.into_iter().map(Node::list_item).collect(),
+            ..Node::new(NodeType::BulletList)
+        }
+    }
+
+    /// Builds an ordered list numbered from `start` with an item for each list
+    /// of blocks.
+    pub fn ordered_list(start: u64, items: Vec<Vec<Node>>) -> Self {
+        Node {
+            attrs: Some(attrs("start", Value::from(start))),
+            content: items.into_iter().map(Node::list_item).collect(),
+            ..Node::new(NodeType::OrderedList)
+        }
+    }
+
+    /// Builds a list item, with an empty paragraph if `content` is empty.
+    pub fn list_item(content: Vec<Node>) -> Self {
+        Node {
+            content: blocks_or_empty_paragraph(content),
+            ..Node::new(NodeType::ListItem)
+        }
+    }
+
+    /// Builds a table with a row of header cells followed by rows of cells,
+    /// each cell given as its inline content.
+    pub fn table(header: Vec<Vec<Node>>, rows: Vec<Vec<Vec<Node>>>) -> Self {
+        let mut table = Node::new(NodeType::Table);
+
+        if !header.is_empty() {
+            table.content.push(table_row(NodeType::TableHeader, header));
+        }
+
+        for row in rows {
+            table.content.push(table_row(NodeType::TableCell, row));
+        }
+
+        table
+    }
+
+    pub fn code_block(language: Option<&str>, text: &str) -> Self {
+        let mut code_block = Node::new(NodeType::CodeBlock);
+
+        if let Some(language) = language {
+            code_block.attrs = Some(attrs("language", Value::from(language)));
+        }
+
+        if !text.is_empty() {
+            code_block.content.push(Node::text(text));
+        }
+
+        code_block
+    }
+
+    pub fn horizontal_rule() -> Self {
+        Node::new(NodeType::HorizontalRule)
+    }
+
+    pub fn hard_break() -> Self {
+        Node::new(NodeType::HardBreak)
+    }
+
+    pub fn image(src: &str, alt: &str) -> Self {
+        let mut image_attrs = attrs("src", Value::from(src));
+        image_attrs.insert("alt".to_string(), Value::from(alt));
+
+        Node {
+            attrs: Some(image_attrs),
+            ..Node::new(NodeType::Image)
+        }
+    }
+
+    /// Builds text linking to `href`.
+    pub fn link(text: &str, href: &str) -> Self {
+        Node::text(text).with_mark(Mark::link(href))
+    }
+
+    /// Adds `mark`, replacing a mark of the same type.
+    pub fn with_mark(mut self, mark: Mark) -> Self {
+        self.marks.retain(|other| other.mark_type != mark.mark_type);
+        self.marks.push(mark);
+        sort_marks(&mut self.marks);
+        self
+    }
+
+    /// Builds inline content for `text`, with a hard break for each newline.
+    pub fn inline_text(text: &str) -> Vec<Node> {
+        let mut content = vec![];
+
+        for (index, line) in text.split('\n').enumerate() {
+            if index > 0 {
+                content.push(Node::hard_break());
+            }
+
+            if !line.is_empty() {
+                content.push(Node::text(line));
+            }
+        }
+
+        content
+    }
+}
+
+impl ProsemirrorState {
+    /// Appends a block to the document, replacing the empty paragraph of an
+    /// otherwise empty document.
+    pub fn append(&mut self, block: Node) {
+        let content = &mut self.doc.content;
+
+        if content.len() == 1 && content[0] == Node::new(NodeType::Paragraph) {
+            content.clear();
+        }
+
+        content.push(block);
+    }
+}
+
+/// Builds a document by appending blocks, see the module documentation.
+#[derive(Debug, Clone, Default)]
+pub struct DocBuilder {
+    content: Vec<Node>,
+}
+
+impl DocBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn block(mut self, block: Node) -> Self {
+        self.content.push(block);
+        self
+    }
+
+    /// Appends a paragraph, newlines in `text` becoming hard breaks.
+    pub fn paragraph(self, text: &str) -> Self {
+        self.block(Node::paragraph(Node::inline_text(text)))
+    }
+
+    pub fn heading(self, level: u8, text: &str) -> Self {
+        self.block(Node::heading(level, Node::inline_text(text)))
+    }
+
+    /// Appends a bullet list with a paragraph item for each of `items`.
+    pub fn bullet_list<S>(self, items: &[S]) -> Self
+    where
+        S: AsRef<str>,
+    {
+        self.block(Node::bullet_list(paragraph_items(items)))
+    }
+
+    /// Appends a list numbered from 1 with a paragraph item for each of `items`.
+    pub fn ordered_list<S>(self, items: &[S]) -> Self
+    where
+        S: AsRef<str>,
+    {
+        self.block(Node::ordered_list(1, paragraph_items(items)))
+    }
+
+    /// Appends a table with a row of header cells followed by rows of cells.
+    pub fn table<R, S>(self, header: &[S], rows: &[R]) -> Self
+    where
+        R: AsRef<[S]>,
+        S: AsRef<str>,
+    {
+        let cells = |cells: &[S]| {
+            cells
+                .iter()
+                .map(|cell| Node::inline_text(cell.as_ref()))
+                .collect()
+        };
+
+        self.block(Node::table(
+            cells(header),
+            rows.iter().map(|row| cells(row.as_ref())).collect(),
+        ))
+    }
+
+    pub fn horizontal_rule(self) -> Self {
+        self.block(Node::horizontal_rule())
+    }
+
+    /// Builds the document, with an empty paragraph if no block was appended.
+    pub fn build(self) -> ProsemirrorState {
+        ProsemirrorState::new(Node {
+            content: blocks_or_empty_paragraph(self.content),
+            ..Node::new(NodeType::Doc)
+        })
+    }
+}
+
+fn attrs(key: &str, value: Value) -> Map<String, Value> {
+    let mut attrs = Map::new();
+    attrs.insert(key.to_string(), value);
+    attrs
+}
+
+fn blocks_or_empty_paragraph(mut content: Vec<Node>) -> Vec<Node> {
+    if content.is_empty() {
+        content.push(Node::new(NodeType::Paragraph));
+    }
+
+    content
+}
+
+fn paragraph_items<S>(items: &[S]) -> Vec<Vec<Node>>
+where
+    S: AsRef<str>,
+{
+    items
+        .iter()
+        .map(|item| vec![Node::paragraph(Node::inline_text(item.as_ref()))])
+        .collect()
+}
+
+fn table_row(cell_type: NodeType, cells: Vec<Vec<Node>>) -> Node {
+    Node {
+        content: cells
+            .into_iter()
+            .map(|content| Node {
+                content: vec![Node::paragraph(content)],
+                ..Node::new(cell_type.clone())
+            })
+            .collect(),
+        ..Node::new(NodeType::TableRow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_presenter::value::{json_codec::JsonCodec, prosemirror::node::MarkType};
+    use serde_json::json;
+
+    #[test]
+    fn test_build() {
+        let state = DocBuilder::new()
+            .heading(9, "Report")
+            .paragraph("line\nbreak")
+            .bullet_list(&["a", "b"])
+            .table(&["Name", "Done"], &[["Design", "yes"]])
+            .block(Node::paragraph(vec![
+                Node::text("See "),
+                Node::link("Jet", "https://jet.work").with_mark(Mark::new(MarkType::Bold)),
+            ]))
+            .build();
+
+        assert_eq!(
+            state.to_json(),
+            json!({
+                "type": "doc",
+                "content": [
+                    {
+                        "type": "heading",
+                        "attrs": { "level": 6 },
+                        "content": [{ "type": "text", "text": "Report" }]
+                    },
+                    {
+                        "type": "paragraph",
+                        "content": [
+                            { "type": "text", "text": "line" },
+                            { "type": "hardBreak" },
+                            { "type": "text", "text": "break" }
+                        ]
+                    },
+                    {
+                        "type": "bulletList",
+                        "content": [
+                            {
+                                "type": "listItem",
+                                "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "a" }] }]
+                            },
+                            {
+                                "type": "listItem",
+                                "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "b" }] }]
+                            }
+                        ]
+                    },
+                    {
+                        "type": "table",
+                        "content": [
+                            {
+                                "type": "tableRow",
+                                "content": [
+                                    {
+                                        "type": "tableHeader",
+                                        "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "Name" }] }]
+                                    },
+                                    {
+                                        "type": "tableHeader",
+                                        "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "Done" }] }]
+                                    }
+                                ]
+                            },
+                            {
+                                "type": "tableRow",
+                                "content": [
+                                    {
+                                        "type": "tableCell",
+                                        "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "Design" }] }]
+                                    },
+                                    {
+                                        "type": "tableCell",
+                                        "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "yes" }] }]
+                                    }
+                                ]
+                            }
+                        ]
+                    },
+                    {
+                        "type": "paragraph",
+                        "content": [
+                            { "type": "text", "text": "See " },
+                            {
+                                "type": "text",
+                                "text": "Jet",
+                                "marks": [
+                                    { "type": "link", "attrs": { "href": "https://jet.work" } },
+                                    { "type": "bold" }
+                                ]
+                            }
+                        ]
+                    }
+                ]
+            })
+        );
+
+        // built documents are valid
+        assert_eq!(
+            ProsemirrorState::from_json(&state.to_json()).unwrap(),
+            state
+        );
+        assert_eq!(
+            DocBuilder::new().build().to_json(),
+            json!({ "type": "doc", "content": [{ "type": "paragraph" }] })
+        );
+    }
+
+    #[test]
+    fn test_append() {
+        let mut state = ProsemirrorState::from_plain_text("");
+        state.append(Node::horizontal_rule());
+        state.append(Node::paragraph(vec![]));
+
+        assert_eq!(
+            state.to_json(),
+            json!({
+                "type": "doc",
+                "content": [{ "type": "horizontalRule" }, { "type": "paragraph" }]
+            })
+        );
+    }
+}
@@ -0,0 +1,1284 @@
+//! Conversions between ProseMirror documents and HTML.
+//!
+//! Written HTML only uses the tags of the schema's node and mark types, with
+//! all text and attributes escaped and links and images with unsafe URLs, e.g.
+//! `javascript:`, dropped. Parsing is lenient: unknown tags are unwrapped,
+//! scripts, styles and comments are dropped, and inline content outside of a
+//! textblock is wrapped in paragraphs.
+
+use serde_json::{Map, Value};
+
+use super::{
+    node::{normalize_inline, sort_marks, Mark, MarkType, Node, NodeType},
+    ProsemirrorState,
+};
+
+impl ProsemirrorState {
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+
+        for node in &self.doc.content {
+            write_node(node, &mut html);
+        }
+
+        html
+    }
+
+    /// Parses an HTML document or fragment, keeping only what the schema can
+    /// represent.
+    pub fn from_html(html: &str) -> Self {
+        let root = parse_dom(html);
+
+        let mut doc = Node::new(NodeType::Doc);
+        doc.content = blocks_from_dom(&root.children);
+
+        if doc.content.is_empty() {
+            doc.content.push(Node::new(NodeType::Paragraph));
+        }
+
+        ProsemirrorState::new(doc)
+    }
+}
+
+/// Whether `url` is relative or uses a scheme that can not run script, i.e.
+/// `http`, `https`, `mailto` or `tel`.
+pub(super) fn is_safe_url(url: &str) -> bool {
+    let url = url.trim();
+
+    match url.find([':', '/', '?', '#']) {
+        Some(index) if url[index..].starts_with(':') => matches!(
+            url[..index].to_ascii_lowercase().as_str(),
+            "http" | "https" | "mailto" | "tel"
+        ),
+        _ => true,
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(char),
+        }
+    }
+
+    escaped
+}
+
+fn write_node(node: &Node, html: &mut String) {
+    match node.node_type {
+        NodeType::Paragraph => write_element("p", &[], &node.content, html),
+        NodeType::Heading => {
+            let level = node
+                .attr("level")
+                .and_then(Value::as_u64)
+                .unwrap_or(1)
+                .clamp(1, 6);
+
+            write_element(&format!("h{}", level), &[], &node.content, html)
+        }
+        NodeType::Blockquote => write_element("blockquote", &[], &node.content, html),
+        NodeType::BulletList => write_element("ul", &[], &node.content, html),
+        NodeType::OrderedList => {
+            let attrs = match node.attr("start").and_then(Value::as_u64) {
+                Some(start) if start != 1 => vec![("start", start.to_string())],
+                _ => vec![],
+            };
+
+            write_element("ol", &attrs, &node.content, html)
+        }
+        NodeType::ListItem => write_element("li", &[], &node.content, html),
+        NodeType::CodeBlock => {
+            html.push_str("<pre><code");
+
+            if let Some(language) = node.attr("language").and_then(Value::as_str) {
+                html.push_str(&format!(" class=\"language-{}\"", escape(language)));
+            }
+
+            html.push('>');
+            html.push_str(&escape(&node.to_plain_text()));
+            html.push_str("</code></pre>");
+        }
+        NodeType::HorizontalRule => html.push_str("<hr>"),
+        NodeType::HardBreak => html.push_str("<br>"),
+        NodeType::Image => {
+            if let Some(src) = node
+                .attr("src")
+                .and_then(Value::as_str)
+                .filter(|src| is_safe_url(src))
+            {
+                html.push_str(&format!("<img src=\"{}\"", escape(src)));
+
+                for key in ["alt", "title"] {
+                    if let Some(value) = node.attr(key).and_then(Value::as_str) {
+                        html.push_str(&format!(" {}=\"{}\"", key, escape(value)));
+                    }
+                }
+
+                html.push('>');
+            }
+        }
+        NodeType::Table => {
+            html.push_str("<table><tbody>");
+
+            for row in &node.content {
+                write_node(row, html);
+            }
+
+            html.push_str("</tbody></table>");
+        }
+        NodeType::TableRow => write_element("tr", &[], &node.content, html),
+        NodeType::TableHeader | NodeType::TableCell => {
+            let tag = if node.node_type == NodeType::TableHeader {
+                "th"
+            } else {
+                "td"
+            };
+            let attrs = ["colspan", "rowspan"]
+                .iter()
+                .filter_map(|key| match node.attr(key).and_then(Value::as_u64) {
+                    Some(span) if span > 1 => Some((*key, span.to_string())),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+            write_element(tag, &attrs, &node.content, html)
+        }
+        NodeType::Text => write_text(node, html),
+        NodeType::Doc | NodeType::Other(_) => {
+            for child in &node.content {
+                write_node(child, html);
+            }
+        }
+    }
+}
+
+fn write_element(tag: &str, attrs: &[(&str, String)], content: &[Node], html: &mut String) {
+    html.push('<');
+    html.push_str(tag);
+
+    for (key, value) in attrs {
+        html.push_str(&format!(" {}=\"{}\"", key, escape(value)));
+    }
+
+    html.push('>');
+
+    for child in content {
+        write_node(child, html);
+    }
+
+    html.push_str(&format!("</{}>", tag));
+}
+
+fn write_text(node: &Node, html: &mut String) {
+    let mut marks = node.marks.clone();
+    sort_marks(&mut marks);
+
+    let mut closing = vec![];
+
+    for mark in &marks {
+        let tag = match mark.mark_type {
+            MarkType::Link => match mark
+                .attr("href")
+                .and_then(Value::as_str)
+                .filter(|href| is_safe_url(href))
+            {
+                Some(href) => {
+                    html.push_str(&format!("<a href=\"{}\">", escape(href)));
+                    closing.push("a");
+                    continue;
+                }
+                None => continue,
+            },
+            MarkType::Bold => "strong",
+            MarkType::Italic => "em",
+            MarkType::Underline => "u",
+            MarkType::Strike => "s",
+            MarkType::Code => "code",
+            MarkType::Other(_) => continue,
+        };
+
+        html.push_str(&format!("<{}>", tag));
+        closing.push(tag);
+    }
+
+    html.push_str(&escape(node.text.as_deref().unwrap_or_default()));
+
+    for tag in closing.iter().rev() {
+        html.push_str(&format!("</{}>", tag));
+    }
+}
+
+enum Dom {
+    Element(Element),
+    Text(String),
+}
+
+struct Element {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Dom>,
+}
+
+impl Element {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            attrs: vec![],
+            children: vec![],
+        }
+    }
+
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(key, _value)| key == name)
+            .map(|(_key, value)| value.as_str())
+    }
+}
+
+enum Tag {
+    Start {
+        element: Element,
+        self_closing: bool,
+    },
+    End(String),
+}
+
+fn is_void(name: &str) -> bool {
+    matches!(
+        name,
+        "area"
+            | "base"
+            | "br"
+            | "col"
+            | "embed"
+            | "hr"
+            | "img"
+            | "input"
+            | "link"
+            | "meta"
+            | "param"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
+
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "p" | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "blockquote"
+            | "ul"
+            | "ol"
+            | "li"
+            | "pre"
+            | "hr"
+            | "table"
+            | "thead"
+            | "tbody"
+            | "tfoot"
+            | "tr"
+            | "td"
+            | "th"
+            | "div"
+            | "section"
+            | "article"
+            | "main"
+            | "header"
+            | "footer"
+            | "aside"
+            | "nav"
+            | "figure"
+            | "figcaption"
+            | "address"
+            | "details"
+            | "summary"
+            | "dl"
+            | "dt"
+            | "dd"
+            | "form"
+            | "fieldset"
+            | "center"
+            | "body"
+            | "html"
+    )
+}
+
+/// Whether the element and everything within it is dropped.
+fn is_ignored(name: &str) -> bool {
+    matches!(
+        name,
+        "head"
+            | "script"
+            | "style"
+            | "title"
+            | "template"
+            | "noscript"
+            | "iframe"
+            | "object"
+            | "embed"
+            | "svg"
+            | "math"
+            | "canvas"
+            | "button"
+            | "input"
+            | "select"
+            | "textarea"
+            | "meta"
+            | "link"
+    )
+}
+
+/// Whether the content of the element is raw text, which can not hold tags.
+fn is_raw_text(name: &str) -> bool {
+    matches!(
+        name,
+        "script" | "style" | "title" | "textarea" | "noscript" | "template"
+    )
+}
+
+/// Builds a tree from HTML, closing elements the way browsers do for the
+/// common cases, e.g. an unclosed `<li>` before the next one.
+fn parse_dom(html: &str) -> Element {
+    let mut stack = vec![Element::new("#root")];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment
+                .find("-->")
+                .map_or("", |index| &comment[index + 3..]);
+            continue;
+        }
+
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |index| &rest[index + 1..]);
+            continue;
+        }
+
+        if let Some((tag, after)) = parse_tag(rest) {
+            rest = after;
+
+            match tag {
+                Tag::Start {
+                    element,
+                    self_closing,
+                } => {
+                    if is_raw_text(&element.name) && !self_closing {
+                        let end = format!("</{}", element.name);
+                        let index = rest.to_ascii_lowercase().find(&end).unwrap_or(rest.len());
+                        rest = &rest[index..];
+                    }
+
+                    open_element(&mut stack, element, self_closing);
+                }
+                Tag::End(name) => {
+                    if let Some(index) = stack.iter().rposition(|element| element.name == name) {
+                        close_to(&mut stack, index);
+                    }
+                }
+            }
+
+            continue;
+        }
+
+        let first = rest.chars().next().map_or(1, char::len_utf8);
+        let end = rest[first..]
+            .find('<')
+            .map_or(rest.len(), |index| index + first);
+        let text = decode_entities(&rest[..end]);
+        let children = &mut stack.last_mut().unwrap().children;
+
+        match children.last_mut() {
+            Some(Dom::Text(last)) => last.push_str(&text),
+            _ => children.push(Dom::Text(text)),
+        }
+
+        rest = &rest[end..];
+    }
+
+    close_to(&mut stack, 1);
+    stack.pop().unwrap()
+}
+
+fn open_element(stack: &mut Vec<Element>, element: Element, self_closing: bool) {
+    match element.name.as_str() {
+        "li" => close_implied(stack, &["li"], |name| name == "ul" || name == "ol"),
+        "dt" | "dd" => close_implied(stack, &["dt", "dd"], |name| name == "dl"),
+        "td" | "th" => close_implied(stack, &["td", "th"], |name| name == "tr" || name == "table"),
+        "tr" => close_implied(stack, &["tr"], |name| {
+            matches!(name, "table" | "thead" | "tbody" | "tfoot")
+        }),
+        "thead" | "tbody" | "tfoot" => {
+            close_implied(stack, &["thead", "tbody", "tfoot"], |name| name == "table")
+        }
+        name if is_block(name) => close_implied(stack, &["p"], is_block),
+        _ => {}
+    }
+
+    if self_closing || is_void(&element.name) {
+        stack
+            .last_mut()
+            .unwrap()
+            .children
+            .push(Dom::Element(element));
+    } else {
+        stack.push(element);
+    }
+}
+
+/// Closes the nearest open element named one of `names`, unless an element
+/// for which `is_scope` holds is open within it.
+fn close_implied<F>(stack: &mut Vec<Element>, names: &[&str], is_scope: F)
+where
+    F: Fn(&str) -> bool,
+{
+    for index in (1..stack.len()).rev() {
+        let name = stack[index].name.as_str();
+
+        if names.contains(&name) {
+            close_to(stack, index);
+            return;
+        }
+
+        if is_scope(name) {
+            return;
+        }
+    }
+}
+
+/// Closes the element at `index` of the stack and every element within it,
+/// never closing the root.
+fn close_to(stack: &mut Vec<Element>, index: usize) {
+    while stack.len() > index.max(1) {
+        let element = stack.pop().unwrap();
+        stack
+            .last_mut()
+            .unwrap()
+            .children
+            .push(Dom::Element(element));
+    }
+}
+
+/// Parses a start or end tag at the start of `html`, returning it and the
+/// HTML after it.
+fn parse_tag(html: &str) -> Option<(Tag, &str)> {
+    let rest = html.strip_prefix('<')?;
+    let (is_end, rest) = match rest.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+
+    if !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let name_len = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == ':'))
+        .unwrap_or(rest.len());
+    let mut element = Element::new(&rest[..name_len].to_ascii_lowercase());
+    let mut rest = &rest[name_len..];
+    let mut self_closing = false;
+
+    loop {
+        rest = rest.trim_start();
+
+        if let Some(after) = rest.strip_prefix('>') {
+            rest = after;
+            break;
+        }
+
+        if let Some(after) = rest.strip_prefix("/>") {
+            rest = after;
+            self_closing = true;
+            break;
+        }
+
+        let name_len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/'))
+            .unwrap_or(rest.len());
+
+        if rest.is_empty() {
+            return None;
+        } else if name_len == 0 {
+            rest = &rest[1..];
+            continue;
+        }
+
+        let name = rest[..name_len].to_ascii_lowercase();
+        rest = rest[name_len..].trim_start();
+
+        let value = match rest.strip_prefix('=') {
+            Some(after) => {
+                let after = after.trim_start();
+
+                match after.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let end = after[1..].find(quote)? + 1;
+                        rest = &after[end + 1..];
+                        decode_entities(&after[1..end])
+                    }
+                    _ => {
+                        let end = after
+                            .find(|c: char| c.is_whitespace() || c == '>')
+                            .unwrap_or(after.len());
+                        rest = &after[end..];
+                        decode_entities(&after[..end])
+                    }
+                }
+            }
+            None => String::new(),
+        };
+
+        element.attrs.push((name, value));
+    }
+
+    if is_end {
+        Some((Tag::End(element.name), rest))
+    } else {
+        Some((
+            Tag::Start {
+                element,
+                self_closing,
+            },
+            rest,
+        ))
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = rest.find('&') {
+        decoded.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        match decode_entity(rest) {
+            Some((char, len)) => {
+                decoded.push(char);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Decodes the character reference at the start of `text`, e.g. `&amp;` or
+/// `&#x4e2d;`, returning the character and the length of the reference.
+fn decode_entity(text: &str) -> Option<(char, usize)> {
+    let (end, _semicolon) = text.char_indices().take(12).find(|(_index, c)| *c == ';')?;
+    let name = &text[1..end];
+
+    let char = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(|c| c == 'x' || c == 'X') {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+
+            char::from_u32(code).filter(|char| *char != '\0')?
+        }
+    };
+
+    Some((char, end + 1))
+}
+
+fn blocks_from_dom<'a, I>(children: I) -> Vec<Node>
+where
+    I: IntoIterator<Item = &'a Dom>,
+{
+    let mut blocks = vec![];
+    let mut inline = vec![];
+
+    for child in children {
+        match child {
+            Dom::Element(element) if is_ignored(&element.name) => {}
+            Dom::Element(element) if is_block(&element.name) => {
+                push_paragraph(&mut inline, &mut blocks);
+                blocks.extend(blocks_from_element(element));
+            }
+            _ => inline_from_dom(child, &[], &mut inline),
+        }
+    }
+
+    push_paragraph(&mut inline, &mut blocks);
+    blocks
+}
+
+/// Wraps loose inline content in a paragraph, unless it is only whitespace.
+fn push_paragraph(inline: &mut Vec<Node>, blocks: &mut Vec<Node>) {
+    let content = finish_inline(std::mem::take(inline));
+
+    if !content.is_empty() {
+        blocks.push(textblock(NodeType::Paragraph, content));
+    }
+}
+
+fn blocks_from_element(element: &Element) -> Vec<Node> {
+    let name = element.name.as_str();
+
+    match name {
+        "p" if element.children.iter().any(is_block_dom) => blocks_from_dom(&element.children),
+        "p" => vec![textblock(
+            NodeType::Paragraph,
+            inline_content(&element.children),
+        )],
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            let mut heading = textblock(NodeType::Heading, inline_content(&element.children));
+            heading.attrs = Some(attrs(vec![(
+                "level",
+                Value::from(name[1..].parse::<u64>().unwrap()),
+            )]));
+
+            vec![heading]
+        }
+        "blockquote" => vec![container(
+            NodeType::Blockquote,
+            blocks_from_dom(&element.children),
+        )],
+        "ul" | "ol" => vec![list_from_element(element)],
+        "pre" => vec![code_block_from_element(element)],
+        "hr" => vec![Node::new(NodeType::HorizontalRule)],
+        "table" | "thead" | "tbody" | "tfoot" | "tr" => {
+            let mut table = Node::new(NodeType::Table);
+
+            if name == "tr" {
+                table.content.push(row_from_element(element));
+            } else {
+                collect_rows(element, &mut table.content);
+            }
+
+            if table.content.is_empty() {
+                vec![]
+            } else {
+                vec![table]
+            }
+        }
+        _ => blocks_from_dom(&element.children),
+    }
+}
+
+fn is_block_dom(dom: &Dom) -> bool {
+    matches!(dom, Dom::Element(element) if is_block(&element.name))
+}
+
+fn list_from_element(element: &Element) -> Node {
+    let mut list = if element.name == "ol" {
+        let start = element
+            .attr("start")
+            .and_then(|start| start.trim().parse::<u64>().ok())
+            .unwrap_or(1);
+
+        Node {
+            attrs: Some(attrs(vec![("start", Value::from(start))])),
+            ..Node::new(NodeType::OrderedList)
+        }
+    } else {
+        Node::new(NodeType::BulletList)
+    };
+    let mut loose: Vec<&Dom> = vec![];
+
+    for child in &element.children {
+        match child {
+            Dom::Element(item) if item.name == "li" => {
+                push_list_item(&mut loose, &mut list);
+                list.content.push(container(
+                    NodeType::ListItem,
+                    blocks_from_dom(&item.children),
+                ));
+            }
+            // A list written directly in a list belongs to the item before it.
+            Dom::Element(nested)
+                if (nested.name == "ul" || nested.name == "ol")
+                    && loose.is_empty()
+                    && !list.content.is_empty() =>
+            {
+                let item = list.content.last_mut().unwrap();
+                item.content.push(list_from_element(nested));
+            }
+            _ => loose.push(child),
+        }
+    }
+
+    push_list_item(&mut loose, &mut list);
+
+    if list.content.is_empty() {
+        list.content.push(container(NodeType::ListItem, vec![]));
+    }
+
+    list
+}
+
+/// Wraps content of a list outside of any `<li>` in a list item, unless it is
+/// only whitespace.
+fn push_list_item(loose: &mut Vec<&Dom>, list: &mut Node) {
+    let blocks = blocks_from_dom(loose.drain(..));
+
+    if !blocks.is_empty() {
+        list.content.push(container(NodeType::ListItem, blocks));
+    }
+}
+
+fn code_block_from_element(pre: &Element) -> Node {
+    let mut text = String::new();
+
+    for child in &pre.children {
+        text_content(child, &mut text);
+    }
+
+    // Browsers drop a newline right after `<pre>`, and editors add one before
+    // `</pre>`.
+    let text = text.strip_prefix('\n').unwrap_or(&text);
+    let text = text.strip_suffix('\n').unwrap_or(text);
+
+    let language = pre.children.iter().find_map(|child| match child {
+        Dom::Element(code) if code.name == "code" => {
+            code.attr("class")?.split_whitespace().find_map(|class| {
+                class
+                    .strip_prefix("language-")
+                    .or_else(|| class.strip_prefix("lang-"))
+            })
+        }
+        _ => None,
+    });
+
+    let mut code_block = Node::new(NodeType::CodeBlock);
+
+    if let Some(language) = language {
+        code_block.attrs = Some(attrs(vec![("language", Value::from(language))]));
+    }
+
+    if !text.is_empty() {
+        code_block.content.push(Node::text(text));
+    }
+
+    code_block
+}
+
+fn text_content(dom: &Dom, text: &mut String) {
+    match dom {
+        Dom::Text(content) => text.push_str(content),
+        Dom::Element(element) if element.name == "br" => text.push('\n'),
+        Dom::Element(element) if is_ignored(&element.name) => {}
+        Dom::Element(element) => {
+            for child in &element.children {
+                text_content(child, text);
+            }
+        }
+    }
+}
+
+fn collect_rows(element: &Element, rows: &mut Vec<Node>) {
+    for child in &element.children {
+        match child {
+            Dom::Element(row) if row.name == "tr" => rows.push(row_from_element(row)),
+            Dom::Element(section)
+                if matches!(section.name.as_str(), "thead" | "tbody" | "tfoot") =>
+            {
+                collect_rows(section, rows)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn row_from_element(tr: &Element) -> Node {
+    let mut row = Node::new(NodeType::TableRow);
+
+    for child in &tr.children {
+        let cell = match child {
+            Dom::Element(cell) if cell.name == "td" || cell.name == "th" => cell,
+            _ => continue,
+        };
+        let cell_type = if cell.name == "th" {
+            NodeType::TableHeader
+        } else {
+            NodeType::TableCell
+        };
+        let spans = ["colspan", "rowspan"]
+            .iter()
+            .filter_map(|key| match cell.attr(key)?.trim().parse::<u64>() {
+                Ok(span) if span > 1 => Some((*key, Value::from(span))),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut node = container(cell_type, blocks_from_dom(&cell.children));
+
+        if !spans.is_empty() {
+            node.attrs = Some(attrs(spans));
+        }
+
+        row.content.push(node);
+    }
+
+    row
+}
+
+fn inline_content(children: &[Dom]) -> Vec<Node> {
+    let mut nodes = vec![];
+
+    for child in children {
+        inline_from_dom(child, &[], &mut nodes);
+    }
+
+    finish_inline(nodes)
+}
+
+fn inline_from_dom(dom: &Dom, marks: &[Mark], nodes: &mut Vec<Node>) {
+    let element = match dom {
+        Dom::Text(text) => {
+            let mut node = Node::text(&collapse_whitespace(text));
+            node.marks = marks.to_vec();
+            nodes.push(node);
+
+            return;
+        }
+        Dom::Element(element) => element,
+    };
+
+    let mark = match element.name.as_str() {
+        "br" => {
+            nodes.push(Node::new(NodeType::HardBreak));
+
+            return;
+        }
+        "img" => {
+            if let Some(mut image) = image_from_element(element) {
+                image.marks = marks.to_vec();
+                nodes.push(image);
+            }
+
+            return;
+        }
+        name if is_ignored(name) => return,
+        "strong" | "b" => Some(Mark::new(MarkType::Bold)),
+        "em" | "i" => Some(Mark::new(MarkType::Italic)),
+        "u" | "ins" => Some(Mark::new(MarkType::Underline)),
+        "s" | "strike" | "del" => Some(Mark::new(MarkType::Strike)),
+        "code" | "kbd" | "samp" | "tt" => Some(Mark::new(MarkType::Code)),
+        "a" => element
+            .attr("href")
+            .filter(|href| is_safe_url(href))
+            .map(Mark::link),
+        _ => None,
+    };
+
+    let mut marks = marks.to_vec();
+
+    if let Some(mark) = mark {
+        if !marks.iter().any(|other| other.mark_type == mark.mark_type) {
+            marks.push(mark);
+            sort_marks(&mut marks);
+        }
+    }
+
+    for child in &element.children {
+        inline_from_dom(child, &marks, nodes);
+    }
+}
+
+fn image_from_element(img: &Element) -> Option<Node> {
+    let src = img.attr("src").filter(|src| is_safe_url(src))?;
+    let mut entries = vec![("src", Value::from(src))];
+
+    for key in ["alt", "title"] {
+        if let Some(value) = img.attr(key) {
+            entries.push((key, Value::from(value)));
+        }
+    }
+
+    Some(Node {
+        attrs: Some(attrs(entries)),
+        ..Node::new(NodeType::Image)
+    })
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+
+    for char in text.chars() {
+        if char.is_ascii_whitespace() {
+            if !collapsed.ends_with(' ') {
+                collapsed.push(' ');
+            }
+        } else {
+            collapsed.push(char);
+        }
+    }
+
+    collapsed
+}
+
+/// Removes the whitespace HTML does not render: at the start and end of a line
+/// and after other whitespace.
+fn finish_inline(nodes: Vec<Node>) -> Vec<Node> {
+    let mut nodes = normalize_inline(nodes);
+    let mut strip = true;
+
+    for node in &mut nodes {
+        match (&node.node_type, &mut node.text) {
+            (NodeType::Text, Some(text)) => {
+                if strip {
+                    *text = text.trim_start_matches(' ').to_string();
+                }
+
+                if !text.is_empty() {
+                    strip = text.ends_with(' ');
+                }
+            }
+            (NodeType::HardBreak, _text) => strip = true,
+            _ => strip = false,
+        }
+    }
+
+    strip = true;
+
+    for node in nodes.iter_mut().rev() {
+        match (&node.node_type, &mut node.text) {
+            (NodeType::Text, Some(text)) => {
+                if strip {
+                    text.truncate(text.trim_end_matches(' ').len());
+                }
+
+                if !text.is_empty() {
+                    strip = false;
+                }
+            }
+            (NodeType::HardBreak, _text) => strip = true,
+            _ => strip = false,
+        }
+    }
+
+    normalize_inline(nodes)
+}
+
+fn textblock(node_type: NodeType, content: Vec<Node>) -> Node {
+    Node {
+        content,
+        ..Node::new(node_type)
+    }
+}
+
+/// Builds a node holding blocks, with an empty paragraph if there are none.
+fn container(node_type: NodeType, mut content: Vec<Node>) -> Node {
+    if content.is_empty() {
+        content.push(Node::new(NodeType::Paragraph));
+    }
+
+    textblock(node_type, content)
+}
+
+fn attrs(entries: Vec<(&str, Value)>) -> Map<String, Value> {
+    entries
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_presenter::value::json_codec::JsonCodec;
+    use serde_json::json;
+
+    fn doc() -> ProsemirrorState {
+        let json = json!({
+            "type": "doc",
+            "content": [
+                {
+                    "type": "heading",
+                    "attrs": { "level": 2 },
+                    "content": [{ "type": "text", "text": "Weekly <report>" }]
+                },
+                {
+                    "type": "paragraph",
+                    "content": [
+                        { "type": "text", "text": "Hello " },
+                        {
+                            "type": "text",
+                            "text": "Jet",
+                            "marks": [
+                                { "type": "link", "attrs": { "href": "https://jet.work/?a=1&b=2" } },
+                                { "type": "bold" }
+                            ]
+                        },
+                        { "type": "hardBreak" },
+                        { "type": "text", "text": "under", "marks": [{ "type": "underline" }] },
+                        { "type": "text", "text": " " },
+                        { "type": "text", "text": "x < y", "marks": [{ "type": "code" }] }
+                    ]
+                },
+                {
+                    "type": "orderedList",
+                    "attrs": { "start": 2 },
+                    "content": [
+                        {
+                            "type": "listItem",
+                            "content": [
+                                { "type": "paragraph", "content": [{ "type": "text", "text": "two" }] },
+                                {
+                                    "type": "bulletList",
+                                    "content": [
+                                        {
+                                            "type": "listItem",
+                                            "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "nested" }] }]
+                                        }
+                                    ]
+                                }
+                            ]
+                        }
+                    ]
+                },
+                {
+                    "type": "blockquote",
+                    "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "quoted" }] }]
+                },
+                {
+                    "type": "codeBlock",
+                    "attrs": { "language": "html" },
+                    "content": [{ "type": "text", "text": "<p>\n  hi\n</p>" }]
+                },
+                { "type": "horizontalRule" },
+                {
+                    "type": "table",
+                    "content": [
+                        {
+                            "type": "tableRow",
+                            "content": [
+                                {
+                                    "type": "tableHeader",
+                                    "attrs": { "colspan": 2 },
+                                    "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "Name" }] }]
+                                }
+                            ]
+                        },
+                        {
+                            "type": "tableRow",
+                            "content": [
+                                {
+                                    "type": "tableCell",
+                                    "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "a" }] }]
+                                },
+                                { "type": "tableCell", "content": [{ "type": "paragraph" }] }
+                            ]
+                        }
+                    ]
+                },
+                {
+                    "type": "paragraph",
+                    "content": [
+                        {
+                            "type": "image",
+                            "attrs": { "src": "https://jet.work/logo.png", "alt": "\"logo\"" }
+                        }
+                    ]
+                }
+            ]
+        });
+
+        ProsemirrorState::from_json(&json).unwrap()
+    }
+
+    const HTML: &str = concat!(
+        "<h2>Weekly &lt;report&gt;</h2>",
+        "<p>Hello <a href=\"https://jet.work/?a=1&amp;b=2\"><strong>Jet</strong></a><br>",
+        "<u>under</u> <code>x &lt; y</code></p>",
+        "<ol start=\"2\"><li><p>two</p><ul><li><p>nested</p></li></ul></li></ol>",
+        "<blockquote><p>quoted</p></blockquote>",
+        "<pre><code class=\"language-html\">&lt;p&gt;\n  hi\n&lt;/p&gt;</code></pre>",
+        "<hr>",
+        "<table><tbody><tr><th colspan=\"2\"><p>Name</p></th></tr>",
+        "<tr><td><p>a</p></td><td><p></p></td></tr></tbody></table>",
+        "<p><img src=\"https://jet.work/logo.png\" alt=\"&quot;logo&quot;\"></p>",
+    );
+
+    #[test]
+    fn test_to_html() {
+        assert_eq!(doc().to_html(), HTML);
+
+        // unsafe urls
+        {
+            let json = json!({
+                "type": "doc",
+                "content": [
+                    {
+                        "type": "paragraph",
+                        "content": [
+                            {
+                                "type": "text",
+                                "text": "click",
+                                "marks": [{ "type": "link", "attrs": { "href": " javascript:alert(1)" } }]
+                            },
+                            { "type": "image", "attrs": { "src": "data:text/html,<script>" } }
+                        ]
+                    }
+                ]
+            });
+            let state = ProsemirrorState::from_json(&json).unwrap();
+
+            assert_eq!(state.to_html(), "<p>click</p>");
+        }
+    }
+
+    #[test]
+    fn test_from_html() {
+        assert_eq!(ProsemirrorState::from_html(HTML), doc());
+
+        {
+            let html = r#"<!DOCTYPE html>
+                <html><head><title>Report</title><style>p { color: red }</style></head>
+                <body>
+                  <!-- a comment -->
+                  <div>Loose <B>text</B>
+                    <p>Unclosed &amp; <span style="x">spaced</span>
+                    <ul>
+                      <li>one
+                      <li><em>two</em></li>
+                    </ul>
+                  </div>
+                  <script>document.write("<p>gone</p>")</script>
+                  <a href="javascript:alert(1)" onclick="alert(2)">link</a> &#x4e2d;&#25991;&nbsp;!
+                </body></html>"#;
+
+            assert_eq!(
+                ProsemirrorState::from_html(html).to_json(),
+                json!({
+                    "type": "doc",
+                    "content": [
+                        {
+                            "type": "paragraph",
+                            "content": [
+                                { "type": "text", "text": "Loose " },
+                                { "type": "text", "text": "text", "marks": [{ "type": "bold" }] }
+                            ]
+                        },
+                        {
+                            "type": "paragraph",
+                            "content": [{ "type": "text", "text": "Unclosed & spaced" }]
+                        },
+                        {
+                            "type": "bulletList",
+                            "content": [
+                                {
+                                    "type": "listItem",
+                                    "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "one" }] }]
+                                },
+                                {
+                                    "type": "listItem",
+                                    "content": [
+                                        {
+                                            "type": "paragraph",
+                                            "content": [{ "type": "text", "text": "two", "marks": [{ "type": "italic" }] }]
+                                        }
+                                    ]
+                                }
+                            ]
+                        },
+                        {
+                            "type": "paragraph",
+                            "content": [{ "type": "text", "text": "link 中文\u{a0}!" }]
+                        }
+                    ]
+                })
+            );
+        }
+
+        // empty document
+        {
+            assert_eq!(
+                ProsemirrorState::from_html("  <br/>").to_json(),
+                json!({
+                    "type": "doc",
+                    "content": [{ "type": "paragraph", "content": [{ "type": "hardBreak" }] }]
+                })
+            );
+            assert_eq!(
+                ProsemirrorState::from_html("").to_json(),
+                json!({ "type": "doc", "content": [{ "type": "paragraph" }] })
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_safe_url() {
+        assert!(is_safe_url("https://jet.work"));
+        assert!(is_safe_url("MAILTO:a@jet.work"));
+        assert!(is_safe_url("/path?next=javascript:x"));
+        assert!(is_safe_url("#top"));
+        assert!(!is_safe_url("javascript:alert(1)"));
+        assert!(!is_safe_url(" JavaScript:alert(1)"));
+        assert!(!is_safe_url("java\tscript:alert(1)"));
+        assert!(!is_safe_url("data:text/html,<script>"));
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        assert_eq!(
+            ProsemirrorState::from_html("<b>é</b>").to_markdown(),
+            "**é**"
+        );
+        assert_eq!(ProsemirrorState::from_html("中文").to_plain_text(), "中文");
+        assert_eq!(
+            ProsemirrorState::from_html("<p>x</p>中文<b>字</b>").to_plain_text(),
+            "x\n中文字"
+        );
+    }
+
+    #[test]
+    fn test_malformed() {
+        for html in [
+            "<",
+            "<p",
+            "<p class=\"a",
+            "</p>",
+            "<!--",
+            "<a href=x>",
+            "&",
+            "&#;",
+            "&#xffffffff;",
+            "<table></table>",
+            "<ul></ul>",
+            "<tr><td>a",
+            "<pre>",
+            "<b><i>a</b>b</i>",
+            "<script>",
+            "<li>a",
+            "<h7>a</h7>",
+        ] {
+            let state = ProsemirrorState::from_html(html);
+
+            assert_eq!(
+                ProsemirrorState::from_json(&state.to_json()).unwrap(),
+                state
+            );
+        }
+    }
+}
@@ -0,0 +1,1644 @@
+//! Conversions between ProseMirror documents and Markdown.
+//!
+//! Documents are written as CommonMark with GFM tables and strikethrough, and
+//! the same subset is parsed back: ATX headings, paragraphs, block quotes,
+//! bullet and ordered lists, fenced code blocks, thematic breaks, tables, and
+//! emphasis, strong, strikethrough, code span, link and image inlines.
+//! Underline has no Markdown syntax and is dropped, as are links and images
+//! with unsafe URLs.
+
+use serde_json::{Map, Value};
+
+use super::{
+    html::is_safe_url,
+    node::{normalize_inline, sort_marks, Mark, MarkType, Node, NodeType},
+    ProsemirrorState,
+};
+
+impl ProsemirrorState {
+    pub fn to_markdown(&self) -> String {
+        blocks_to_markdown(&self.doc.content)
+    }
+
+    /// Parses a Markdown document. Anything that is not recognised is kept as
+    /// paragraph text.
+    pub fn from_markdown(markdown: &str) -> Self {
+        let lines = markdown.lines().map(expand_tabs).collect::<Vec<_>>();
+
+        let mut doc = Node::new(NodeType::Doc);
+        doc.content = parse_blocks(&lines);
+
+        if doc.content.is_empty() {
+            doc.content.push(Node::new(NodeType::Paragraph));
+        }
+
+        ProsemirrorState::new(doc)
+    }
+}
+
+fn blocks_to_markdown(nodes: &[Node]) -> String {
+    nodes
+        .iter()
+        .map(block_to_markdown)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn block_to_markdown(node: &Node) -> String {
+    match node.node_type {
+        NodeType::Paragraph => inline_to_markdown(&node.content),
+        NodeType::Heading => {
+            let level = node
+                .attr("level")
+                .and_then(Value::as_u64)
+                .unwrap_or(1)
+                .clamp(1, 6);
+
+            format!(
+                "{} {}",
+                "#".repeat(level as usize),
+                single_line(inline_to_markdown(&node.content))
+            )
+        }
+        NodeType::Blockquote => prefix_lines(&blocks_to_markdown(&node.content), "> ", "> "),
+        NodeType::BulletList => list_to_markdown(node, |_index| "- ".to_string()),
+        NodeType::OrderedList => {
+            let start = node.attr("start").and_then(Value::as_u64).unwrap_or(1);
+
+            list_to_markdown(node, |index| format!("{}. ", start + index as u64))
+        }
+        NodeType::CodeBlock => {
+            let text = node.to_plain_text();
+            let language = node
+                .attr("language")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let fence = "`".repeat(longest_run(&text, '`').max(2) + 1);
+
+            format!("{}{}\n{}\n{}", fence, language, text, fence)
+        }
+        NodeType::HorizontalRule => "---".to_string(),
+        NodeType::Table => table_to_markdown(node),
+        _ if node.node_type.is_inline() => inline_to_markdown(std::slice::from_ref(node)),
+        _ if !node.content.is_empty()
+            && node.content.iter().all(|child| child.node_type.is_inline()) =>
+        {
+            inline_to_markdown(&node.content)
+        }
+        _ => blocks_to_markdown(&node.content),
+    }
+}
+
+fn list_to_markdown<F>(list: &Node, marker: F) -> String
+where
+    F: Fn(usize) -> String,
+{
+    list.content
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let marker = marker(index);
+            let indent = " ".repeat(marker.len());
+
+            prefix_lines(&blocks_to_markdown(&item.content), &marker, &indent)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn table_to_markdown(table: &Node) -> String {
+    let rows = table
+        .content
+        .iter()
+        .map(|row| {
+            row.content
+                .iter()
+                .map(|cell| single_line(blocks_to_markdown(&cell.content)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+    if columns == 0 {
+        return String::new();
+    }
+
+    let line = |cells: &[String]| {
+        let cells = (0..columns)
+            .map(|index| cells.get(index).map(String::as_str).unwrap_or_default())
+            .collect::<Vec<_>>();
+
+        format!("| {} |", cells.join(" | "))
+    };
+
+    let mut lines = vec![line(&rows[0]), format!("|{}", " --- |".repeat(columns))];
+    lines.extend(rows[1..].iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+/// Writes inline nodes, keeping a mark open across adjacent nodes that share
+/// it so that e.g. a bold run with an italic word is not split up.
+fn inline_to_markdown(nodes: &[Node]) -> String {
+    let mut markdown = String::new();
+    let mut open: Vec<&Mark> = vec![];
+    let mut pending = String::new();
+
+    for node in nodes {
+        let marks = markdown_marks(node);
+        let kept = open
+            .iter()
+            .zip(&marks)
+            .take_while(|(open, mark)| open == mark)
+            .count();
+
+        let (leading, core, trailing) = match node.node_type {
+            NodeType::Text => {
+                let text = node.text.as_deref().unwrap_or_default();
+                let (leading, core, trailing) = split_whitespace(text);
+
+                // whitespace is kept pending so that the marks it does not
+                // have are closed before it, and no mark is opened for it
+                if core.is_empty() {
+                    for mark in open.drain(kept..).rev() {
+                        markdown.push_str(&closing_delimiter(mark));
+                    }
+
+                    pending.push_str(text);
+                    continue;
+                }
+
+                let core = if node.has_mark(&MarkType::Code) {
+                    code_span(core)
+                } else {
+                    escape(core)
+                };
+
+                (leading, core, trailing)
+            }
+            NodeType::HardBreak => {
+                markdown.push_str(&pending);
+                markdown.push_str("\\\n");
+                pending.clear();
+                continue;
+            }
+            NodeType::Image => match image_to_markdown(node) {
+                Some(image) => ("", image, ""),
+                None => continue,
+            },
+            _ => ("", escape(&node.to_plain_text()), ""),
+        };
+
+        for mark in open.drain(kept..).rev() {
+            markdown.push_str(&closing_delimiter(mark));
+        }
+
+        markdown.push_str(&pending);
+        markdown.push_str(leading);
+
+        for mark in &marks[kept..] {
+            markdown.push_str(opening_delimiter(mark));
+            open.push(*mark);
+        }
+
+        markdown.push_str(&core);
+        pending = trailing.to_string();
+    }
+
+    for mark in open.iter().rev() {
+        markdown.push_str(&closing_delimiter(mark));
+    }
+
+    markdown.push_str(&pending);
+    escape_line_starts(&markdown)
+}
+
+/// Returns the marks written with delimiters, in the order they are opened.
+fn markdown_marks(node: &Node) -> Vec<&Mark> {
+    let mut marks = node
+        .marks
+        .iter()
+        .filter(|mark| match mark.mark_type {
+            MarkType::Bold | MarkType::Italic | MarkType::Strike => true,
+            MarkType::Link => link_href(mark).is_some(),
+            _ => false,
+        })
+        .collect::<Vec<_>>();
+    marks.sort_by_key(|mark| mark_rank(mark));
+    marks
+}
+
+fn image_to_markdown(image: &Node) -> Option<String> {
+    let src = image
+        .attr("src")
+        .and_then(Value::as_str)
+        .filter(|src| is_safe_url(src))?;
+    let alt = image
+        .attr("alt")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let title = image.attr("title").and_then(Value::as_str);
+
+    Some(format!("![{}]({})", escape(alt), destination(src, title)))
+}
+
+fn mark_rank(mark: &Mark) -> usize {
+    match mark.mark_type {
+        MarkType::Link => 0,
+        MarkType::Bold => 1,
+        MarkType::Italic => 2,
+        _ => 3,
+    }
+}
+
+fn link_href(mark: &Mark) -> Option<&str> {
+    mark.attr("href")
+        .and_then(Value::as_str)
+        .filter(|href| is_safe_url(href))
+}
+
+fn opening_delimiter(mark: &Mark) -> &'static str {
+    match mark.mark_type {
+        MarkType::Link => "[",
+        MarkType::Bold => "**",
+        MarkType::Italic => "*",
+        _ => "~~",
+    }
+}
+
+fn closing_delimiter(mark: &Mark) -> String {
+    match mark.mark_type {
+        MarkType::Link => format!(
+            "]({})",
+            destination(
+                link_href(mark).unwrap_or_default(),
+                mark.attr("title").and_then(Value::as_str)
+            )
+        ),
+        _ => opening_delimiter(mark).to_string(),
+    }
+}
+
+fn destination(url: &str, title: Option<&str>) -> String {
+    let url = if url.is_empty() || url.contains(|c: char| c.is_whitespace() || "()<>".contains(c)) {
+        format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+    } else {
+        url.to_string()
+    };
+
+    match title {
+        Some(title) => format!("{} \"{}\"", url, title.replace('"', "\\\"")),
+        None => url,
+    }
+}
+
+fn code_span(text: &str) -> String {
+    let ticks = "`".repeat(longest_run(text, '`') + 1);
+    let padding = if text.starts_with('`') || text.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+
+    format!("{}{}{}{}{}", ticks, padding, text, padding, ticks)
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for char in text.chars() {
+        if "\\`*_[]~<>|".contains(char) {
+            escaped.push('\\');
+        }
+
+        escaped.push(char);
+    }
+
+    escaped
+}
+
+/// Escapes text at the start of a line that would otherwise begin a heading,
+/// a list item or a thematic break.
+fn escape_line_starts(markdown: &str) -> String {
+    markdown
+        .split('\n')
+        .map(|line| {
+            let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+            if line.starts_with(|c: char| "#-+".contains(c)) {
+                format!("\\{}", line)
+            } else if digits > 0 && line[digits..].starts_with(['.', ')']) {
+                format!("{}\\{}", &line[..digits], &line[digits..])
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn split_whitespace(text: &str) -> (&str, &str, &str) {
+    let start = text.len() - text.trim_start().len();
+    let end = start + text.trim().len();
+
+    (&text[..start], &text[start..end], &text[end..])
+}
+
+fn longest_run(text: &str, char: char) -> usize {
+    text.split(|c| c != char).map(str::len).max().unwrap_or(0)
+}
+
+fn prefix_lines(text: &str, first: &str, rest: &str) -> String {
+    text.split('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            let prefix = if index == 0 { first } else { rest };
+
+            if line.is_empty() {
+                prefix.trim_end().to_string()
+            } else {
+                format!("{}{}", prefix, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Joins the lines of inline Markdown for a heading or table cell, which can
+/// not span lines.
+fn single_line(markdown: String) -> String {
+    markdown.replace("\\\n", " ").replace('\n', " ")
+}
+
+fn expand_tabs(line: &str) -> String {
+    let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+    let mut expanded = String::with_capacity(line.len());
+
+    for char in line[..indent].chars() {
+        match char {
+            '\t' => expanded.push_str(&" ".repeat(4 - expanded.len() % 4)),
+            _ => expanded.push(char),
+        }
+    }
+
+    expanded.push_str(&line[indent..]);
+    expanded
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+fn parse_blocks(lines: &[String]) -> Vec<Node> {
+    let mut blocks = vec![];
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = &lines[index];
+        let trimmed = line.trim_start();
+
+        if trimmed.is_empty() {
+            index += 1;
+            continue;
+        }
+
+        let (block, next) = if let Some(fence) = Fence::parse(line) {
+            parse_code_block(lines, index, &fence)
+        } else if is_thematic_break(trimmed) {
+            (Node::new(NodeType::HorizontalRule), index + 1)
+        } else if let Some((level, text)) = parse_heading(trimmed) {
+            let mut heading = textblock(NodeType::Heading, parse_inline(text));
+            heading.attrs = Some(attrs(vec![("level", Value::from(level))]));
+
+            (heading, index + 1)
+        } else if trimmed.starts_with('>') {
+            parse_blockquote(lines, index)
+        } else if let Some(marker) = ListMarker::parse(line) {
+            parse_list(lines, index, &marker)
+        } else if is_table_start(lines, index) {
+            parse_table(lines, index)
+        } else {
+            parse_paragraph(lines, index)
+        };
+
+        blocks.push(block);
+        index = next;
+    }
+
+    blocks
+}
+
+/// Whether `line` starts a block that interrupts a paragraph.
+fn starts_block(line: &str) -> bool {
+    let trimmed = line.trim_start();
+
+    Fence::parse(line).is_some()
+        || is_thematic_break(trimmed)
+        || parse_heading(trimmed).is_some()
+        || trimmed.starts_with('>')
+        || matches!(ListMarker::parse(line), Some(marker) if !marker.ordered || marker.start == 1)
+}
+
+fn parse_paragraph(lines: &[String], start: usize) -> (Node, usize) {
+    let mut end = start + 1;
+
+    while end < lines.len()
+        && !is_blank(&lines[end])
+        && !starts_block(&lines[end])
+        && !is_table_start(lines, end)
+    {
+        end += 1;
+    }
+
+    let text = lines[start..end]
+        .iter()
+        .map(|line| line.trim_start())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    (
+        textblock(NodeType::Paragraph, parse_inline(text.trim_end())),
+        end,
+    )
+}
+
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.len() - line.trim_start_matches('#').len();
+    let rest = &line[level..];
+
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+
+    let text = rest.trim();
+    let without_closing = text.trim_end_matches('#');
+
+    if without_closing.is_empty() {
+        Some((level, without_closing))
+    } else if without_closing.ends_with([' ', '\t']) {
+        Some((level, without_closing.trim_end()))
+    } else {
+        Some((level, text))
+    }
+}
+
+fn is_thematic_break(line: &str) -> bool {
+    let chars = line
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<Vec<_>>();
+
+    chars.len() >= 3 && matches!(chars[0], '-' | '*' | '_') && chars.iter().all(|c| *c == chars[0])
+}
+
+struct Fence {
+    char: char,
+    len: usize,
+    indent: usize,
+    language: String,
+}
+
+impl Fence {
+    fn parse(line: &str) -> Option<Fence> {
+        let indent = indent_of(line);
+        let rest = &line[indent..];
+        let char = rest.chars().next().filter(|c| *c == '`' || *c == '~')?;
+        let len = rest.len() - rest.trim_start_matches(char).len();
+        let info = rest[len..].trim();
+
+        if indent > 3 || len < 3 || (char == '`' && info.contains('`')) {
+            return None;
+        }
+
+        Some(Fence {
+            char,
+            len,
+            indent,
+            language: info
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+        })
+    }
+
+    fn is_closed_by(&self, line: &str) -> bool {
+        let trimmed = line.trim_start();
+        let len = trimmed.len() - trimmed.trim_start_matches(self.char).len();
+
+        len >= self.len && trimmed[len..].trim().is_empty()
+    }
+}
+
+fn parse_code_block(lines: &[String], start: usize, fence: &Fence) -> (Node, usize) {
+    let mut end = start + 1;
+
+    while end < lines.len() && !fence.is_closed_by(&lines[end]) {
+        end += 1;
+    }
+
+    let text = lines[start + 1..end]
+        .iter()
+        .map(|line| &line[indent_of(line).min(fence.indent)..])
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut code_block = Node::new(NodeType::CodeBlock);
+
+    if !fence.language.is_empty() {
+        code_block.attrs = Some(attrs(vec![(
+            "language",
+            Value::String(fence.language.clone()),
+        )]));
+    }
+
+    if !text.is_empty() {
+        code_block.content.push(Node::text(&text));
+    }
+
+    (code_block, (end + 1).min(lines.len()))
+}
+
+fn parse_blockquote(lines: &[String], start: usize) -> (Node, usize) {
+    let mut quoted: Vec<String> = vec![];
+    let mut end = start;
+
+    while end < lines.len() {
+        let line = lines[end].trim_start();
+
+        if let Some(rest) = line.strip_prefix('>') {
+            quoted.push(rest.strip_prefix(' ').unwrap_or(rest).to_string());
+        } else if !is_blank(line)
+            && !starts_block(line)
+            && matches!(quoted.last(), Some(last) if !is_blank(last))
+        {
+            quoted.push(line.to_string());
+        } else {
+            break;
+        }
+
+        end += 1;
+    }
+
+    (container(NodeType::Blockquote, parse_blocks(&quoted)), end)
+}
+
+struct ListMarker {
+    ordered: bool,
+    /// The bullet of a bullet list, or the `.` or `)` of an ordered list.
+    char: char,
+    start: u64,
+    /// The column where the content of the item starts.
+    offset: usize,
+}
+
+impl ListMarker {
+    fn parse(line: &str) -> Option<ListMarker> {
+        let indent = indent_of(line);
+        let rest = &line[indent..];
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+        let (ordered, char, start, width) = match rest.chars().next()? {
+            char @ ('-' | '*' | '+') => (false, char, 1, 1),
+            _ if (1..=9).contains(&digits) => {
+                let char = rest[digits..]
+                    .chars()
+                    .next()
+                    .filter(|c| *c == '.' || *c == ')')?;
+
+                (true, char, rest[..digits].parse().ok()?, digits + 1)
+            }
+            _ => return None,
+        };
+
+        let after = &rest[width..];
+        let spaces = indent_of(after);
+
+        if indent > 3 || !(after.is_empty() || spaces > 0) {
+            return None;
+        }
+
+        let offset = if after.trim().is_empty() || spaces > 4 {
+            indent + width + 1
+        } else {
+            indent + width + spaces
+        };
+
+        Some(ListMarker {
+            ordered,
+            char,
+            start,
+            offset,
+        })
+    }
+}
+
+fn parse_list(lines: &[String], start: usize, marker: &ListMarker) -> (Node, usize) {
+    let mut list = if marker.ordered {
+        let mut list = Node::new(NodeType::OrderedList);
+        list.attrs = Some(attrs(vec![("start", Value::from(marker.start))]));
+        list
+    } else {
+        Node::new(NodeType::BulletList)
+    };
+    let mut index = start;
+
+    while index < lines.len() && !is_thematic_break(lines[index].trim_start()) {
+        let item_marker = match ListMarker::parse(&lines[index]) {
+            Some(item_marker)
+                if item_marker.ordered == marker.ordered && item_marker.char == marker.char =>
+            {
+                item_marker
+            }
+            _ => break,
+        };
+
+        let mut item_lines = vec![lines[index]
+            .get(item_marker.offset..)
+            .unwrap_or_default()
+            .to_string()];
+        index += 1;
+
+        while index < lines.len() {
+            let line = &lines[index];
+
+            if is_blank(line) {
+                item_lines.push(String::new());
+            } else if indent_of(line) >= item_marker.offset {
+                item_lines.push(line[item_marker.offset..].to_string());
+            } else if !starts_block(line)
+                && ListMarker::parse(line).is_none()
+                && matches!(item_lines.last(), Some(last) if !is_blank(last))
+            {
+                item_lines.push(line.trim_start().to_string());
+            } else {
+                break;
+            }
+
+            index += 1;
+        }
+
+        while matches!(item_lines.last(), Some(last) if is_blank(last)) {
+            item_lines.pop();
+        }
+
+        list.content
+            .push(container(NodeType::ListItem, parse_blocks(&item_lines)));
+    }
+
+    (list, index)
+}
+
+fn is_table_start(lines: &[String], index: usize) -> bool {
+    index + 1 < lines.len()
+        && lines[index].contains('|')
+        && is_delimiter_row(&lines[index + 1])
+        && split_cells(&lines[index]).len() == split_cells(&lines[index + 1]).len()
+}
+
+fn is_delimiter_row(line: &str) -> bool {
+    line.contains('|')
+        && split_cells(line).iter().all(|cell| {
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+
+            !dashes.is_empty() && dashes.chars().all(|c| c == '-')
+        })
+}
+
+/// Splits a table row into its cells, keeping escaped pipes escaped for the
+/// inline parser.
+fn split_cells(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = match line.strip_suffix('|') {
+        Some(stripped) if !stripped.ends_with('\\') => stripped,
+        _ => line,
+    };
+
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut escaped = false;
+
+    for char in line.chars() {
+        match char {
+            '|' if !escaped => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(char),
+        }
+
+        escaped = char == '\\' && !escaped;
+    }
+
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+fn parse_table(lines: &[String], start: usize) -> (Node, usize) {
+    let columns = split_cells(&lines[start]).len();
+    let mut table = Node::new(NodeType::Table);
+    table
+        .content
+        .push(table_row(&lines[start], columns, NodeType::TableHeader));
+
+    let mut end = start + 2;
+
+    while end < lines.len() && !is_blank(&lines[end]) && !starts_block(&lines[end]) {
+        table
+            .content
+            .push(table_row(&lines[end], columns, NodeType::TableCell));
+        end += 1;
+    }
+
+    (table, end)
+}
+
+fn table_row(line: &str, columns: usize, cell_type: NodeType) -> Node {
+    let cells = split_cells(line);
+    let mut row = Node::new(NodeType::TableRow);
+
+    for index in 0..columns {
+        let text = cells.get(index).map(String::as_str).unwrap_or_default();
+        let paragraph = textblock(NodeType::Paragraph, parse_inline(text));
+
+        row.content
+            .push(container(cell_type.clone(), vec![paragraph]));
+    }
+
+    row
+}
+
+fn textblock(node_type: NodeType, content: Vec<Node>) -> Node {
+    Node {
+        content,
+        ..Node::new(node_type)
+    }
+}
+
+/// Builds a node holding blocks, with an empty paragraph if there are none.
+fn container(node_type: NodeType, mut content: Vec<Node>) -> Node {
+    if content.is_empty() {
+        content.push(Node::new(NodeType::Paragraph));
+    }
+
+    textblock(node_type, content)
+}
+
+fn attrs(entries: Vec<(&str, Value)>) -> Map<String, Value> {
+    entries
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect()
+}
+
+enum Inline {
+    Text(String),
+    /// A run of `*`, `_` or `~` that may open or close emphasis.
+    Delimiter {
+        char: char,
+        count: usize,
+        can_open: bool,
+        can_close: bool,
+    },
+    Code(String),
+    HardBreak,
+    Image(Node),
+    /// Inlines within a link or resolved emphasis, `None` for a link whose
+    /// URL is unsafe.
+    Group(Option<Mark>, Vec<Inline>),
+}
+
+fn parse_inline(text: &str) -> Vec<Node> {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut nodes = vec![];
+
+    inlines_to_nodes(tokenize(&chars), &[], &mut nodes);
+    normalize_inline(nodes)
+}
+
+fn tokenize(chars: &[char]) -> Vec<Inline> {
+    let mut inlines = vec![];
+    let mut text = String::new();
+    let mut index = 0;
+
+    fn flush(text: &mut String, inlines: &mut Vec<Inline>) {
+        if !text.is_empty() {
+            inlines.push(Inline::Text(std::mem::take(text)));
+        }
+    }
+
+    while index < chars.len() {
+        let char = chars[index];
+        let next = chars.get(index + 1).copied();
+
+        match char {
+            '\\' if next == Some('\n') => {
+                flush(&mut text, &mut inlines);
+                inlines.push(Inline::HardBreak);
+                index += 2;
+            }
+            '\\' if is_escaped(chars, index + 1) => {
+                text.push(chars[index + 1]);
+                index += 2;
+            }
+            '\n' => {
+                let trimmed = text.trim_end_matches(' ').len();
+                let spaces = text.len() - trimmed;
+                text.truncate(trimmed);
+
+                if spaces >= 2 {
+                    flush(&mut text, &mut inlines);
+                    inlines.push(Inline::HardBreak);
+                } else {
+                    text.push(' ');
+                }
+
+                index += 1;
+            }
+            '`' => {
+                let count = run_length(chars, index);
+
+                match find_code_span_end(chars, index + count, count) {
+                    Some(end) => {
+                        flush(&mut text, &mut inlines);
+                        inlines.push(Inline::Code(code_span_text(&chars[index + count..end])));
+                        index = end + count;
+                    }
+                    None => {
+                        text.extend(&chars[index..index + count]);
+                        index += count;
+                    }
+                }
+            }
+            '*' | '_' | '~' => {
+                let count = run_length(chars, index);
+
+                if char == '~' && count != 2 {
+                    text.extend(&chars[index..index + count]);
+                } else {
+                    let before = if index == 0 { ' ' } else { chars[index - 1] };
+                    let after = chars.get(index + count).copied().unwrap_or(' ');
+                    let (left, right) = flanking(before, after);
+                    let (can_open, can_close) = match char {
+                        '_' => (
+                            left && (!right || is_punctuation(before)),
+                            right && (!left || is_punctuation(after)),
+                        ),
+                        _ => (left, right),
+                    };
+
+                    flush(&mut text, &mut inlines);
+                    inlines.push(Inline::Delimiter {
+                        char,
+                        count,
+                        can_open,
+                        can_close,
+                    });
+                }
+
+                index += count;
+            }
+            '!' | '[' => {
+                let bracket = if char == '!' { index + 1 } else { index };
+
+                match parse_link(chars, bracket) {
+                    Some(link) => {
+                        flush(&mut text, &mut inlines);
+                        index = link.end;
+                        inlines.push(link.into_inline(char == '!'));
+                    }
+                    _ => {
+                        text.push(char);
+                        index += 1;
+                    }
+                }
+            }
+            '<' => match parse_autolink(chars, index) {
+                Some((url, end)) => {
+                    flush(&mut text, &mut inlines);
+                    inlines.push(Inline::Group(
+                        Some(Mark::link(&url)),
+                        vec![Inline::Text(url)],
+                    ));
+                    index = end;
+                }
+                None => {
+                    text.push(char);
+                    index += 1;
+                }
+            },
+            _ => {
+                text.push(char);
+                index += 1;
+            }
+        }
+    }
+
+    flush(&mut text, &mut inlines);
+    resolve_emphasis(&mut inlines);
+    inlines
+}
+
+fn run_length(chars: &[char], start: usize) -> usize {
+    chars[start..]
+        .iter()
+        .take_while(|char| **char == chars[start])
+        .count()
+}
+
+fn find_code_span_end(chars: &[char], start: usize, count: usize) -> Option<usize> {
+    let mut index = start;
+
+    while index < chars.len() {
+        if chars[index] == '`' {
+            let run = run_length(chars, index);
+
+            if run == count {
+                return Some(index);
+            }
+
+            index += run;
+        } else {
+            index += 1;
+        }
+    }
+
+    None
+}
+
+fn code_span_text(chars: &[char]) -> String {
+    let text = chars
+        .iter()
+        .map(|char| if *char == '\n' { ' ' } else { *char })
+        .collect::<String>();
+
+    match text
+        .strip_prefix(' ')
+        .and_then(|text| text.strip_suffix(' '))
+    {
+        Some(stripped) if !stripped.trim().is_empty() => stripped.to_string(),
+        _ => text,
+    }
+}
+
+fn is_punctuation(char: char) -> bool {
+    !char.is_alphanumeric() && !char.is_whitespace()
+}
+
+/// Whether a delimiter run between `before` and `after` is left-flanking and
+/// right-flanking, as CommonMark defines them.
+fn flanking(before: char, after: char) -> (bool, bool) {
+    let left = !after.is_whitespace()
+        && (!is_punctuation(after) || before.is_whitespace() || is_punctuation(before));
+    let right = !before.is_whitespace()
+        && (!is_punctuation(before) || after.is_whitespace() || is_punctuation(after));
+
+    (left, right)
+}
+
+/// Matches each closing delimiter run with the nearest opening run of the same
+/// character before it, wrapping the inlines between them in a group.
+fn resolve_emphasis(inlines: &mut Vec<Inline>) {
+    let mut closer = 0;
+
+    while closer < inlines.len() {
+        let (char, closer_count) = match inlines[closer] {
+            Inline::Delimiter {
+                char,
+                count,
+                can_close: true,
+                ..
+            } => (char, count),
+            _ => {
+                closer += 1;
+                continue;
+            }
+        };
+
+        let opener = (0..closer).rev().find(|index| {
+            matches!(
+                inlines[*index],
+                Inline::Delimiter { char: c, count, can_open: true, .. } if c == char && count > 0
+            )
+        });
+        let opener = match opener {
+            Some(opener) => opener,
+            None => {
+                closer += 1;
+                continue;
+            }
+        };
+
+        let opener_count = match inlines[opener] {
+            Inline::Delimiter { count, .. } => count,
+            _ => 0,
+        };
+        let used = if opener_count >= 2 && closer_count >= 2 {
+            2
+        } else {
+            1
+        };
+        let mark_type = match (char, used) {
+            ('~', _) => MarkType::Strike,
+            (_, 2) => MarkType::Bold,
+            _ => MarkType::Italic,
+        };
+
+        let inner = inlines.drain(opener + 1..closer).collect();
+        inlines.insert(opener + 1, Inline::Group(Some(Mark::new(mark_type)), inner));
+        closer = opener + 2;
+
+        if take_delimiters(&mut inlines[closer], used) == 0 {
+            inlines.remove(closer);
+        }
+
+        if take_delimiters(&mut inlines[opener], used) == 0 {
+            inlines.remove(opener);
+            closer -= 1;
+        }
+    }
+}
+
+/// Removes `used` characters from a delimiter run, returning how many are left.
+fn take_delimiters(inline: &mut Inline, used: usize) -> usize {
+    match inline {
+        Inline::Delimiter { count, .. } => {
+            *count -= used;
+            *count
+        }
+        _ => 0,
+    }
+}
+
+fn inlines_to_nodes(inlines: Vec<Inline>, marks: &[Mark], nodes: &mut Vec<Node>) {
+    let marked = |mut node: Node, extra: Option<Mark>| {
+        node.marks = marks.to_vec();
+        node.marks.extend(extra);
+        sort_marks(&mut node.marks);
+        node
+    };
+
+    for inline in inlines {
+        match inline {
+            Inline::Text(text) => nodes.push(marked(Node::text(&text), None)),
+            Inline::Delimiter { char, count, .. } => {
+                nodes.push(marked(Node::text(&char.to_string().repeat(count)), None))
+            }
+            Inline::Code(text) => {
+                let code = Some(Mark::new(MarkType::Code)).filter(|code| !marks.contains(code));
+
+                nodes.push(marked(Node::text(&text), code))
+            }
+            Inline::HardBreak => nodes.push(Node::new(NodeType::HardBreak)),
+            Inline::Image(image) => nodes.push(marked(image, None)),
+            Inline::Group(mark, inner) => {
+                let mut marks = marks.to_vec();
+
+                if let Some(mark) = mark {
+                    if !marks.iter().any(|other| other.mark_type == mark.mark_type) {
+                        marks.push(mark);
+                    }
+                }
+
+                inlines_to_nodes(inner, &marks, nodes);
+            }
+        }
+    }
+}
+
+struct Link {
+    label: Vec<char>,
+    url: String,
+    title: Option<String>,
+    end: usize,
+}
+
+impl Link {
+    fn into_inline(self, is_image: bool) -> Inline {
+        let Link {
+            label, url, title, ..
+        } = self;
+        let safe = is_safe_url(&url);
+
+        if is_image {
+            let alt = label.iter().collect::<String>();
+
+            if !safe {
+                return Inline::Text(alt);
+            }
+
+            let mut entries = vec![("src", Value::String(url)), ("alt", Value::String(alt))];
+            entries.extend(title.map(|title| ("title", Value::String(title))));
+
+            return Inline::Image(Node {
+                attrs: Some(attrs(entries)),
+                ..Node::new(NodeType::Image)
+            });
+        }
+
+        let mark = if safe {
+            let mut mark = Mark::link(&url);
+
+            if let (Some(attrs), Some(title)) = (&mut mark.attrs, title) {
+                attrs.insert("title".to_string(), Value::String(title));
+            }
+
+            Some(mark)
+        } else {
+            None
+        };
+
+        Inline::Group(mark, tokenize(&label))
+    }
+}
+
+/// Parses `[label](url "title")` starting at the `[`.
+fn parse_link(chars: &[char], start: usize) -> Option<Link> {
+    if chars.get(start) != Some(&'[') {
+        return None;
+    }
+
+    let mut depth = 0;
+    let mut index = start;
+
+    let close = loop {
+        match chars.get(index)? {
+            '\\' => index += 1,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+
+                if depth == 0 {
+                    break index;
+                }
+            }
+            _ => {}
+        }
+
+        index += 1;
+    };
+
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+
+    let mut index = skip_spaces(chars, close + 2);
+    let mut url = String::new();
+
+    if chars.get(index) == Some(&'<') {
+        index += 1;
+
+        loop {
+            match chars.get(index)? {
+                '>' => break,
+                '\n' | '<' => return None,
+                '\\' if is_escaped(chars, index + 1) => {
+                    index += 1;
+                    url.push(chars[index]);
+                }
+                char => url.push(*char),
+            }
+
+            index += 1;
+        }
+
+        index += 1;
+    } else {
+        let mut parens = 0;
+
+        while let Some(char) = chars.get(index) {
+            match char {
+                _ if char.is_whitespace() => break,
+                ')' if parens == 0 => break,
+                ')' => parens -= 1,
+                '(' => parens += 1,
+                '\\' if is_escaped(chars, index + 1) => {
+                    index += 1;
+                }
+                _ => {}
+            }
+
+            url.push(chars[index]);
+            index += 1;
+        }
+    }
+
+    index = skip_spaces(chars, index);
+
+    let title = match chars.get(index) {
+        Some(quote @ ('"' | '\'')) => {
+            let mut title = String::new();
+            index += 1;
+
+            loop {
+                match chars.get(index)? {
+                    char if char == quote => break,
+                    '\\' if is_escaped(chars, index + 1) => {
+                        index += 1;
+                        title.push(chars[index]);
+                    }
+                    char => title.push(*char),
+                }
+
+                index += 1;
+            }
+
+            index = skip_spaces(chars, index + 1);
+            Some(title)
+        }
+        _ => None,
+    };
+
+    if chars.get(index) != Some(&')') {
+        return None;
+    }
+
+    Some(Link {
+        label: chars[start + 1..close].to_vec(),
+        url,
+        title,
+        end: index + 1,
+    })
+}
+
+/// Whether a backslash before `index` escapes the character there.
+fn is_escaped(chars: &[char], index: usize) -> bool {
+    matches!(chars.get(index), Some(char) if char.is_ascii_punctuation())
+}
+
+fn skip_spaces(chars: &[char], mut index: usize) -> usize {
+    while matches!(chars.get(index), Some(char) if char.is_whitespace()) {
+        index += 1;
+    }
+
+    index
+}
+
+/// Parses `<https://...>` starting at the `<`.
+fn parse_autolink(chars: &[char], start: usize) -> Option<(String, usize)> {
+    let end = start + chars[start..].iter().position(|char| *char == '>')?;
+    let url = chars[start + 1..end].iter().collect::<String>();
+    let has_scheme = ["http://", "https://", "mailto:"]
+        .iter()
+        .any(|scheme| url.to_ascii_lowercase().starts_with(scheme));
+
+    if has_scheme && !url.contains(|c: char| c.is_whitespace() || c == '<') {
+        Some((url, end + 1))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_presenter::value::json_codec::JsonCodec;
+    use serde_json::json;
+
+    fn doc() -> ProsemirrorState {
+        let json = json!({
+            "type": "doc",
+            "content": [
+                {
+                    "type": "heading",
+                    "attrs": { "level": 2 },
+                    "content": [{ "type": "text", "text": "Weekly report" }]
+                },
+                {
+                    "type": "paragraph",
+                    "content": [
+                        { "type": "text", "text": "Hello " },
+                        { "type": "text", "text": "bold ", "marks": [{ "type": "bold" }] },
+                        {
+                            "type": "text",
+                            "text": "and italic",
+                            "marks": [{ "type": "bold" }, { "type": "italic" }]
+                        },
+                        { "type": "text", "text": ", " },
+                        {
+                            "type": "text",
+                            "text": "Jet",
+                            "marks": [{ "type": "link", "attrs": { "href": "https://jet.work" } }]
+                        },
+                        { "type": "text", "text": " and " },
+                        { "type": "text", "text": "a_b", "marks": [{ "type": "code" }] },
+                        { "type": "hardBreak" },
+                        { "type": "text", "text": "gone", "marks": [{ "type": "strike" }] }
+                    ]
+                },
+                {
+                    "type": "bulletList",
+                    "content": [
+                        {
+                            "type": "listItem",
+                            "content": [
+                                { "type": "paragraph", "content": [{ "type": "text", "text": "one" }] },
+                                {
+                                    "type": "orderedList",
+                                    "attrs": { "start": 3 },
+                                    "content": [
+                                        {
+                                            "type": "listItem",
+                                            "content": [
+                                                { "type": "paragraph", "content": [{ "type": "text", "text": "three" }] }
+                                            ]
+                                        }
+                                    ]
+                                }
+                            ]
+                        },
+                        {
+                            "type": "listItem",
+                            "content": [
+                                { "type": "paragraph", "content": [{ "type": "text", "text": "two" }] }
+                            ]
+                        }
+                    ]
+                },
+                {
+                    "type": "blockquote",
+                    "content": [
+                        { "type": "paragraph", "content": [{ "type": "text", "text": "quoted" }] },
+                        { "type": "paragraph", "content": [{ "type": "text", "text": "twice" }] }
+                    ]
+                },
+                {
+                    "type": "codeBlock",
+                    "attrs": { "language": "rust" },
+                    "content": [{ "type": "text", "text": "let a = 1;\n\nlet b = 2;" }]
+                },
+                { "type": "horizontalRule" },
+                {
+                    "type": "table",
+                    "content": [
+                        {
+                            "type": "tableRow",
+                            "content": [
+                                {
+                                    "type": "tableHeader",
+                                    "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "Name" }] }]
+                                },
+                                {
+                                    "type": "tableHeader",
+                                    "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "Score" }] }]
+                                }
+                            ]
+                        },
+                        {
+                            "type": "tableRow",
+                            "content": [
+                                {
+                                    "type": "tableCell",
+                                    "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "a|b" }] }]
+                                },
+                                {
+                                    "type": "tableCell",
+                                    "content": [{ "type": "paragraph" }]
+                                }
+                            ]
+                        }
+                    ]
+                },
+                {
+                    "type": "paragraph",
+                    "content": [
+                        {
+                            "type": "image",
+                            "attrs": { "src": "https://jet.work/logo.png", "alt": "logo" }
+                        }
+                    ]
+                }
+            ]
+        });
+
+        ProsemirrorState::from_json(&json).unwrap()
+    }
+
+    const MARKDOWN: &str = "## Weekly report
+
+Hello **bold *and italic***, [Jet](https://jet.work) and `a_b`\\
+~~gone~~
+
+- one
+
+  3. three
+- two
+
+> quoted
+>
+> twice
+
+```rust
+let a = 1;
+
+let b = 2;
+```
+
+---
+
+| Name | Score |
+| --- | --- |
+| a\\|b |  |
+
+![logo](https://jet.work/logo.png)";
+
+    #[test]
+    fn test_to_markdown() {
+        assert_eq!(doc().to_markdown(), MARKDOWN);
+    }
+
+    #[test]
+    fn test_from_markdown() {
+        assert_eq!(ProsemirrorState::from_markdown(MARKDOWN), doc());
+
+        {
+            let state = ProsemirrorState::from_markdown(
+                "# Title #\n\n* a\n* b\ncontinued\n\n1) x\n2) y\n\nline  \nbreak *a **b** c* <https://jet.work>",
+            );
+
+            assert_eq!(
+                state.to_json(),
+                json!({
+                    "type": "doc",
+                    "content": [
+                        {
+                            "type": "heading",
+                            "attrs": { "level": 1 },
+                            "content": [{ "type": "text", "text": "Title" }]
+                        },
+                        {
+                            "type": "bulletList",
+                            "content": [
+                                {
+                                    "type": "listItem",
+                                    "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "a" }] }]
+                                },
+                                {
+                                    "type": "listItem",
+                                    "content": [
+                                        { "type": "paragraph", "content": [{ "type": "text", "text": "b continued" }] }
+                                    ]
+                                }
+                            ]
+                        },
+                        {
+                            "type": "orderedList",
+                            "attrs": { "start": 1 },
+                            "content": [
+                                {
+                                    "type": "listItem",
+                                    "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "x" }] }]
+                                },
+                                {
+                                    "type": "listItem",
+                                    "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "y" }] }]
+                                }
+                            ]
+                        },
+                        {
+                            "type": "paragraph",
+                            "content": [
+                                { "type": "text", "text": "line" },
+                                { "type": "hardBreak" },
+                                { "type": "text", "text": "break " },
+                                { "type": "text", "text": "a ", "marks": [{ "type": "italic" }] },
+                                {
+                                    "type": "text",
+                                    "text": "b",
+                                    "marks": [{ "type": "bold" }, { "type": "italic" }]
+                                },
+                                { "type": "text", "text": " c", "marks": [{ "type": "italic" }] },
+                                { "type": "text", "text": " " },
+                                {
+                                    "type": "text",
+                                    "text": "https://jet.work",
+                                    "marks": [{ "type": "link", "attrs": { "href": "https://jet.work" } }]
+                                }
+                            ]
+                        }
+                    ]
+                })
+            );
+        }
+
+        // empty document
+        {
+            let state = ProsemirrorState::from_markdown("");
+
+            assert_eq!(
+                state.to_json(),
+                json!({ "type": "doc", "content": [{ "type": "paragraph" }] })
+            );
+        }
+    }
+
+    #[test]
+    fn test_escape() {
+        let text = "1. *not* [a](b) # _x_ `y` ~~z~~ <b> \\";
+        let state = ProsemirrorState::from_plain_text(text);
+
+        assert_eq!(
+            state.to_markdown(),
+            "1\\. \\*not\\* \\[a\\](b) # \\_x\\_ \\`y\\` \\~\\~z\\~\\~ \\<b\\> \\\\"
+        );
+        assert_eq!(ProsemirrorState::from_markdown(&state.to_markdown()), state);
+        assert_eq!(
+            ProsemirrorState::from_plain_text("- # +").to_markdown(),
+            "\\- # +"
+        );
+    }
+
+    #[test]
+    fn test_adjacent_marks() {
+        let paragraph = |content: Value| {
+            ProsemirrorState::from_json(&json!({
+                "type": "doc",
+                "content": [{ "type": "paragraph", "content": content }]
+            }))
+            .unwrap()
+        };
+
+        for (state, markdown) in [
+            (
+                paragraph(json!([
+                    { "type": "text", "text": "a", "marks": [{ "type": "italic" }] },
+                    { "type": "text", "text": " " },
+                    { "type": "text", "text": "c", "marks": [{ "type": "code" }] }
+                ])),
+                "*a* `c`",
+            ),
+            (
+                paragraph(json!([
+                    { "type": "text", "text": "a", "marks": [{ "type": "bold" }] },
+                    { "type": "text", "text": " " },
+                    { "type": "text", "text": "b", "marks": [{ "type": "italic" }] }
+                ])),
+                "**a** *b*",
+            ),
+            (
+                paragraph(json!([
+                    { "type": "text", "text": "a", "marks": [{ "type": "italic" }] },
+                    { "type": "text", "text": " " },
+                    { "type": "text", "text": "b", "marks": [{ "type": "italic" }] }
+                ])),
+                "*a* *b*",
+            ),
+        ] {
+            assert_eq!(state.to_markdown(), markdown);
+            assert_eq!(ProsemirrorState::from_markdown(markdown), state);
+        }
+    }
+
+    #[test]
+    fn test_unsafe_url() {
+        let state = ProsemirrorState::from_markdown(
+            "[click](javascript:alert(1)) ![x](JavaScript:alert(1))",
+        );
+
+        assert_eq!(state.to_plain_text(), "click x");
+        assert!(state.descendants().all(|node| node.marks.is_empty()));
+    }
+
+    #[test]
+    fn test_malformed() {
+        for markdown in [
+            "**",
+            "*a",
+            "a**",
+            "[",
+            "[a](",
+            "[a](<b",
+            "![",
+            "`",
+            "``a`",
+            "<",
+            "<http://a",
+            "#",
+            "-",
+            "1.",
+            "> ",
+            "```",
+            "| a |\n| - |",
+            "\\",
+            "~~",
+            "***a**",
+            "_a*",
+            "中文**粗体**中文",
+            "- a\n\n\n  b\n-",
+        ] {
+            let state = ProsemirrorState::from_markdown(markdown);
+
+            assert_eq!(
+                ProsemirrorState::from_json(&state.to_json()).unwrap(),
+                state
+            );
+        }
+
+        assert_eq!(
+            ProsemirrorState::from_markdown("中文**粗体**中文").to_json()["content"][0]["content"]
+                [1],
+            json!({ "type": "text", "text": "粗体", "marks": [{ "type": "bold" }] })
+        );
+    }
+}
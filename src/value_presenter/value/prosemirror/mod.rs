@@ -0,0 +1,170 @@
+use serde_json::{Map, Value};
+
+use super::json_codec::JsonCodec;
+
+pub mod builder;
+pub mod html;
+pub mod markdown;
+pub mod node;
+pub mod template;
+pub mod text;
+
+use node::{Descendants, Node, NodeType};
+
+/// The value of a multiple line field, a ProseMirror document.
+///
+/// Hosts send either the document itself or an editor state holding it under
+/// `doc`, e.g. `{"doc": {...}, "selection": {...}}`; both are encoded back in
+/// the form they were decoded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProsemirrorState {
+    pub doc: Node,
+    /// The other fields of an editor state, `None` for a bare document.
+    pub state: Option<Map<String, Value>>,
+}
+
+#[derive(Debug)]
+pub struct ParseProsemirrorStateError;
+
+impl ProsemirrorState {
+    /// Wraps a document, which must be a `Doc` node.
+    pub fn new(doc: Node) -> Self {
+        Self { doc, state: None }
+    }
+
+    /// Iterates over the nodes of the document, depth-first in document order.
+    pub fn descendants(&self) -> Descendants<'_> {
+        self.doc.descendants()
+    }
+}
+
+impl JsonCodec for ProsemirrorState {
+    type Err = ParseProsemirrorStateError;
+
+    fn from_json(value: &Value) -> Result<Self, Self::Err> {
+        let object = value.as_object().ok_or(ParseProsemirrorStateError)?;
+
+        let (doc, state) = match object.get("doc") {
+            Some(doc) => {
+                let mut state = object.clone();
+                state.remove("doc");
+
+                (doc, Some(state))
+            }
+            None => (value, None),
+        };
+
+        match Node::from_json(doc) {
+            Ok(doc) if doc.node_type == NodeType::Doc => Ok(ProsemirrorState { doc, state }),
+            _ => Err(ParseProsemirrorStateError),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match &self.state {
+            Some(state) => {
+                let mut object = state.clone();
+                object.insert("doc".to_string(), self.doc.to_json());
+
+                Value::Object(object)
+            }
+            None => self.doc.to_json(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, json};
+
+    #[test]
+    fn test_from_json() {
+        {
+            let doc = r#"{
+                "type": "doc",
+                "content": [
+                    {
+                        "type": "paragraph",
+                        "content": [
+                            {
+                                "type": "text",
+                                "text": "hello world!"
+                            }
+                        ]
+                    }
+                ]
+            }"#;
+
+            let json = from_str(doc).unwrap();
+
+            let state = ProsemirrorState::from_json(&json);
+
+            assert!(
+                matches!(state, Ok(ProsemirrorState { ref doc, state: None }) if doc.content.len() == 1)
+            );
+            assert_eq!(state.unwrap().to_json(), json);
+        }
+
+        // editor state
+        {
+            let json = json!({
+                "doc": {
+                    "type": "doc",
+                    "content": [{ "type": "paragraph" }]
+                },
+                "selection": { "type": "text", "anchor": 1, "head": 1 }
+            });
+
+            let state = ProsemirrorState::from_json(&json).unwrap();
+
+            assert_eq!(state.doc.content[0].node_type, NodeType::Paragraph);
+            assert_eq!(state.to_json(), json);
+        }
+    }
+
+    #[test]
+    fn test_from_invalid_json() {
+        {
+            let json = json!([]);
+
+            let state = ProsemirrorState::from_json(&json);
+
+            assert!(matches!(state, Err(ParseProsemirrorStateError)));
+        }
+
+        // not a doc
+        {
+            let json = json!({ "doc": { "type": "text", "value": "Hello, world!" } });
+
+            let state = ProsemirrorState::from_json(&json);
+
+            assert!(matches!(state, Err(ParseProsemirrorStateError)));
+        }
+    }
+
+    #[test]
+    fn test_to_json() {
+        {
+            let doc = r#"{
+                "type": "doc",
+                "content": [
+                    {
+                        "type": "paragraph",
+                        "content": [
+                            {
+                                "type": "text",
+                                "text": "hello world!"
+                            }
+                        ]
+                    }
+                ]
+            }"#;
+            let state = ProsemirrorState::new(Node::from_json(&from_str(doc).unwrap()).unwrap());
+
+            let json: Value = from_str(doc).unwrap();
+
+            assert!(state.to_json() == json);
+        }
+    }
+}
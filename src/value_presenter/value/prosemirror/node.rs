@@ -0,0 +1,698 @@
+use std::slice::Iter;
+
+use serde_json::{Map, Value};
+
+const NODE_KEYS: [&str; 5] = ["type", "attrs", "content", "marks", "text"];
+const MARK_KEYS: [&str; 2] = ["type", "attrs"];
+
+#[derive(Debug)]
+pub struct ParseNodeError;
+
+/// The type of a `Node`, named as in Jet's editor schema.
+///
+/// Types outside the schema are kept as `Other` so that documents written by
+/// newer editors round-trip unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeType {
+    Doc,
+    Paragraph,
+    Heading,
+    Blockquote,
+    BulletList,
+    OrderedList,
+    ListItem,
+    CodeBlock,
+    HorizontalRule,
+    HardBreak,
+    Image,
+    Table,
+    TableRow,
+    TableCell,
+    TableHeader,
+    Text,
+    Other(String),
+}
+
+impl NodeType {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "doc" => NodeType::Doc,
+            "paragraph" => NodeType::Paragraph,
+            "heading" => NodeType::Heading,
+            "blockquote" => NodeType::Blockquote,
+            "bulletList" => NodeType::BulletList,
+            "orderedList" => NodeType::OrderedList,
+            "listItem" => NodeType::ListItem,
+            "codeBlock" => NodeType::CodeBlock,
+            "horizontalRule" => NodeType::HorizontalRule,
+            "hardBreak" => NodeType::HardBreak,
+            "image" => NodeType::Image,
+            "table" => NodeType::Table,
+            "tableRow" => NodeType::TableRow,
+            "tableCell" => NodeType::TableCell,
+            "tableHeader" => NodeType::TableHeader,
+            "text" => NodeType::Text,
+            other => NodeType::Other(other.to_string()),
+        }
+    }
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            NodeType::Doc => "doc",
+            NodeType::Paragraph => "paragraph",
+            NodeType::Heading => "heading",
+            NodeType::Blockquote => "blockquote",
+            NodeType::BulletList => "bulletList",
+            NodeType::OrderedList => "orderedList",
+            NodeType::ListItem => "listItem",
+            NodeType::CodeBlock => "codeBlock",
+            NodeType::HorizontalRule => "horizontalRule",
+            NodeType::HardBreak => "hardBreak",
+            NodeType::Image => "image",
+            NodeType::Table => "table",
+            NodeType::TableRow => "tableRow",
+            NodeType::TableCell => "tableCell",
+            NodeType::TableHeader => "tableHeader",
+            NodeType::Text => "text",
+            NodeType::Other(name) => name,
+        }
+    }
+
+    /// Whether the node sits within a paragraph-like node, e.g. text.
+    pub fn is_inline(&self) -> bool {
+        matches!(self, NodeType::Text | NodeType::HardBreak | NodeType::Image)
+    }
+
+    /// Whether the node can not have content.
+    pub fn is_leaf(&self) -> bool {
+        matches!(
+            self,
+            NodeType::Text | NodeType::HardBreak | NodeType::Image | NodeType::HorizontalRule
+        )
+    }
+
+    fn is_block(&self) -> bool {
+        matches!(
+            self,
+            NodeType::Paragraph
+                | NodeType::Heading
+                | NodeType::Blockquote
+                | NodeType::BulletList
+                | NodeType::OrderedList
+                | NodeType::CodeBlock
+                | NodeType::HorizontalRule
+                | NodeType::Table
+        )
+    }
+
+    /// Whether the schema allows `child` within this node. Nodes of `Other`
+    /// types are allowed anywhere and may hold anything.
+    fn allows_child(&self, child: &NodeType) -> bool {
+        if let NodeType::Other(_name) = child {
+            return true;
+        }
+
+        match self {
+            NodeType::Doc
+            | NodeType::Blockquote
+            | NodeType::ListItem
+            | NodeType::TableCell
+            | NodeType::TableHeader => child.is_block(),
+            NodeType::Paragraph | NodeType::Heading | NodeType::CodeBlock => child.is_inline(),
+            NodeType::BulletList | NodeType::OrderedList => *child == NodeType::ListItem,
+            NodeType::Table => *child == NodeType::TableRow,
+            NodeType::TableRow => matches!(child, NodeType::TableCell | NodeType::TableHeader),
+            NodeType::Other(_name) => true,
+            _leaf => false,
+        }
+    }
+}
+
+/// The type of a `Mark`, named as in Jet's editor schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkType {
+    Bold,
+    Italic,
+    Underline,
+    Strike,
+    Code,
+    Link,
+    Other(String),
+}
+
+impl MarkType {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "bold" => MarkType::Bold,
+            "italic" => MarkType::Italic,
+            "underline" => MarkType::Underline,
+            "strike" => MarkType::Strike,
+            "code" => MarkType::Code,
+            "link" => MarkType::Link,
+            other => MarkType::Other(other.to_string()),
+        }
+    }
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            MarkType::Bold => "bold",
+            MarkType::Italic => "italic",
+            MarkType::Underline => "underline",
+            MarkType::Strike => "strike",
+            MarkType::Code => "code",
+            MarkType::Link => "link",
+            MarkType::Other(name) => name,
+        }
+    }
+}
+
+/// Formatting applied to an inline node, e.g. bold or a link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mark {
+    pub mark_type: MarkType,
+    pub attrs: Option<Map<String, Value>>,
+    /// Keys besides `type` and `attrs`, written back as they were decoded.
+    pub extra: Map<String, Value>,
+}
+
+impl Mark {
+    pub fn new(mark_type: MarkType) -> Self {
+        Self {
+            mark_type,
+            attrs: None,
+            extra: Map::new(),
+        }
+    }
+
+    pub fn link(href: &str) -> Self {
+        let mut attrs = Map::new();
+        attrs.insert("href".to_string(), Value::String(href.to_string()));
+
+        Self {
+            attrs: Some(attrs),
+            ..Mark::new(MarkType::Link)
+        }
+    }
+
+    pub fn attr(&self, key: &str) -> Option<&Value> {
+        self.attrs.as_ref()?.get(key)
+    }
+
+    fn from_json(value: &Value) -> Result<Self, ParseNodeError> {
+        let object = value.as_object().ok_or(ParseNodeError)?;
+        let mark_type = match object.get("type") {
+            Some(Value::String(name)) => MarkType::from_name(name),
+            _ => return Err(ParseNodeError),
+        };
+        let attrs = match object.get("attrs") {
+            Some(Value::Object(attrs)) => Some(attrs.clone()),
+            Some(_other) => return Err(ParseNodeError),
+            None => None,
+        };
+
+        let mark = Mark {
+            mark_type,
+            attrs,
+            extra: extra_keys(object, &MARK_KEYS),
+        };
+
+        if mark.mark_type == MarkType::Link && !matches!(mark.attr("href"), Some(Value::String(_)))
+        {
+            return Err(ParseNodeError);
+        }
+
+        Ok(mark)
+    }
+
+    fn to_json(&self) -> Value {
+        let mut object = Map::new();
+        object.insert(
+            "type".to_string(),
+            Value::String(self.mark_type.to_str().to_string()),
+        );
+
+        if let Some(attrs) = &self.attrs {
+            object.insert("attrs".to_string(), Value::Object(attrs.clone()));
+        }
+
+        object.extend(self.extra.clone());
+
+        Value::Object(object)
+    }
+}
+
+/// A node of a ProseMirror document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub node_type: NodeType,
+    pub attrs: Option<Map<String, Value>>,
+    pub content: Vec<Node>,
+    pub marks: Vec<Mark>,
+    /// The text of a `Text` node, `None` for other nodes.
+    pub text: Option<String>,
+    /// Whether `content` is written even when empty, as it is for a decoded
+    /// node that had an empty `content` array.
+    pub keep_empty_content: bool,
+    /// Keys besides the ones above, written back as they were decoded so that
+    /// nodes of `Other` types round-trip unchanged.
+    pub extra: Map<String, Value>,
+}
+
+impl Node {
+    pub fn new(node_type: NodeType) -> Self {
+        Self {
+            node_type,
+            attrs: None,
+            content: vec![],
+            marks: vec![],
+            text: None,
+            keep_empty_content: false,
+            extra: Map::new(),
+        }
+    }
+
+    pub fn text(text: &str) -> Self {
+        Self {
+            text: Some(text.to_string()),
+            ..Node::new(NodeType::Text)
+        }
+    }
+
+    pub fn attr(&self, key: &str) -> Option<&Value> {
+        self.attrs.as_ref()?.get(key)
+    }
+
+    pub fn has_mark(&self, mark_type: &MarkType) -> bool {
+        self.marks.iter().any(|mark| mark.mark_type == *mark_type)
+    }
+
+    /// Iterates over the nodes below this one, depth-first in document order.
+    pub fn descendants(&self) -> Descendants<'_> {
+        Descendants {
+            stack: vec![self.content.iter()],
+        }
+    }
+
+    /// Calls `f` on this node and its descendants in document order. The
+    /// children of a node are skipped when `f` returns `false` for it.
+    pub fn walk<F>(&self, f: &mut F)
+    where
+        F: FnMut(&Node) -> bool,
+    {
+        if f(self) {
+            for child in &self.content {
+                child.walk(f);
+            }
+        }
+    }
+
+    /// Calls `f` on this node and then on its descendants in document order,
+    /// visiting the children as they are after `f` returns.
+    pub fn walk_mut<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(&mut Node),
+    {
+        f(self);
+
+        for child in &mut self.content {
+            child.walk_mut(f);
+        }
+    }
+
+    /// Decodes a node and its descendants, checking them against the schema.
+    pub fn from_json(value: &Value) -> Result<Self, ParseNodeError> {
+        let object = value.as_object().ok_or(ParseNodeError)?;
+        let node_type = match object.get("type") {
+            Some(Value::String(name)) => NodeType::from_name(name),
+            _ => return Err(ParseNodeError),
+        };
+        let attrs = match object.get("attrs") {
+            Some(Value::Object(attrs)) => Some(attrs.clone()),
+            Some(_other) => return Err(ParseNodeError),
+            None => None,
+        };
+        let content = match object.get("content") {
+            Some(Value::Array(values)) => values
+                .iter()
+                .map(Node::from_json)
+                .collect::<Result<Vec<_>, _>>()?,
+            Some(_other) => return Err(ParseNodeError),
+            None => vec![],
+        };
+        let marks = match object.get("marks") {
+            Some(Value::Array(values)) => values
+                .iter()
+                .map(Mark::from_json)
+                .collect::<Result<Vec<_>, _>>()?,
+            Some(_other) => return Err(ParseNodeError),
+            None => vec![],
+        };
+        let text = match object.get("text") {
+            Some(Value::String(text)) => Some(text.clone()),
+            Some(_other) => return Err(ParseNodeError),
+            None => None,
+        };
+
+        let keep_empty_content = content.is_empty() && object.contains_key("content");
+        let node = Node {
+            node_type,
+            attrs,
+            content,
+            marks,
+            text,
+            keep_empty_content,
+            extra: extra_keys(object, &NODE_KEYS),
+        };
+
+        if node.is_valid() {
+            Ok(node)
+        } else {
+            Err(ParseNodeError)
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut object = Map::new();
+        object.insert(
+            "type".to_string(),
+            Value::String(self.node_type.to_str().to_string()),
+        );
+
+        if let Some(attrs) = &self.attrs {
+            object.insert("attrs".to_string(), Value::Object(attrs.clone()));
+        }
+
+        if !self.content.is_empty() || self.keep_empty_content {
+            object.insert(
+                "content".to_string(),
+                Value::Array(self.content.iter().map(Node::to_json).collect()),
+            );
+        }
+
+        if !self.marks.is_empty() {
+            object.insert(
+                "marks".to_string(),
+                Value::Array(self.marks.iter().map(Mark::to_json).collect()),
+            );
+        }
+
+        if let Some(text) = &self.text {
+            object.insert("text".to_string(), Value::String(text.clone()));
+        }
+
+        object.extend(self.extra.clone());
+
+        Value::Object(object)
+    }
+
+    /// Checks this node, but not its descendants, against the schema.
+    fn is_valid(&self) -> bool {
+        let node_type = &self.node_type;
+
+        let text_is_valid = match (node_type, &self.text) {
+            (NodeType::Text, Some(text)) => !text.is_empty(),
+            (NodeType::Text, None) => false,
+            (NodeType::Other(_name), _text) => true,
+            (_node_type, text) => text.is_none(),
+        };
+        let content_is_valid = self
+            .content
+            .iter()
+            .all(|child| node_type.allows_child(&child.node_type));
+        let marks_are_valid = self.marks.is_empty()
+            || node_type.is_inline()
+            || matches!(node_type, NodeType::Other(_name));
+        let attrs_are_valid = match node_type {
+            NodeType::Heading => matches!(
+                self.attr("level").and_then(Value::as_u64),
+                Some(level) if (1..=6).contains(&level)
+            ),
+            _ => true,
+        };
+
+        text_is_valid && content_is_valid && marks_are_valid && attrs_are_valid
+    }
+}
+
+/// Iterator over the descendants of a `Node`, see `Node::descendants`.
+pub struct Descendants<'a> {
+    stack: Vec<Iter<'a, Node>>,
+}
+
+impl<'a> Iterator for Descendants<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.last_mut()?.next() {
+                Some(node) => {
+                    self.stack.push(node.content.iter());
+
+                    return Some(node);
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+/// The entries of `object` whose keys are not in `keys`.
+fn extra_keys(object: &Map<String, Value>, keys: &[&str]) -> Map<String, Value> {
+    object
+        .iter()
+        .filter(|(key, _value)| !keys.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// Sorts marks into a fixed order, links outermost and code innermost, so
+/// that equally marked text compares equal.
+pub(super) fn sort_marks(marks: &mut [Mark]) {
+    marks.sort_by_key(|mark| match mark.mark_type {
+        MarkType::Link => 0,
+        MarkType::Bold => 1,
+        MarkType::Italic => 2,
+        MarkType::Underline => 3,
+        MarkType::Strike => 4,
+        MarkType::Other(_) => 5,
+        MarkType::Code => 6,
+    });
+}
+
+/// Drops empty text nodes and merges adjacent text nodes with the same marks.
+pub(super) fn normalize_inline(nodes: Vec<Node>) -> Vec<Node> {
+    let mut normalized: Vec<Node> = Vec::with_capacity(nodes.len());
+
+    for node in nodes {
+        if node.node_type == NodeType::Text {
+            let text = match &node.text {
+                Some(text) if !text.is_empty() => text,
+                _ => continue,
+            };
+
+            if let Some(last) = normalized.last_mut() {
+                if last.node_type == NodeType::Text && last.marks == node.marks {
+                    last.text.get_or_insert_with(String::new).push_str(text);
+                    continue;
+                }
+            }
+        }
+
+        normalized.push(node);
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn doc() -> Value {
+        json!({
+            "type": "doc",
+            "content": [
+                {
+                    "type": "heading",
+                    "attrs": { "level": 1 },
+                    "content": [{ "type": "text", "text": "Title" }]
+                },
+                {
+                    "type": "paragraph",
+                    "content": [
+                        { "type": "text", "text": "Hello " },
+                        {
+                            "type": "text",
+                            "text": "world",
+                            "marks": [
+                                { "type": "bold" },
+                                { "type": "link", "attrs": { "href": "https://jet.work" } }
+                            ]
+                        },
+                        { "type": "hardBreak" }
+                    ]
+                },
+                {
+                    "type": "bulletList",
+                    "content": [
+                        {
+                            "type": "listItem",
+                            "content": [
+                                {
+                                    "type": "paragraph",
+                                    "content": [{ "type": "text", "text": "item" }]
+                                }
+                            ]
+                        }
+                    ]
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_from_json() {
+        let node = Node::from_json(&doc()).unwrap();
+
+        assert_eq!(node.node_type, NodeType::Doc);
+        assert_eq!(node.content.len(), 3);
+        assert_eq!(node.content[0].attr("level"), Some(&json!(1)));
+
+        let world = &node.content[1].content[1];
+
+        assert_eq!(world.text.as_deref(), Some("world"));
+        assert!(world.has_mark(&MarkType::Bold));
+        assert_eq!(world.marks[1], Mark::link("https://jet.work"));
+    }
+
+    #[test]
+    fn test_from_invalid_json() {
+        let invalid = vec![
+            // text node without text
+            json!({ "type": "paragraph", "content": [{ "type": "text" }] }),
+            // empty text
+            json!({ "type": "paragraph", "content": [{ "type": "text", "text": "" }] }),
+            // block within inline content
+            json!({ "type": "paragraph", "content": [{ "type": "paragraph" }] }),
+            // text directly within a list
+            json!({ "type": "bulletList", "content": [{ "type": "text", "text": "a" }] }),
+            // heading without level
+            json!({ "type": "heading" }),
+            // link without href
+            json!({
+                "type": "paragraph",
+                "content": [{ "type": "text", "text": "a", "marks": [{ "type": "link" }] }]
+            }),
+            // marks on a block
+            json!({ "type": "paragraph", "marks": [{ "type": "bold" }] }),
+            json!({ "type": "paragraph", "content": {} }),
+            json!({ "content": [] }),
+            json!("doc"),
+        ];
+
+        for value in invalid {
+            assert!(Node::from_json(&value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_unknown_types() {
+        let json = json!({
+            "type": "doc",
+            "content": [
+                {
+                    "type": "mention",
+                    "attrs": { "id": "u1" },
+                    "content": [{ "type": "text", "text": "@jet" }]
+                },
+                {
+                    "type": "paragraph",
+                    "content": [
+                        { "type": "text", "text": "hi", "marks": [{ "type": "highlight", "attrs": { "color": "red" } }] },
+                        { "type": "emoji", "attrs": { "name": "smile" } }
+                    ]
+                }
+            ]
+        });
+
+        let node = Node::from_json(&json).unwrap();
+
+        assert_eq!(
+            node.content[0].node_type,
+            NodeType::Other("mention".to_string())
+        );
+        assert_eq!(node.to_json(), json);
+        assert_eq!(Node::from_json(&doc()).unwrap().to_json(), doc());
+    }
+
+    #[test]
+    fn test_round_trip_extra_keys() {
+        let json = json!({
+            "type": "doc",
+            "content": [
+                {
+                    "type": "mention",
+                    "id": "u1",
+                    "attrs": { "x": 1 },
+                    "content": []
+                },
+                {
+                    "type": "paragraph",
+                    "content": [
+                        {
+                            "type": "text",
+                            "text": "hi",
+                            "marks": [{ "type": "comment", "thread": "t1" }]
+                        }
+                    ]
+                }
+            ]
+        });
+
+        let node = Node::from_json(&json).unwrap();
+        let mention = &node.content[0];
+
+        assert_eq!(mention.node_type, NodeType::Other("mention".to_string()));
+        assert_eq!(mention.extra.get("id"), Some(&json!("u1")));
+        assert!(mention.content.is_empty());
+        assert_eq!(node.to_json(), json);
+
+        // an empty content is left out of nodes that were not decoded with one
+        assert_eq!(
+            Node::new(NodeType::Paragraph).to_json(),
+            json!({ "type": "paragraph" })
+        );
+    }
+
+    #[test]
+    fn test_traversal() {
+        let mut node = Node::from_json(&doc()).unwrap();
+
+        let texts: Vec<_> = node
+            .descendants()
+            .filter_map(|node| node.text.as_deref())
+            .collect();
+
+        assert_eq!(texts, vec!["Title", "Hello ", "world", "item"]);
+
+        let mut visited = vec![];
+        node.walk(&mut |node| {
+            visited.push(node.node_type.clone());
+            node.node_type != NodeType::BulletList
+        });
+
+        assert_eq!(visited.len(), 8);
+        assert_eq!(visited.last(), Some(&NodeType::BulletList));
+
+        node.walk_mut(&mut |node| {
+            if let Some(text) = &mut node.text {
+                *text = text.to_uppercase();
+            }
+        });
+
+        assert_eq!(node.content[0].content[0].text.as_deref(), Some("TITLE"));
+    }
+}
@@ -0,0 +1,282 @@
+//! `{{placeholder}}` substitution in ProseMirror documents.
+//!
+//! A placeholder is found in the text of a textblock even when editing split
+//! it over text nodes with different marks, e.g. `{{na` in bold and `me}}` in
+//! italic. Its value takes the marks of the text where the placeholder starts,
+//! and newlines in it become hard breaks outside of code blocks.
+
+use super::{
+    node::{normalize_inline, Node, NodeType},
+    ProsemirrorState,
+};
+
+impl ProsemirrorState {
+    /// Returns the names of the placeholders in the document, in document
+    /// order and without duplicates. Names are trimmed, so `{{ name }}` and
+    /// `{{name}}` are the same placeholder.
+    pub fn placeholders(&self) -> Vec<String> {
+        let mut names: Vec<String> = vec![];
+
+        self.doc.walk(&mut |node| {
+            if !has_text(node) {
+                return true;
+            }
+
+            for run in text_runs(&node.content) {
+                for (_start, _end, name) in find_placeholders(&run_text(run)) {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+            }
+
+            false
+        });
+
+        names
+    }
+
+    /// Returns a copy of the document with each placeholder replaced by the
+    /// value `resolve` gives for its name. Placeholders it gives `None` for are
+    /// kept as they are.
+    pub fn render_template<F>(&self, mut resolve: F) -> Self
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        let mut rendered = self.clone();
+        render_node(&mut rendered.doc, &mut resolve);
+        rendered
+    }
+}
+
+fn has_text(node: &Node) -> bool {
+    node.content
+        .iter()
+        .any(|child| child.node_type == NodeType::Text)
+}
+
+/// Splits inline content into runs of adjacent text nodes.
+fn text_runs(content: &[Node]) -> impl Iterator<Item = &[Node]> {
+    content
+        .split(|node| node.node_type != NodeType::Text)
+        .filter(|run| !run.is_empty())
+}
+
+fn run_text(run: &[Node]) -> String {
+    run.iter().filter_map(|node| node.text.as_deref()).collect()
+}
+
+/// Finds the placeholders in `text`, returning the byte range of each, braces
+/// included, and its trimmed name.
+fn find_placeholders(text: &str) -> Vec<(usize, usize, String)> {
+    let mut placeholders = vec![];
+    let mut position = 0;
+
+    while let Some(offset) = text[position..].find("{{") {
+        let start = position + offset;
+        let name_start = start + 2;
+
+        let name_end = match text[name_start..].find("}}") {
+            Some(offset) => name_start + offset,
+            None => break,
+        };
+        let name = &text[name_start..name_end];
+
+        if name.trim().is_empty() || name.contains(['{', '}', '\n']) {
+            position = start + 1;
+            continue;
+        }
+
+        placeholders.push((start, name_end + 2, name.trim().to_string()));
+        position = name_end + 2;
+    }
+
+    placeholders
+}
+
+fn render_node<F>(node: &mut Node, resolve: &mut F)
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    if !has_text(node) {
+        for child in &mut node.content {
+            render_node(child, resolve);
+        }
+
+        return;
+    }
+
+    let is_code = node.node_type == NodeType::CodeBlock;
+    let mut content = vec![];
+    let mut run = vec![];
+
+    for child in std::mem::take(&mut node.content) {
+        if child.node_type == NodeType::Text {
+            run.push(child);
+        } else {
+            content.extend(render_run(std::mem::take(&mut run), is_code, resolve));
+            content.push(child);
+        }
+    }
+
+    content.extend(render_run(run, is_code, resolve));
+    node.content = normalize_inline(content);
+}
+
+fn render_run<F>(run: Vec<Node>, is_code: bool, resolve: &mut F) -> Vec<Node>
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    let text = run_text(&run);
+    let mut content = vec![];
+    let mut position = 0;
+
+    for (start, end, name) in find_placeholders(&text) {
+        let value = match resolve(&name) {
+            Some(value) => value,
+            None => continue,
+        };
+
+        slice_run(&run, position, start, &mut content);
+
+        let marks = node_at(&run, start).marks.clone();
+
+        for (index, line) in value.split('\n').enumerate() {
+            if index > 0 {
+                if is_code {
+                    content.push(Node::text("\n"));
+                } else {
+                    content.push(Node::hard_break());
+                }
+            }
+
+            content.push(Node {
+                marks: marks.clone(),
+                ..Node::text(line)
+            });
+        }
+
+        position = end;
+    }
+
+    slice_run(&run, position, text.len(), &mut content);
+    content
+}
+
+/// Returns the text node holding byte `offset` of the run's text.
+fn node_at(run: &[Node], offset: usize) -> &Node {
+    let mut end = 0;
+
+    for node in run {
+        end += node.text.as_deref().unwrap_or_default().len();
+
+        if offset < end {
+            return node;
+        }
+    }
+
+    &run[run.len() - 1]
+}
+
+/// Pushes the part of the run's text from byte `start` to `end`, keeping the
+/// marks of each node.
+fn slice_run(run: &[Node], start: usize, end: usize, content: &mut Vec<Node>) {
+    let mut node_start = 0;
+
+    for node in run {
+        let text = node.text.as_deref().unwrap_or_default();
+        let node_end = node_start + text.len();
+        let from = start.max(node_start);
+        let to = end.min(node_end);
+
+        if from < to {
+            content.push(Node {
+                marks: node.marks.clone(),
+                ..Node::text(&text[from - node_start..to - node_start])
+            });
+        }
+
+        node_start = node_end;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_presenter::value::json_codec::JsonCodec;
+    use serde_json::json;
+
+    fn template() -> ProsemirrorState {
+        let json = json!({
+            "type": "doc",
+            "content": [
+                {
+                    "type": "paragraph",
+                    "content": [
+                        { "type": "text", "text": "Dear " },
+                        { "type": "text", "text": "{{ na", "marks": [{ "type": "bold" }] },
+                        { "type": "text", "text": "me }}", "marks": [{ "type": "italic" }] },
+                        { "type": "text", "text": ", see {{unknown}} and {{name}}." },
+                        { "type": "hardBreak" },
+                        { "type": "text", "text": "{{address}}" }
+                    ]
+                },
+                {
+                    "type": "codeBlock",
+                    "content": [{ "type": "text", "text": "{{address}} {{}}" }]
+                }
+            ]
+        });
+
+        ProsemirrorState::from_json(&json).unwrap()
+    }
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(
+            template().placeholders(),
+            vec![
+                "name".to_string(),
+                "unknown".to_string(),
+                "address".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_template() {
+        let rendered = template().render_template(|name| match name {
+            "name" => Some("Jet".to_string()),
+            "address" => Some("1 Main St\nSpringfield".to_string()),
+            _ => None,
+        });
+
+        assert_eq!(
+            rendered.to_json(),
+            json!({
+                "type": "doc",
+                "content": [
+                    {
+                        "type": "paragraph",
+                        "content": [
+                            { "type": "text", "text": "Dear " },
+                            { "type": "text", "text": "Jet", "marks": [{ "type": "bold" }] },
+                            { "type": "text", "text": ", see {{unknown}} and Jet." },
+                            { "type": "hardBreak" },
+                            { "type": "text", "text": "1 Main St" },
+                            { "type": "hardBreak" },
+                            { "type": "text", "text": "Springfield" }
+                        ]
+                    },
+                    {
+                        "type": "codeBlock",
+                        "content": [{ "type": "text", "text": "1 Main St\nSpringfield {{}}" }]
+                    }
+                ]
+            })
+        );
+
+        // the template is unchanged
+        assert_eq!(template().render_template(|_name| None), template());
+    }
+}
@@ -0,0 +1,194 @@
+//! Conversions between ProseMirror documents and plain text.
+//!
+//! Each textblock (paragraph, heading, code block) becomes a line, hard breaks
+//! become newlines and table cells are separated by tabs. Marks are dropped.
+
+use super::{
+    node::{Node, NodeType},
+    ProsemirrorState,
+};
+
+impl ProsemirrorState {
+    /// Returns the text of the document, one line per textblock.
+    pub fn to_plain_text(&self) -> String {
+        self.doc.to_plain_text()
+    }
+
+    /// Builds a document with one paragraph per line of `text`, an empty line
+    /// giving an empty paragraph.
+    pub fn from_plain_text(text: &str) -> Self {
+        let mut doc = Node::new(NodeType::Doc);
+        doc.content = text.lines().map(paragraph).collect();
+
+        if doc.content.is_empty() {
+            doc.content.push(paragraph(""));
+        }
+
+        // `lines` ignores a trailing newline, which ends an empty last line.
+        if text.ends_with('\n') {
+            doc.content.push(paragraph(""));
+        }
+
+        ProsemirrorState::new(doc)
+    }
+}
+
+impl Node {
+    /// Returns the text of the node, one line per textblock.
+    pub fn to_plain_text(&self) -> String {
+        let mut lines = vec![];
+        block_lines(self, &mut lines);
+        lines.join("\n")
+    }
+}
+
+fn paragraph(line: &str) -> Node {
+    let mut paragraph = Node::new(NodeType::Paragraph);
+
+    if !line.is_empty() {
+        paragraph.content.push(Node::text(line));
+    }
+
+    paragraph
+}
+
+fn block_lines(node: &Node, lines: &mut Vec<String>) {
+    match node.node_type {
+        NodeType::Paragraph | NodeType::Heading | NodeType::CodeBlock => {
+            lines.push(inline_text(node))
+        }
+        NodeType::HorizontalRule => lines.push(String::new()),
+        NodeType::TableRow => lines.push(
+            node.content
+                .iter()
+                .map(|cell| {
+                    let mut cell_lines = vec![];
+                    block_lines(cell, &mut cell_lines);
+                    cell_lines.join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join("\t"),
+        ),
+        _ if node.node_type.is_inline() => lines.push(inline_text(node)),
+        _ if !node.content.is_empty()
+            && node.content.iter().all(|child| child.node_type.is_inline()) =>
+        {
+            lines.push(inline_text(node))
+        }
+        _ => {
+            for child in &node.content {
+                block_lines(child, lines);
+            }
+        }
+    }
+}
+
+fn inline_text(node: &Node) -> String {
+    match node.node_type {
+        NodeType::Text => node.text.clone().unwrap_or_default(),
+        NodeType::HardBreak => "\n".to_string(),
+        NodeType::Image => node
+            .attr("alt")
+            .and_then(|alt| alt.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        _ => node.content.iter().map(inline_text).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_presenter::value::json_codec::JsonCodec;
+    use serde_json::json;
+
+    #[test]
+    fn test_to_plain_text() {
+        let json = json!({
+            "type": "doc",
+            "content": [
+                {
+                    "type": "heading",
+                    "attrs": { "level": 1 },
+                    "content": [{ "type": "text", "text": "Title" }]
+                },
+                {
+                    "type": "paragraph",
+                    "content": [
+                        { "type": "text", "text": "Hello " },
+                        { "type": "text", "text": "world", "marks": [{ "type": "bold" }] },
+                        { "type": "hardBreak" },
+                        { "type": "text", "text": "again" }
+                    ]
+                },
+                { "type": "paragraph" },
+                {
+                    "type": "bulletList",
+                    "content": [
+                        {
+                            "type": "listItem",
+                            "content": [
+                                { "type": "paragraph", "content": [{ "type": "text", "text": "one" }] }
+                            ]
+                        },
+                        {
+                            "type": "listItem",
+                            "content": [
+                                { "type": "paragraph", "content": [{ "type": "text", "text": "two" }] }
+                            ]
+                        }
+                    ]
+                },
+                {
+                    "type": "table",
+                    "content": [
+                        {
+                            "type": "tableRow",
+                            "content": [
+                                {
+                                    "type": "tableHeader",
+                                    "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "a" }] }]
+                                },
+                                {
+                                    "type": "tableCell",
+                                    "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "b" }] }]
+                                }
+                            ]
+                        }
+                    ]
+                }
+            ]
+        });
+
+        let state = ProsemirrorState::from_json(&json).unwrap();
+
+        assert_eq!(
+            state.to_plain_text(),
+            "Title\nHello world\nagain\n\none\ntwo\na\tb"
+        );
+    }
+
+    #[test]
+    fn test_from_plain_text() {
+        let state = ProsemirrorState::from_plain_text("Hello\n\nworld");
+
+        assert_eq!(
+            state.to_json(),
+            json!({
+                "type": "doc",
+                "content": [
+                    { "type": "paragraph", "content": [{ "type": "text", "text": "Hello" }] },
+                    { "type": "paragraph" },
+                    { "type": "paragraph", "content": [{ "type": "text", "text": "world" }] }
+                ]
+            })
+        );
+
+        for text in &["", "a", "a\r\nb", "a\n", "\n\nb"] {
+            assert_eq!(
+                ProsemirrorState::from_plain_text(text).to_plain_text(),
+                text.replace("\r\n", "\n")
+            );
+        }
+    }
+}
@@ -163,7 +163,7 @@ mod tests {
                 Ok(RelationValue {
                     resource_type: ResourceType::DatabaseTable,
                     resource_uuid,
-                }) if resource_uuid == Uuid("00000000-0000-0000-0000-ffff00000000".to_string())
+                }) if resource_uuid == Uuid::new("00000000-0000-0000-0000-ffff00000000").unwrap()
             ));
         }
 
@@ -180,7 +180,7 @@ mod tests {
                 Ok(RelationValue {
                     resource_type: ResourceType::DatabaseTable,
                     resource_uuid,
-                }) if resource_uuid == Uuid("00000000-0000-0000-0000-ffff00000000".to_string())
+                }) if resource_uuid == Uuid::new("00000000-0000-0000-0000-ffff00000000").unwrap()
             ));
         }
     }
@@ -196,7 +196,7 @@ mod tests {
                 Ok(RelationValue {
                     resource_type: ResourceType::DatabaseTable,
                     resource_uuid,
-                }) if resource_uuid == Uuid("00000000-0000-0000-0000-ffff00000000".to_string())
+                }) if resource_uuid == Uuid::new("00000000-0000-0000-0000-ffff00000000").unwrap()
             ));
         }
 
@@ -210,7 +210,7 @@ mod tests {
                 Ok(RelationValue {
                     resource_type: ResourceType::DatabaseTable,
                     resource_uuid,
-                }) if resource_uuid == Uuid("00000000-0000-0000-0000-ffff00000000".to_string())
+                }) if resource_uuid == Uuid::new("00000000-0000-0000-0000-ffff00000000").unwrap()
             ));
         }
     }
@@ -256,7 +256,7 @@ mod tests {
         {
             let relation_value = RelationValue {
                 resource_type: ResourceType::DatabaseTable,
-                resource_uuid: Uuid("00000000-0000-0000-0000-ffff00000000".to_string()),
+                resource_uuid: Uuid::new("00000000-0000-0000-0000-ffff00000000").unwrap(),
             };
 
             let json = json!({
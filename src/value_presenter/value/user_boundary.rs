@@ -171,7 +171,7 @@ mod tests {
                     user_uuids,
                     simple_department_uuids,
                     penetrating_department_uuids
-                }) if user_uuids.len() == 0 && simple_department_uuids.len() == 0 && penetrating_department_uuids.len() == 0
+                }) if user_uuids.is_empty() && simple_department_uuids.is_empty() && penetrating_department_uuids.is_empty()
             ));
         }
 
@@ -186,7 +186,7 @@ mod tests {
                     user_uuids,
                     simple_department_uuids,
                     penetrating_department_uuids
-                }) if user_uuids.len() == 0 && simple_department_uuids.len() == 0 && penetrating_department_uuids.len() == 0
+                }) if user_uuids.is_empty() && simple_department_uuids.is_empty() && penetrating_department_uuids.is_empty()
             ));
         }
     }
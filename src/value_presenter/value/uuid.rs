@@ -1,53 +1,147 @@
+use core::{fmt, str::FromStr};
+
 use serde_json::Value;
-use uuid::Uuid as UuidLib;
+use uuid::{Builder, Uuid as UuidLib};
 
 use super::json_codec::JsonCodec;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Uuid(pub String);
+/// A UUID, encoded in JSON as a hyphenated lowercase string, e.g.
+/// `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Uuid(UuidLib);
 
 #[derive(Debug)]
 pub struct ParseUuidError;
 
-fn parse_str(str: &str) -> Result<Uuid, ParseUuidError> {
-    match UuidLib::parse_str(str) {
-        Ok(uuid) => Ok(Uuid(uuid.hyphenated().to_string())),
-        Err(_err) => Err(ParseUuidError),
-    }
-}
-
 impl JsonCodec for Uuid {
     type Err = ParseUuidError;
 
     fn from_json(value: &Value) -> Result<Uuid, ParseUuidError> {
         match value.as_str() {
-            Some(str) => parse_str(str),
+            Some(str) => str.parse(),
             None => Err(ParseUuidError),
         }
     }
 
     fn to_json(&self) -> Value {
-        Value::String(self.0.clone())
+        Value::String(self.to_string())
     }
 }
 
 impl Uuid {
     pub fn new(str: &str) -> Result<Uuid, ParseUuidError> {
-        parse_str(str)
+        str.parse()
+    }
+
+    /// The nil UUID, `00000000-0000-0000-0000-000000000000`.
+    pub fn nil() -> Uuid {
+        Uuid(UuidLib::nil())
+    }
+
+    pub fn from_bytes(bytes: [u8; 16]) -> Uuid {
+        Uuid(UuidLib::from_bytes(bytes))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        self.0.as_bytes()
+    }
+
+    pub fn as_uuid(&self) -> &UuidLib {
+        &self.0
+    }
+
+    pub fn is_nil(&self) -> bool {
+        self.0.is_nil()
+    }
+
+    /// The version number, e.g. `4` for a random UUID, or `0` for the nil UUID.
+    pub fn get_version_num(&self) -> usize {
+        self.0.get_version_num()
+    }
+
+    /// Builds a random (version 4) UUID from 16 random bytes.
+    pub fn from_random_bytes(bytes: [u8; 16]) -> Uuid {
+        Uuid(Builder::from_random_bytes(bytes).into_uuid())
+    }
+
+    /// Builds a time-ordered (version 7) UUID from a Unix timestamp in
+    /// milliseconds and 10 random bytes.
+    pub fn from_unix_millis_and_random_bytes(unix_millis: u64, bytes: [u8; 10]) -> Uuid {
+        Uuid(Builder::from_unix_timestamp_millis(unix_millis, &bytes).into_uuid())
+    }
+
+    /// Builds a name-based (version 5) UUID, the same `namespace` and `name`
+    /// always giving the same UUID.
+    pub fn new_v5(namespace: &Uuid, name: &[u8]) -> Uuid {
+        Uuid(UuidLib::new_v5(&namespace.0, name))
+    }
+
+    /// Generates a random (version 4) UUID with randomness from the host.
+    #[cfg(feature = "random")]
+    pub fn new_v4() -> Uuid {
+        Uuid::from_random_bytes(crate::random::random_bytes())
+    }
+
+    /// Generates a time-ordered (version 7) UUID for `unix_millis` with
+    /// randomness from the host.
+    #[cfg(feature = "random")]
+    pub fn new_v7(unix_millis: u64) -> Uuid {
+        Uuid::from_unix_millis_and_random_bytes(unix_millis, crate::random::random_bytes())
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = ParseUuidError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match UuidLib::parse_str(str) {
+            Ok(uuid) => Ok(Uuid(uuid)),
+            Err(_err) => Err(ParseUuidError),
+        }
+    }
+}
+
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0.hyphenated(), f)
+    }
+}
+
+impl From<UuidLib> for Uuid {
+    fn from(uuid: UuidLib) -> Self {
+        Uuid(uuid)
+    }
+}
+
+impl From<Uuid> for UuidLib {
+    fn from(uuid: Uuid) -> Self {
+        uuid.0
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
 
     #[test]
     fn test_new() {
         {
             let result = Uuid::new("67e55044-10b1-426f-9247-bb680e5fe0c8");
-            let expected = String::from("67e55044-10b1-426f-9247-bb680e5fe0c8");
 
-            assert!(matches!(result, Ok(Uuid(uuid)) if uuid == expected));
+            assert!(
+                matches!(result, Ok(uuid) if uuid.to_string() == "67e55044-10b1-426f-9247-bb680e5fe0c8")
+            );
+        }
+
+        // uppercase and simple formats are normalized
+        {
+            let result = Uuid::new("67E5504410B1426F9247BB680E5FE0C8");
+
+            assert_eq!(
+                result.unwrap().to_json(),
+                json!("67e55044-10b1-426f-9247-bb680e5fe0c8")
+            );
         }
 
         // invalid str
@@ -57,4 +151,34 @@ mod tests {
             assert!(matches!(result, Err(ParseUuidError)));
         }
     }
+
+    #[test]
+    fn test_ord() {
+        let a = Uuid::new("00000000-0000-0000-0000-000000000001").unwrap();
+        let b = Uuid::new("00000000-0000-0000-0000-000000000002").unwrap();
+
+        assert!(Uuid::nil() < a);
+        assert!(a < b);
+    }
+
+    #[test]
+    fn test_generate() {
+        let v4 = Uuid::from_random_bytes([0xff; 16]);
+
+        assert_eq!(v4.get_version_num(), 4);
+        assert_eq!(v4.to_string(), "ffffffff-ffff-4fff-bfff-ffffffffffff");
+
+        let v7 = Uuid::from_unix_millis_and_random_bytes(1_651_217_650_000, [0; 10]);
+
+        assert_eq!(v7.get_version_num(), 7);
+        assert_eq!(v7.to_string(), "0180743d-d150-7000-8000-000000000000");
+        assert!(v7 < Uuid::from_unix_millis_and_random_bytes(1_651_217_650_001, [0; 10]));
+
+        let namespace = Uuid::new("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+        let v5 = Uuid::new_v5(&namespace, b"rust-lang.org");
+
+        assert_eq!(v5.get_version_num(), 5);
+        assert_eq!(v5, Uuid::new_v5(&namespace, b"rust-lang.org"));
+        assert_eq!(v5.to_string(), "c66bbb60-d62e-5f17-a399-3a0bd237c503");
+    }
 }
@@ -0,0 +1,331 @@
+//! Decompression of raw DEFLATE streams (RFC 1951), which is how ZIP archives
+//! compress their entries.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InflateError;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+/// The order in which a dynamic block lists the code lengths of its code
+/// length alphabet.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Inflates `input`, failing if it is not a complete DEFLATE stream or if it
+/// inflates to more than `limit` bytes.
+pub fn inflate(input: &[u8], limit: usize) -> Result<Vec<u8>, InflateError> {
+    let mut bits = Bits {
+        input,
+        position: 0,
+        buffer: 0,
+        count: 0,
+    };
+    let mut output = vec![];
+
+    loop {
+        let last = bits.read(1)? == 1;
+
+        match bits.read(2)? {
+            0 => stored(&mut bits, &mut output, limit)?,
+            1 => {
+                let (literals, distances) = fixed_codes()?;
+                codes(&mut bits, &mut output, limit, &literals, &distances)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(&mut bits)?;
+                codes(&mut bits, &mut output, limit, &literals, &distances)?;
+            }
+            _reserved => return Err(InflateError),
+        }
+
+        if last {
+            return Ok(output);
+        }
+    }
+}
+
+/// Reads the bits of a stream, least significant bit of each byte first.
+struct Bits<'a> {
+    input: &'a [u8],
+    position: usize,
+    buffer: u32,
+    /// The number of bits in `buffer`, always less than 8 between reads.
+    count: u32,
+}
+
+impl Bits<'_> {
+    fn read(&mut self, count: u32) -> Result<u32, InflateError> {
+        while self.count < count {
+            let byte = *self.input.get(self.position).ok_or(InflateError)?;
+
+            self.position += 1;
+            self.buffer |= u32::from(byte) << self.count;
+            self.count += 8;
+        }
+
+        let value = self.buffer & ((1 << count) - 1);
+
+        self.buffer >>= count;
+        self.count -= count;
+
+        Ok(value)
+    }
+
+    /// Skips to the next byte boundary.
+    fn align(&mut self) {
+        self.buffer = 0;
+        self.count = 0;
+    }
+}
+
+/// A canonical Huffman code, decoded one bit at a time.
+struct Huffman {
+    /// The number of symbols with a code of each length.
+    counts: [u16; 16],
+    /// The symbols ordered by their code.
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    /// Builds the code from the code length of each symbol, 0 for a symbol
+    /// that is not used.
+    fn new(lengths: &[u8]) -> Result<Self, InflateError> {
+        let mut counts = [0; 16];
+
+        for length in lengths {
+            *counts.get_mut(usize::from(*length)).ok_or(InflateError)? += 1;
+        }
+
+        // more codes of a length than there are bit patterns left for them
+        let mut left: i32 = 1;
+        for count in &counts[1..] {
+            left = (left << 1) - i32::from(*count);
+
+            if left < 0 {
+                return Err(InflateError);
+            }
+        }
+
+        let mut offsets = [0; 16];
+        for length in 1..15 {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, length) in lengths.iter().enumerate() {
+            if *length != 0 {
+                let offset = &mut offsets[usize::from(*length)];
+
+                symbols[usize::from(*offset)] = symbol as u16;
+                *offset += 1;
+            }
+        }
+
+        Ok(Self { counts, symbols })
+    }
+
+    fn decode(&self, bits: &mut Bits<'_>) -> Result<u16, InflateError> {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+
+        for count in &self.counts[1..] {
+            code |= bits.read(1)? as i32;
+
+            let count = i32::from(*count);
+
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        Err(InflateError)
+    }
+}
+
+fn stored(bits: &mut Bits<'_>, output: &mut Vec<u8>, limit: usize) -> Result<(), InflateError> {
+    bits.align();
+
+    let header = bits
+        .input
+        .get(bits.position..bits.position + 4)
+        .ok_or(InflateError)?;
+    let length = u16::from_le_bytes([header[0], header[1]]);
+    let complement = u16::from_le_bytes([header[2], header[3]]);
+
+    if length != !complement {
+        return Err(InflateError);
+    }
+
+    let start = bits.position + 4;
+    let end = start + usize::from(length);
+    let data = bits.input.get(start..end).ok_or(InflateError)?;
+
+    if output.len() + data.len() > limit {
+        return Err(InflateError);
+    }
+
+    output.extend_from_slice(data);
+    bits.position = end;
+
+    Ok(())
+}
+
+fn fixed_codes() -> Result<(Huffman, Huffman), InflateError> {
+    let mut lengths = [0; 288];
+
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+
+    Ok((Huffman::new(&lengths)?, Huffman::new(&[5; 30])?))
+}
+
+fn dynamic_codes(bits: &mut Bits<'_>) -> Result<(Huffman, Huffman), InflateError> {
+    let literal_count = bits.read(5)? as usize + 257;
+    let distance_count = bits.read(5)? as usize + 1;
+    let code_length_count = bits.read(4)? as usize + 4;
+
+    if literal_count > 286 || distance_count > 30 {
+        return Err(InflateError);
+    }
+
+    let mut code_lengths = [0; 19];
+    for index in &CODE_LENGTH_ORDER[..code_length_count] {
+        code_lengths[*index] = bits.read(3)? as u8;
+    }
+
+    let code_lengths = Huffman::new(&code_lengths)?;
+    let total = literal_count + distance_count;
+    let mut lengths = vec![0; total];
+    let mut index = 0;
+
+    while index < total {
+        let (length, repeat) = match code_lengths.decode(bits)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 if index > 0 => (lengths[index - 1], 3 + bits.read(2)? as usize),
+            17 => (0, 3 + bits.read(3)? as usize),
+            18 => (0, 11 + bits.read(7)? as usize),
+            _other => return Err(InflateError),
+        };
+
+        if index + repeat > total {
+            return Err(InflateError);
+        }
+
+        lengths[index..index + repeat].fill(length);
+        index += repeat;
+    }
+
+    // a block needs a code for its end
+    if lengths[256] == 0 {
+        return Err(InflateError);
+    }
+
+    Ok((
+        Huffman::new(&lengths[..literal_count])?,
+        Huffman::new(&lengths[literal_count..])?,
+    ))
+}
+
+fn codes(
+    bits: &mut Bits<'_>,
+    output: &mut Vec<u8>,
+    limit: usize,
+    literals: &Huffman,
+    distances: &Huffman,
+) -> Result<(), InflateError> {
+    loop {
+        let symbol = usize::from(literals.decode(bits)?);
+
+        if symbol == 256 {
+            return Ok(());
+        }
+
+        if output.len() >= limit {
+            return Err(InflateError);
+        }
+
+        if symbol < 256 {
+            output.push(symbol as u8);
+            continue;
+        }
+
+        let index = symbol - 257;
+        let length = usize::from(*LENGTH_BASE.get(index).ok_or(InflateError)?)
+            + bits.read(u32::from(LENGTH_EXTRA[index]))? as usize;
+
+        let index = usize::from(distances.decode(bits)?);
+        let distance = usize::from(*DISTANCE_BASE.get(index).ok_or(InflateError)?)
+            + bits.read(u32::from(DISTANCE_EXTRA[index]))? as usize;
+
+        if distance > output.len() || output.len() + length > limit {
+            return Err(InflateError);
+        }
+
+        // the copied bytes may overlap the bytes being written
+        let start = output.len() - distance;
+        for offset in 0..length {
+            let byte = output[start + offset];
+            output.push(byte);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inflate() {
+        // a stored block
+        assert_eq!(
+            inflate(&[0x01, 0x03, 0x00, 0xfc, 0xff, b'a', b'b', b'c'], 3).unwrap(),
+            b"abc"
+        );
+        assert_eq!(
+            inflate(&[0x01, 0x03, 0x00, 0xfc, 0xff, b'a', b'b', b'c'], 2),
+            Err(InflateError)
+        );
+
+        // a fixed block copying overlapping bytes
+        let fixed = [0x4b, 0x4c, 0x4a, 0x4e, 0x84, 0x21, 0x00];
+        assert_eq!(inflate(&fixed, 100).unwrap(), b"abcabcabcabc");
+        assert_eq!(inflate(&fixed, 11), Err(InflateError));
+
+        // a dynamic block
+        let dynamic = [
+            0x05, 0xc1, 0x01, 0x01, 0x00, 0x00, 0x00, 0x82, 0xa0, 0xad, 0xd8, 0xff, 0x0f, 0x01,
+            0x00, 0x55, 0x55, 0x05, 0x40, 0x55, 0x55, 0x6d, 0xdb, 0x0e,
+        ];
+        assert_eq!(
+            inflate(&dynamic, 100).unwrap(),
+            b"aaaaaaaaaabbbbbbbbbbaaaaaaaaaabbbbbbbbbbccccc".to_vec()
+        );
+
+        // truncated streams
+        assert_eq!(inflate(&dynamic[..12], 100), Err(InflateError));
+        assert_eq!(inflate(&[], 100), Err(InflateError));
+        // a reserved block type
+        assert_eq!(inflate(&[0x07], 100), Err(InflateError));
+    }
+}
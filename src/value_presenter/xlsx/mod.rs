@@ -0,0 +1,710 @@
+//! Reading columns of list values from an XLSX workbook.
+//!
+//! `parse_columns` reads the first worksheet of a workbook the way
+//! `csv::parse_columns` reads a CSV, each column becoming a list value of the
+//! given list field type. Cells are read as the text a CSV export of the
+//! worksheet would hold: shared and inline strings as they are, booleans as
+//! `TRUE`/`FALSE`, numbers with a date or time format as ISO dates or times
+//! and other numbers as written. Formulas are not evaluated, their cached
+//! results are read instead.
+
+mod inflate;
+mod xml;
+mod zip;
+
+use std::collections::HashMap;
+
+use super::{
+    csv::{item_field_types, table_from_records, CsvTable},
+    field_type::FieldType,
+    value::{
+        json_codec::JsonCodec,
+        naive_date_time::{Duration, NaiveDateTime},
+    },
+};
+use xml::Event;
+use zip::Archive;
+
+const WORKBOOK: &str = "xl/workbook.xml";
+const WORKBOOK_RELATIONSHIPS: &str = "xl/_rels/workbook.xml.rels";
+const SHARED_STRINGS: &str = "xl/sharedStrings.xml";
+const STYLES: &str = "xl/styles.xml";
+/// The part of the first worksheet when the workbook has no relationship
+/// for it.
+const FIRST_WORKSHEET: &str = "xl/worksheets/sheet1.xml";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XlsxError {
+    /// The bytes are not a ZIP archive, or an entry of the archive is corrupt.
+    InvalidArchive,
+    /// An entry of the archive is compressed with a method other than DEFLATE.
+    UnsupportedCompression(u16),
+    /// The part at this path is missing from the workbook.
+    MissingPart(String),
+    /// The part at this path is not well-formed XML, or refers to something
+    /// that does not exist.
+    InvalidXml(String),
+    /// The field type is not a list field type whose items can be converted
+    /// from a `SingleLineField`.
+    UnsupportedFieldType(FieldType),
+}
+
+/// Parses the columns of the first worksheet of the workbook in `bytes` into
+/// list values of `field_types`, the first row being the header row if
+/// `header` is `true`. Rows without any value are skipped.
+pub fn parse_columns(
+    bytes: &[u8],
+    field_types: &[FieldType],
+    header: bool,
+) -> Result<CsvTable, XlsxError> {
+    let item_field_types =
+        item_field_types(field_types).map_err(XlsxError::UnsupportedFieldType)?;
+
+    let archive = Archive::new(bytes)?;
+    let (worksheet, date1904) = first_worksheet(&archive)?;
+
+    let workbook = Workbook {
+        shared_strings: read_part(&archive, SHARED_STRINGS)?
+            .map(|events| shared_strings(&events))
+            .unwrap_or_default(),
+        date_styles: read_part(&archive, STYLES)?
+            .map(|events| date_styles(&events))
+            .unwrap_or_default(),
+        date1904,
+    };
+
+    let events = read_part(&archive, &worksheet)?.ok_or(XlsxError::MissingPart(worksheet))?;
+    let records = workbook.records(&events, &item_field_types)?;
+
+    Ok(table_from_records(
+        records,
+        field_types,
+        &item_field_types,
+        header,
+    ))
+}
+
+fn read_part(archive: &Archive<'_>, path: &str) -> Result<Option<Vec<Event>>, XlsxError> {
+    let bytes = match archive.read(path)? {
+        Some(bytes) => bytes,
+        None => return Ok(None),
+    };
+
+    String::from_utf8(bytes)
+        .ok()
+        .and_then(|text| xml::parse(&text).ok())
+        .map(Some)
+        .ok_or_else(|| XlsxError::InvalidXml(path.to_string()))
+}
+
+/// The path of the part of the first worksheet, and whether the workbook
+/// counts dates from 1904 rather than 1900.
+fn first_worksheet(archive: &Archive<'_>) -> Result<(String, bool), XlsxError> {
+    let workbook = read_part(archive, WORKBOOK)?
+        .ok_or_else(|| XlsxError::MissingPart(WORKBOOK.to_string()))?;
+
+    let date1904 = workbook.iter().any(|event| {
+        is_start(event, "workbookPr")
+            && matches!(event.attribute("date1904"), Some("1") | Some("true"))
+    });
+
+    let id = workbook
+        .iter()
+        .find(|event| is_start(event, "sheet"))
+        .and_then(|event| event.attribute("id"));
+    let relationships = read_part(archive, WORKBOOK_RELATIONSHIPS)?.unwrap_or_default();
+
+    let target = id.and_then(|id| {
+        relationships
+            .iter()
+            .find(|event| is_start(event, "Relationship") && event.attribute("Id") == Some(id))
+            .and_then(|event| event.attribute("Target"))
+    });
+
+    let path = match target {
+        // relative to the workbook part, or to the root of the archive
+        Some(target) => match target.strip_prefix('/') {
+            Some(target) => target.to_string(),
+            None => format!("xl/{}", target),
+        },
+        None => FIRST_WORKSHEET.to_string(),
+    };
+
+    Ok((path, date1904))
+}
+
+fn is_start(event: &Event, name: &str) -> bool {
+    matches!(event, Event::Start { name: start, .. } if start == name)
+}
+
+fn is_end(event: &Event, name: &str) -> bool {
+    matches!(event, Event::End(end) if end == name)
+}
+
+/// The text of each string of the shared string table, leaving out phonetic
+/// readings.
+fn shared_strings(events: &[Event]) -> Vec<String> {
+    let mut strings = vec![];
+    let mut string = None;
+    let mut text = false;
+    let mut phonetic = false;
+
+    for event in events {
+        match event {
+            Event::Start { name, .. } => match name.as_str() {
+                "si" => string = Some(String::new()),
+                "t" => text = true,
+                "rPh" => phonetic = true,
+                _ => (),
+            },
+            Event::End(name) => match name.as_str() {
+                "si" => strings.extend(string.take()),
+                "t" => text = false,
+                "rPh" => phonetic = false,
+                _ => (),
+            },
+            Event::Text(str) => {
+                if let Some(string) = string.as_mut().filter(|_| text && !phonetic) {
+                    string.push_str(str);
+                }
+            }
+        }
+    }
+
+    strings
+}
+
+/// Whether each cell style formats numbers as dates or times.
+fn date_styles(events: &[Event]) -> Vec<bool> {
+    let mut custom_formats: HashMap<u32, bool> = HashMap::new();
+    let mut styles = vec![];
+    let mut cell_formats = false;
+
+    for event in events {
+        if is_start(event, "numFmt") {
+            let id = event.attribute("numFmtId").and_then(|id| id.parse().ok());
+
+            if let (Some(id), Some(code)) = (id, event.attribute("formatCode")) {
+                custom_formats.insert(id, is_date_format(code));
+            }
+        } else if is_start(event, "cellXfs") {
+            cell_formats = true;
+        } else if is_end(event, "cellXfs") {
+            cell_formats = false;
+        } else if cell_formats && is_start(event, "xf") {
+            let id = event
+                .attribute("numFmtId")
+                .and_then(|id| id.parse().ok())
+                .unwrap_or(0);
+
+            styles.push(
+                custom_formats
+                    .get(&id)
+                    .copied()
+                    .unwrap_or_else(|| is_date_format_id(id)),
+            );
+        }
+    }
+
+    styles
+}
+
+/// Whether the built-in number format `id` is a date or time format.
+fn is_date_format_id(id: u32) -> bool {
+    matches!(id, 14..=22 | 27..=36 | 45..=47 | 50..=58)
+}
+
+/// Whether the number format `code` has a date or time part, ignoring quoted
+/// text, escaped characters and bracketed colors or conditions.
+fn is_date_format(code: &str) -> bool {
+    let mut chars = code.chars();
+    let mut quoted = false;
+
+    while let Some(char) = chars.next() {
+        match char {
+            '"' => quoted = !quoted,
+            _ if quoted => (),
+            '\\' | '_' | '*' => {
+                chars.next();
+            }
+            '[' => {
+                chars.by_ref().find(|char| *char == ']');
+            }
+            'y' | 'Y' | 'm' | 'M' | 'd' | 'D' | 'h' | 'H' | 's' | 'S' => return true,
+            _ => (),
+        }
+    }
+
+    false
+}
+
+/// The zero based index of the column of a cell reference such as `B2`.
+fn column_index(reference: &str) -> Option<usize> {
+    let letters = reference
+        .chars()
+        .take_while(char::is_ascii_alphabetic)
+        .collect::<Vec<_>>();
+
+    // the last column is XFD
+    if letters.is_empty() || letters.len() > 3 {
+        return None;
+    }
+
+    let column = letters.iter().fold(0, |column, letter| {
+        column * 26 + usize::from(letter.to_ascii_uppercase() as u8 - b'A') + 1
+    });
+
+    Some(column - 1)
+}
+
+struct Workbook {
+    shared_strings: Vec<String>,
+    date_styles: Vec<bool>,
+    date1904: bool,
+}
+
+struct Cell {
+    column: usize,
+    /// The `t` attribute, the type of the value.
+    kind: String,
+    /// The `s` attribute, the index of the cell style.
+    style: usize,
+    text: String,
+}
+
+impl Workbook {
+    /// The text of the cells of each row of a worksheet with a value.
+    fn records(
+        &self,
+        events: &[Event],
+        item_field_types: &[FieldType],
+    ) -> Result<Vec<Vec<String>>, XlsxError> {
+        let mut records = vec![];
+        let mut record: Vec<String> = vec![];
+        let mut cell: Option<Cell> = None;
+        let mut text = false;
+        let mut phonetic = false;
+
+        for event in events {
+            match event {
+                Event::Start { name, .. } => match name.as_str() {
+                    "row" => record.clear(),
+                    "c" => {
+                        cell = Some(Cell {
+                            column: event
+                                .attribute("r")
+                                .and_then(column_index)
+                                .unwrap_or(record.len()),
+                            kind: event.attribute("t").unwrap_or("n").to_string(),
+                            style: event
+                                .attribute("s")
+                                .and_then(|style| style.parse().ok())
+                                .unwrap_or(0),
+                            text: String::new(),
+                        })
+                    }
+                    // the value, or the text of an inline string
+                    "v" | "t" => text = true,
+                    "rPh" => phonetic = true,
+                    _ => (),
+                },
+                Event::End(name) => match name.as_str() {
+                    "row" if record.iter().any(|value| !value.is_empty()) => {
+                        records.push(std::mem::take(&mut record));
+                    }
+                    "c" => {
+                        if let Some(cell) = cell.take() {
+                            let value =
+                                self.cell_value(&cell, item_field_types.get(cell.column))?;
+
+                            if record.len() <= cell.column {
+                                record.resize(cell.column + 1, String::new());
+                            }
+
+                            record[cell.column] = value;
+                        }
+                    }
+                    "v" | "t" => text = false,
+                    "rPh" => phonetic = false,
+                    _ => (),
+                },
+                Event::Text(str) => {
+                    if let Some(cell) = cell.as_mut().filter(|_| text && !phonetic) {
+                        cell.text.push_str(str);
+                    }
+                }
+            }
+        }
+
+        Ok(records)
+    }
+
+    fn cell_value(
+        &self,
+        cell: &Cell,
+        item_field_type: Option<&FieldType>,
+    ) -> Result<String, XlsxError> {
+        let text = cell.text.trim();
+
+        if text.is_empty() {
+            return Ok(String::new());
+        }
+
+        let value = match cell.kind.as_str() {
+            "s" => text
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| self.shared_strings.get(index))
+                .cloned()
+                .ok_or_else(|| XlsxError::InvalidXml(SHARED_STRINGS.to_string()))?,
+            "b" => if text == "1" { "TRUE" } else { "FALSE" }.to_string(),
+            "n" => match text.parse::<f64>() {
+                Ok(number) => self
+                    .date_time_text(cell.style, number, item_field_type)
+                    .unwrap_or_else(|| number_text(text, number)),
+                Err(_err) => text.to_string(),
+            },
+            // inline strings, formula strings, errors and ISO dates
+            _other => cell.text.clone(),
+        };
+
+        Ok(value)
+    }
+
+    /// The text of a number with a date or time style, formatted for the item
+    /// field type of its column.
+    fn date_time_text(
+        &self,
+        style: usize,
+        serial: f64,
+        item_field_type: Option<&FieldType>,
+    ) -> Option<String> {
+        if !self.date_styles.get(style).copied().unwrap_or(false) {
+            return None;
+        }
+
+        let date_time = self.date_time(serial)?;
+        let value = match item_field_type {
+            Some(FieldType::DateField) => date_time.date().to_json(),
+            Some(FieldType::TimeField) => date_time.time().to_json(),
+            _ => date_time.to_json(),
+        };
+
+        value.as_str().map(str::to_string)
+    }
+
+    /// The date time of a serial number, the number of days since the start
+    /// of the date system of the workbook.
+    fn date_time(&self, serial: f64) -> Option<NaiveDateTime> {
+        if serial.is_nan() || serial < 0.0 {
+            return None;
+        }
+
+        let start = if self.date1904 {
+            NaiveDateTime::new(1904, 1, 1, 0, 0, 0, 0)
+        } else if serial < 61.0 {
+            // serial 60 is 1900-02-29, which Excel counts as a day
+            NaiveDateTime::new(1899, 12, 31, 0, 0, 0, 0)
+        } else {
+            NaiveDateTime::new(1899, 12, 30, 0, 0, 0, 0)
+        };
+
+        start.checked_add(Duration::seconds((serial * 86400.0).round() as i64))
+    }
+}
+
+/// The text of a number cell, written as an integer when it is one.
+fn number_text(text: &str, number: f64) -> String {
+    if text.parse::<i64>().is_ok() {
+        text.to_string()
+    } else {
+        number.to_string()
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::{zip::crc32, *};
+    use crate::value_presenter::{
+        cast::CastError,
+        csv::CellError,
+        literal_list_value::ListFieldValue,
+        literal_naive_value::{
+            BooleanFieldValue, DateFieldValue, NumericFieldValue, SingleLineFieldValue,
+            TimeFieldValue,
+        },
+        literal_value_presenter::LiteralValuePresenter,
+        value::{naive_date::NaiveDate, naive_time::NaiveTime, number::Number},
+    };
+
+    fn push_u16(bytes: &mut Vec<u8>, value: u16) {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn push_u32(bytes: &mut Vec<u8>, value: u32) {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// A ZIP archive of `entries`, stored without compression.
+    pub(in crate::value_presenter) fn stored_zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut bytes = vec![];
+        let mut directory = vec![];
+
+        for (name, contents) in entries {
+            let offset = bytes.len() as u32;
+            let crc = crc32(contents);
+
+            push_u32(&mut bytes, 0x0403_4b50);
+            push_u16(&mut bytes, 20);
+            push_u16(&mut bytes, 0);
+            push_u16(&mut bytes, 0);
+            push_u32(&mut bytes, 0);
+            push_u32(&mut bytes, crc);
+            push_u32(&mut bytes, contents.len() as u32);
+            push_u32(&mut bytes, contents.len() as u32);
+            push_u16(&mut bytes, name.len() as u16);
+            push_u16(&mut bytes, 0);
+            bytes.extend_from_slice(name.as_bytes());
+            bytes.extend_from_slice(contents);
+
+            push_u32(&mut directory, 0x0201_4b50);
+            push_u16(&mut directory, 20);
+            push_u16(&mut directory, 20);
+            push_u16(&mut directory, 0);
+            push_u16(&mut directory, 0);
+            push_u32(&mut directory, 0);
+            push_u32(&mut directory, crc);
+            push_u32(&mut directory, contents.len() as u32);
+            push_u32(&mut directory, contents.len() as u32);
+            push_u16(&mut directory, name.len() as u16);
+            push_u32(&mut directory, 0);
+            push_u32(&mut directory, 0);
+            push_u32(&mut directory, 0);
+            push_u32(&mut directory, offset);
+            directory.extend_from_slice(name.as_bytes());
+        }
+
+        let directory_offset = bytes.len() as u32;
+
+        bytes.extend_from_slice(&directory);
+        push_u32(&mut bytes, 0x0605_4b50);
+        push_u32(&mut bytes, 0);
+        push_u16(&mut bytes, entries.len() as u16);
+        push_u16(&mut bytes, entries.len() as u16);
+        push_u32(&mut bytes, directory.len() as u32);
+        push_u32(&mut bytes, directory_offset);
+        push_u16(&mut bytes, 0);
+
+        bytes
+    }
+
+    const WORKBOOK_XML: &[u8] = br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+  <workbookPr/>
+  <sheets><sheet name="Scores" sheetId="1" r:id="rId2"/><sheet name="Other" sheetId="2" r:id="rId3"/></sheets>
+</workbook>"#;
+
+    const RELATIONSHIPS_XML: &[u8] = br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>
+  <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/scores.xml"/>
+  <Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="/xl/worksheets/other.xml"/>
+</Relationships>"#;
+
+    const SHARED_STRINGS_XML: &[u8] = "<sst count=\"3\" uniqueCount=\"3\">\
+        <si><t>name</t></si>\
+        <si><t xml:space=\"preserve\">score </t></si>\
+        <si><r><t>Ja</t></r><r><rPr><b/></rPr><t>net</t></r><rPh sb=\"0\" eb=\"2\"><t>ジャネット</t></rPh></si>\
+        </sst>"
+        .as_bytes();
+
+    const STYLES_XML: &[u8] = br#"<styleSheet>
+  <numFmts count="2"><numFmt numFmtId="164" formatCode="yyyy/mm/dd"/><numFmt numFmtId="165" formatCode="&quot;Day&quot;\ 0.0"/></numFmts>
+  <cellStyleXfs count="1"><xf numFmtId="14"/></cellStyleXfs>
+  <cellXfs count="4"><xf numFmtId="0"/><xf numFmtId="164"/><xf numFmtId="20"/><xf numFmtId="165"/></cellXfs>
+</styleSheet>"#;
+
+    const SHEET_XML: &[u8] = br#"<worksheet><sheetData>
+  <row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c><c r="C1" t="inlineStr"><is><t>passed</t></is></c><c r="D1" t="inlineStr"><is><t>date</t></is></c><c r="E1" t="inlineStr"><is><t>time</t></is></c><c r="F1" t="inlineStr"><is><t>updated</t></is></c></row>
+  <row r="2"><c r="A2" t="s"><v>2</v></c><c r="B2"><v>42</v></c><c r="C2" t="b"><v>1</v></c><c r="D2" s="1"><v>45412</v></c><c r="E2" s="2"><v>0.5</v></c><c r="F2" s="1"><v>45412.75</v></c></row>
+  <row r="3"/>
+  <row r="4"><c r="A4" t="inlineStr"><is><t>Bob &amp; co</t></is></c><c r="B4" s="3"><v>1.5</v></c><c r="D4" t="str"><f>"n/a"</f><v>n/a</v></c><c r="G4"><v>7</v></c></row>
+</sheetData></worksheet>"#;
+
+    fn workbook(sheet: &[u8]) -> Vec<u8> {
+        stored_zip(&[
+            ("[Content_Types].xml", b"<Types/>"),
+            ("xl/workbook.xml", WORKBOOK_XML),
+            ("xl/_rels/workbook.xml.rels", RELATIONSHIPS_XML),
+            ("xl/sharedStrings.xml", SHARED_STRINGS_XML),
+            ("xl/styles.xml", STYLES_XML),
+            ("xl/worksheets/scores.xml", sheet),
+        ])
+    }
+
+    #[test]
+    fn test_parse_columns() {
+        let field_types = [
+            FieldType::SingleLineListField,
+            FieldType::NumericListField,
+            FieldType::BooleanListField,
+            FieldType::DateListField,
+            FieldType::TimeListField,
+            FieldType::SingleLineListField,
+        ];
+
+        let table = parse_columns(&workbook(SHEET_XML), &field_types, true).unwrap();
+
+        assert_eq!(
+            table.header,
+            vec!["name", "score ", "passed", "date", "time", "updated"]
+        );
+        assert_eq!(
+            table.columns,
+            vec![
+                LiteralValuePresenter::SingleLineListField(ListFieldValue::Value(vec![
+                    SingleLineFieldValue::Value("Janet".to_string()),
+                    SingleLineFieldValue::Value("Bob & co".to_string()),
+                ])),
+                LiteralValuePresenter::NumericListField(ListFieldValue::Value(vec![
+                    NumericFieldValue::Value(Number::Integer(42)),
+                    NumericFieldValue::Value(Number::Float(1.5)),
+                ])),
+                LiteralValuePresenter::BooleanListField(ListFieldValue::Value(vec![
+                    BooleanFieldValue::Value(true),
+                    BooleanFieldValue::Nil,
+                ])),
+                LiteralValuePresenter::DateListField(ListFieldValue::Value(vec![
+                    DateFieldValue::Value(NaiveDate::new(2024, 4, 30)),
+                    DateFieldValue::Nil,
+                ])),
+                LiteralValuePresenter::TimeListField(ListFieldValue::Value(vec![
+                    TimeFieldValue::Value(NaiveTime::new(12, 0, 0, 0)),
+                    TimeFieldValue::Nil,
+                ])),
+                LiteralValuePresenter::SingleLineListField(ListFieldValue::Value(vec![
+                    SingleLineFieldValue::Value("2024-04-30T18:00:00".to_string()),
+                    SingleLineFieldValue::Nil,
+                ])),
+            ]
+        );
+        assert_eq!(
+            table.errors,
+            vec![CellError {
+                row: 1,
+                column: 3,
+                value: "n/a".to_string(),
+                error: CastError::InvalidValue {
+                    from: FieldType::SingleLineField,
+                    to: FieldType::DateField,
+                },
+            }]
+        );
+
+        // without a header row
+        let table = parse_columns(&workbook(SHEET_XML), &field_types[..1], false).unwrap();
+        assert!(table.header.is_empty());
+        assert_eq!(
+            table.columns,
+            vec![LiteralValuePresenter::SingleLineListField(
+                ListFieldValue::Value(vec![
+                    SingleLineFieldValue::Value("name".to_string()),
+                    SingleLineFieldValue::Value("Janet".to_string()),
+                    SingleLineFieldValue::Value("Bob & co".to_string()),
+                ])
+            )]
+        );
+    }
+
+    #[test]
+    fn test_parse_columns_errors() {
+        assert_eq!(
+            parse_columns(b"not a workbook", &[FieldType::NumericListField], true),
+            Err(XlsxError::InvalidArchive)
+        );
+        assert_eq!(
+            parse_columns(&workbook(SHEET_XML), &[FieldType::FileListField], true),
+            Err(XlsxError::UnsupportedFieldType(FieldType::FileListField))
+        );
+        assert_eq!(
+            parse_columns(
+                &stored_zip(&[("xl/worksheets/sheet1.xml", b"<worksheet/>")]),
+                &[FieldType::NumericListField],
+                true
+            ),
+            Err(XlsxError::MissingPart("xl/workbook.xml".to_string()))
+        );
+        assert_eq!(
+            parse_columns(
+                &workbook(b"<worksheet><sheetData></worksheet>"),
+                &[FieldType::NumericListField],
+                true
+            ),
+            Err(XlsxError::InvalidXml(
+                "xl/worksheets/scores.xml".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_columns(
+                &workbook(b"<worksheet><row><c t=\"s\"><v>9</v></c></row></worksheet>"),
+                &[FieldType::NumericListField],
+                true
+            ),
+            Err(XlsxError::InvalidXml("xl/sharedStrings.xml".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_date_formats() {
+        assert!(is_date_format("yyyy-mm-dd"));
+        assert!(is_date_format("[$-409]h:mm AM/PM"));
+        assert!(!is_date_format("\"Day\" 0"));
+        assert!(!is_date_format("[Red]0.00;\\d0"));
+        assert!(!is_date_format("General"));
+
+        assert!(is_date_format_id(14));
+        assert!(!is_date_format_id(0));
+    }
+
+    #[test]
+    fn test_date_time() {
+        let workbook = Workbook {
+            shared_strings: vec![],
+            date_styles: vec![],
+            date1904: false,
+        };
+
+        assert_eq!(
+            workbook.date_time(1.0),
+            Some(NaiveDateTime::new(1900, 1, 1, 0, 0, 0, 0))
+        );
+        assert_eq!(
+            workbook.date_time(59.25),
+            Some(NaiveDateTime::new(1900, 2, 28, 6, 0, 0, 0))
+        );
+        assert_eq!(
+            workbook.date_time(61.0),
+            Some(NaiveDateTime::new(1900, 3, 1, 0, 0, 0, 0))
+        );
+        assert_eq!(workbook.date_time(-1.0), None);
+        assert_eq!(workbook.date_time(f64::NAN), None);
+
+        let workbook = Workbook {
+            date1904: true,
+            ..workbook
+        };
+        assert_eq!(
+            workbook.date_time(1.5),
+            Some(NaiveDateTime::new(1904, 1, 2, 12, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_column_index() {
+        assert_eq!(column_index("A1"), Some(0));
+        assert_eq!(column_index("b12"), Some(1));
+        assert_eq!(column_index("AA3"), Some(26));
+        assert_eq!(column_index("XFD1"), Some(16383));
+        assert_eq!(column_index("12"), None);
+    }
+}
@@ -0,0 +1,249 @@
+//! A minimal reader of the XML parts of a workbook. Names are read without
+//! their namespace prefix, and declarations, comments and doctypes are
+//! skipped.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XmlError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// An opening tag, a self-closing tag being followed by its `End`.
+    Start {
+        name: String,
+        attributes: Vec<(String, String)>,
+    },
+    End(String),
+    /// Character data with its entities decoded.
+    Text(String),
+}
+
+impl Event {
+    /// The value of the attribute named `name` of a `Start` event.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        match self {
+            Event::Start { attributes, .. } => attributes
+                .iter()
+                .find(|(attribute, _value)| attribute == name)
+                .map(|(_attribute, value)| value.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the events of `text`, failing if the tags are not well nested.
+pub fn parse(text: &str) -> Result<Vec<Event>, XmlError> {
+    let mut rest = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut events = vec![];
+    let mut open = vec![];
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = skip_past(after, "-->")?;
+        } else if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            let end = after.find("]]>").ok_or(XmlError)?;
+
+            events.push(Event::Text(after[..end].to_string()));
+            rest = &after[end + 3..];
+        } else if let Some(after) = rest.strip_prefix("<?") {
+            rest = skip_past(after, "?>")?;
+        } else if let Some(after) = rest.strip_prefix("<!") {
+            rest = skip_past(after, ">")?;
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let end = after.find('>').ok_or(XmlError)?;
+            let name = local_name(after[..end].trim_end());
+
+            if open.pop() != Some(name) {
+                return Err(XmlError);
+            }
+
+            events.push(Event::End(name.to_string()));
+            rest = &after[end + 1..];
+        } else if let Some(after) = rest.strip_prefix('<') {
+            let end = tag_end(after)?;
+            let tag = &after[..end];
+            let (tag, empty) = match tag.strip_suffix('/') {
+                Some(tag) => (tag, true),
+                None => (tag, false),
+            };
+
+            let name_end = tag
+                .find(|char: char| char.is_ascii_whitespace())
+                .unwrap_or(tag.len());
+            let name = local_name(&tag[..name_end]);
+
+            if name.is_empty() {
+                return Err(XmlError);
+            }
+
+            events.push(Event::Start {
+                name: name.to_string(),
+                attributes: parse_attributes(&tag[name_end..])?,
+            });
+
+            if empty {
+                events.push(Event::End(name.to_string()));
+            } else {
+                open.push(name);
+            }
+
+            rest = &after[end + 1..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+
+            events.push(Event::Text(decode(&rest[..end])?));
+            rest = &rest[end..];
+        }
+    }
+
+    if !open.is_empty() {
+        return Err(XmlError);
+    }
+
+    Ok(events)
+}
+
+fn skip_past<'a>(text: &'a str, pattern: &str) -> Result<&'a str, XmlError> {
+    let end = text.find(pattern).ok_or(XmlError)?;
+
+    Ok(&text[end + pattern.len()..])
+}
+
+/// The offset of the `>` closing a tag, skipping quoted attribute values.
+fn tag_end(text: &str) -> Result<usize, XmlError> {
+    let mut quote = None;
+
+    for (offset, char) in text.char_indices() {
+        match (char, quote) {
+            ('"', None) | ('\'', None) => quote = Some(char),
+            (char, Some(open)) if char == open => quote = None,
+            ('>', None) => return Ok(offset),
+            _ => (),
+        }
+    }
+
+    Err(XmlError)
+}
+
+fn local_name(name: &str) -> &str {
+    match name.rfind(':') {
+        Some(colon) => &name[colon + 1..],
+        None => name,
+    }
+}
+
+fn parse_attributes(mut text: &str) -> Result<Vec<(String, String)>, XmlError> {
+    let mut attributes = vec![];
+
+    loop {
+        text = text.trim_start();
+
+        if text.is_empty() {
+            return Ok(attributes);
+        }
+
+        let equals = text.find('=').ok_or(XmlError)?;
+        let name = local_name(text[..equals].trim_end());
+        let value = text[equals + 1..].trim_start();
+
+        let quote = match value.chars().next() {
+            Some(quote @ '"') | Some(quote @ '\'') => quote,
+            _ => return Err(XmlError),
+        };
+        let end = value[1..].find(quote).ok_or(XmlError)? + 1;
+
+        attributes.push((name.to_string(), decode(&value[1..end])?));
+        text = &value[end + 1..];
+    }
+}
+
+/// Decodes the predefined entities and character references of `text`.
+fn decode(text: &str) -> Result<String, XmlError> {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+
+        let after = &rest[start + 1..];
+        let end = after.find(';').ok_or(XmlError)?;
+
+        let char = match &after[..end] {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            reference => {
+                let code = if let Some(hex) = reference.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16)
+                } else if let Some(decimal) = reference.strip_prefix('#') {
+                    decimal.parse()
+                } else {
+                    return Err(XmlError);
+                };
+
+                code.ok().and_then(char::from_u32).ok_or(XmlError)?
+            }
+        };
+
+        decoded.push(char);
+        rest = &after[end + 1..];
+    }
+
+    decoded.push_str(rest);
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start(name: &str, attributes: &[(&str, &str)]) -> Event {
+        Event::Start {
+            name: name.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let text = "\u{feff}<?xml version=\"1.0\"?>\n\
+                    <!-- a comment -->\
+                    <x:sheet xmlns:x=\"urn:x\" r:id='rId1' name=\"a &amp; &#x62;&#99;\">\
+                    <c r=\"A1\"/><t>1 &lt; 2</t><t><![CDATA[<b>]]></t>\
+                    </x:sheet>";
+
+        assert_eq!(
+            parse(text).unwrap(),
+            vec![
+                Event::Text("\n".to_string()),
+                start(
+                    "sheet",
+                    &[("x", "urn:x"), ("id", "rId1"), ("name", "a & bc")]
+                ),
+                start("c", &[("r", "A1")]),
+                Event::End("c".to_string()),
+                start("t", &[]),
+                Event::Text("1 < 2".to_string()),
+                Event::End("t".to_string()),
+                start("t", &[]),
+                Event::Text("<b>".to_string()),
+                Event::End("t".to_string()),
+                Event::End("sheet".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse("<c r=\"A1\"/>").unwrap()[0].attribute("r"),
+            Some("A1")
+        );
+
+        assert_eq!(parse("<a><b></a></b>"), Err(XmlError));
+        assert_eq!(parse("<a>"), Err(XmlError));
+        assert_eq!(parse("<a>&nbsp;</a>"), Err(XmlError));
+        assert_eq!(parse("<a b=c/>"), Err(XmlError));
+    }
+}
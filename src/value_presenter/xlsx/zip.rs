@@ -0,0 +1,217 @@
+//! Reading the entries of a ZIP archive, which is how an XLSX workbook stores
+//! its parts. Entries are stored or DEFLATE compressed; ZIP64 archives and
+//! encrypted entries are not supported.
+
+use super::{inflate::inflate, XlsxError};
+
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+const CENTRAL_DIRECTORY_HEADER: u32 = 0x0201_4b50;
+const LOCAL_FILE_HEADER: u32 = 0x0403_4b50;
+
+/// The size of the end of central directory record without its comment.
+const END_OF_CENTRAL_DIRECTORY_SIZE: usize = 22;
+
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+
+pub struct Archive<'a> {
+    bytes: &'a [u8],
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    name: String,
+    method: u16,
+    crc32: u32,
+    compressed_size: usize,
+    size: usize,
+    /// The offset of the local file header.
+    offset: usize,
+}
+
+impl<'a> Archive<'a> {
+    /// Reads the central directory of the archive in `bytes`.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, XlsxError> {
+        // the record is at the end, followed by a comment of up to 64 KiB
+        let last = bytes
+            .len()
+            .checked_sub(END_OF_CENTRAL_DIRECTORY_SIZE)
+            .ok_or(XlsxError::InvalidArchive)?;
+        let end = (last.saturating_sub(usize::from(u16::MAX))..=last)
+            .rev()
+            .find(|offset| read_u32(bytes, *offset) == Some(END_OF_CENTRAL_DIRECTORY))
+            .ok_or(XlsxError::InvalidArchive)?;
+
+        let count = read_u16(bytes, end + 10).ok_or(XlsxError::InvalidArchive)?;
+        let mut offset = read_u32(bytes, end + 16).ok_or(XlsxError::InvalidArchive)? as usize;
+        let mut entries = Vec::with_capacity(usize::from(count));
+
+        for _index in 0..count {
+            if read_u32(bytes, offset) != Some(CENTRAL_DIRECTORY_HEADER) {
+                return Err(XlsxError::InvalidArchive);
+            }
+
+            let field = |at: usize| read_u32(bytes, offset + at).ok_or(XlsxError::InvalidArchive);
+            let short_field =
+                |at: usize| read_u16(bytes, offset + at).ok_or(XlsxError::InvalidArchive);
+
+            let name_length = usize::from(short_field(28)?);
+            let name = bytes
+                .get(offset + 46..offset + 46 + name_length)
+                .ok_or(XlsxError::InvalidArchive)?;
+
+            entries.push(Entry {
+                name: String::from_utf8_lossy(name).into_owned(),
+                method: short_field(10)?,
+                crc32: field(16)?,
+                compressed_size: field(20)? as usize,
+                size: field(24)? as usize,
+                offset: field(42)? as usize,
+            });
+
+            offset +=
+                46 + name_length + usize::from(short_field(30)?) + usize::from(short_field(32)?);
+        }
+
+        Ok(Self { bytes, entries })
+    }
+
+    /// Reads the contents of the entry named `name`, ignoring ASCII case as
+    /// part names of a workbook do. `None` if there is no such entry.
+    pub fn read(&self, name: &str) -> Result<Option<Vec<u8>>, XlsxError> {
+        let entry = match self
+            .entries
+            .iter()
+            .find(|entry| entry.name.eq_ignore_ascii_case(name))
+        {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+
+        let bytes = self.bytes;
+        let offset = entry.offset;
+
+        if read_u32(bytes, offset) != Some(LOCAL_FILE_HEADER) {
+            return Err(XlsxError::InvalidArchive);
+        }
+
+        let name_length = read_u16(bytes, offset + 26).ok_or(XlsxError::InvalidArchive)?;
+        let extra_length = read_u16(bytes, offset + 28).ok_or(XlsxError::InvalidArchive)?;
+        let start = offset + 30 + usize::from(name_length) + usize::from(extra_length);
+        let data = bytes
+            .get(start..start + entry.compressed_size)
+            .ok_or(XlsxError::InvalidArchive)?;
+
+        let contents = match entry.method {
+            STORED => data.to_vec(),
+            DEFLATED => inflate(data, entry.size).map_err(|_err| XlsxError::InvalidArchive)?,
+            method => return Err(XlsxError::UnsupportedCompression(method)),
+        };
+
+        if contents.len() != entry.size || crc32(&contents) != entry.crc32 {
+            return Err(XlsxError::InvalidArchive);
+        }
+
+        Ok(Some(contents))
+    }
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    let bytes = bytes.get(offset..offset + 2)?;
+
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset + 4)?;
+
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// The CRC-32 checksum ZIP archives keep of the contents of each entry.
+pub(super) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0_u32;
+
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+
+        for _bit in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_presenter::xlsx::tests::stored_zip;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn test_read() {
+        let bytes = stored_zip(&[("xl/workbook.xml", b"<workbook/>"), ("a.txt", b"")]);
+        let archive = Archive::new(&bytes).unwrap();
+
+        assert_eq!(
+            archive.read("XL/Workbook.xml").unwrap(),
+            Some(b"<workbook/>".to_vec())
+        );
+        assert_eq!(archive.read("a.txt").unwrap(), Some(vec![]));
+        assert_eq!(archive.read("b.txt").unwrap(), None);
+
+        // a corrupt entry
+        let mut corrupt = bytes.clone();
+        corrupt[30 + "xl/workbook.xml".len()] = b'[';
+        assert_eq!(
+            Archive::new(&corrupt).unwrap().read("xl/workbook.xml"),
+            Err(XlsxError::InvalidArchive)
+        );
+
+        assert!(matches!(
+            Archive::new(b"not a zip"),
+            Err(XlsxError::InvalidArchive)
+        ));
+    }
+
+    #[test]
+    fn test_read_deflated() {
+        // written by Python's zipfile with ZIP_DEFLATED
+        let bytes = [
+            0x50, 0x4b, 0x03, 0x04, 0x14, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x21, 0x58,
+            0x34, 0x2a, 0x6e, 0x5a, 0x07, 0x00, 0x00, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x05, 0x00,
+            0x00, 0x00, 0x61, 0x2e, 0x74, 0x78, 0x74, 0x4b, 0x4c, 0x4a, 0x4e, 0x84, 0x21, 0x00,
+            0x50, 0x4b, 0x01, 0x02, 0x14, 0x03, 0x14, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00,
+            0x21, 0x58, 0x34, 0x2a, 0x6e, 0x5a, 0x07, 0x00, 0x00, 0x00, 0x0c, 0x00, 0x00, 0x00,
+            0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x01,
+            0x00, 0x00, 0x00, 0x00, 0x61, 0x2e, 0x74, 0x78, 0x74, 0x50, 0x4b, 0x05, 0x06, 0x00,
+            0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x33, 0x00, 0x00, 0x00, 0x2a, 0x00, 0x00,
+            0x00, 0x00, 0x00,
+        ];
+        let archive = Archive::new(&bytes).unwrap();
+
+        assert_eq!(
+            archive.read("a.txt").unwrap(),
+            Some(b"abcabcabcabc".to_vec())
+        );
+
+        // an unsupported compression method
+        let mut bzip2 = bytes;
+        bzip2[8] = 12;
+        bzip2[52] = 12;
+        assert_eq!(
+            Archive::new(&bzip2).unwrap().read("a.txt"),
+            Err(XlsxError::UnsupportedCompression(12))
+        );
+    }
+}